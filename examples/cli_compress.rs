@@ -3,7 +3,7 @@
 
 #[tokio::main]
 async fn main() {
-    #[cfg(features = "deflate")]
+    #[cfg(feature = "deflate")]
     if let Err(err) = inner::run().await {
         eprintln!("Error: {}", err);
         eprintln!("Usage: cli_compress <input file or directory> <output ZIP file name>");
@@ -11,7 +11,7 @@ async fn main() {
     }
 }
 
-#[cfg(features = "deflate")]
+#[cfg(feature = "deflate")]
 mod inner {
 
     use async_zip::write::ZipFileWriter;
@@ -23,7 +23,7 @@ mod inner {
     use tokio::fs::File;
     use tokio::io::AsyncReadExt;
 
-    async fn run() -> Result<()> {
+    pub async fn run() -> Result<()> {
         let mut args = std::env::args().skip(1);
 
         let input_str = args.next().ok_or(anyhow!("No input file or directory specified."))?;
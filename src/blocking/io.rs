@@ -0,0 +1,74 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+
+/// A wrapper which implements tokio's IO traits over implementers of the standard library's IO traits.
+///
+/// Every poll performs the underlying blocking call directly and so is always ready. This is only suitable when driven
+/// by the facade's own runtime, where nothing else is waiting to be scheduled on the thread.
+pub(crate) struct SyncIo<T> {
+    inner: T,
+    seek: Option<std::io::Result<u64>>,
+}
+
+// The inner value is never pinned, so the wrapper can always be moved freely.
+impl<T> Unpin for SyncIo<T> {}
+
+impl<T> SyncIo<T> {
+    pub(crate) fn new(inner: T) -> Self {
+        Self { inner, seek: None }
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub(crate) fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<R: Read> AsyncRead for SyncIo<R> {
+    fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let read = self.get_mut().inner.read(buf.initialize_unfilled())?;
+        buf.advance(read);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: Seek> AsyncSeek for SyncIo<S> {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        let this = self.get_mut();
+        this.seek = Some(this.inner.seek(position));
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+
+        // tokio polls for completion before any seek has been started (eg. to flush buffered readers).
+        match this.seek.take() {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Ready(this.inner.stream_position()),
+        }
+    }
+}
+
+impl<W: Write> AsyncWrite for SyncIo<W> {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(self.get_mut().inner.write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(self.get_mut().inner.flush())
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(self.get_mut().inner.flush())
+    }
+}
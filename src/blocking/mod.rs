@@ -0,0 +1,219 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A synchronous facade over the asynchronous ZIP reader and writer.
+//!
+//! Each reader or writer owns a lightweight, single-threaded tokio runtime which drives the same parsing & writing code
+//! to completion on the calling thread. The standard library's [`Read`], [`Seek`], and [`Write`] traits are used in
+//! place of their asynchronous counterparts, so synchronous applications (such as CLI tools) don't need to set up tokio
+//! themselves. Note that this requires the `blocking` feature.
+//!
+//! As with any blocking call, these types must not be used from within an asynchronous context (doing so panics).
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::blocking::{ZipFileReader, ZipFileWriter};
+//! # use async_zip::error::Result;
+//! # use async_zip::{Compression, ZipEntryBuilder};
+//! # use std::fs::File;
+//! #
+//! fn run() -> Result<()> {
+//!     let mut writer = ZipFileWriter::new(File::create("./foo.zip")?)?;
+//!     writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored), b"bar")?;
+//!     writer.close()?;
+//!
+//!     let mut reader = ZipFileReader::new(File::open("./foo.zip")?)?;
+//!     let entry = reader.file().entries()[0].entry().clone();
+//!
+//!     let mut data = String::new();
+//!     reader.entry(0)?.read_to_string_checked(&mut data, &entry)?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+pub(crate) mod io;
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::file::ZipFile;
+use crate::read::ReaderOptions;
+use io::SyncIo;
+
+use std::io::{Read, Seek, Write};
+
+use tokio::io::AsyncReadExt;
+use tokio::runtime::{Builder, Runtime};
+
+fn runtime() -> Result<Runtime> {
+    let mut builder = Builder::new_current_thread();
+
+    // Required by the open timeout within the reader options.
+    #[cfg(feature = "time")]
+    builder.enable_time();
+
+    Ok(builder.build()?)
+}
+
+/// A synchronous ZIP reader which acts over a seekable source.
+pub struct ZipFileReader<R> {
+    runtime: Runtime,
+    inner: crate::read::seek::ZipFileReader<SyncIo<R>>,
+}
+
+impl<R> ZipFileReader<R>
+where
+    R: Read + Seek,
+{
+    /// Constructs a new ZIP reader from a seekable source.
+    pub fn new(reader: R) -> Result<ZipFileReader<R>> {
+        Self::with_options(reader, ReaderOptions::default())
+    }
+
+    /// Constructs a new ZIP reader from a seekable source and a set of reader options.
+    pub fn with_options(reader: R, options: ReaderOptions) -> Result<ZipFileReader<R>> {
+        let runtime = runtime()?;
+        let inner = runtime.block_on(crate::read::seek::ZipFileReader::with_options(SyncIo::new(reader), options))?;
+
+        Ok(ZipFileReader { runtime, inner })
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        self.inner.file()
+    }
+
+    /// Returns a mutable reference to the inner seekable source.
+    ///
+    /// Swapping the source (eg. via std::mem operations) may cause the reader to become invalid.
+    pub fn inner_mut(&mut self) -> &mut R {
+        self.inner.inner_mut().get_mut()
+    }
+
+    /// Returns the inner seekable source by consuming self.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner().into_inner()
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub fn entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R>> {
+        let reader = self.runtime.block_on(self.inner.entry(index))?;
+        Ok(ZipEntryReader { runtime: &self.runtime, reader })
+    }
+}
+
+/// A synchronous reader over a single entry's data, which decompresses it as it's read.
+pub struct ZipEntryReader<'a, R> {
+    runtime: &'a Runtime,
+    reader: crate::read::io::entry::ZipEntryReader<'a, SyncIo<R>>,
+}
+
+impl<'a, R> ZipEntryReader<'a, R>
+where
+    R: Read,
+{
+    /// Sets the max number of bytes which a single checked read may read into memory.
+    pub fn allocation_limit(&mut self, limit: u64) {
+        self.reader.allocation_limit(limit);
+    }
+
+    /// Returns the CRC32 value of the uncompressed bytes which have been read from this reader so far.
+    pub fn crc32(&self) -> u32 {
+        self.reader.crc32()
+    }
+
+    /// Returns the number of compressed bytes which have been consumed from the underlying reader so far.
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.reader.compressed_bytes_read()
+    }
+
+    /// Returns the number of uncompressed bytes which have been read from this reader so far.
+    pub fn uncompressed_bytes_read(&self) -> u64 {
+        self.reader.uncompressed_bytes_read()
+    }
+
+    /// Reads all bytes until EOF has been reached, appending them to buf, and verifies the CRC32 values.
+    pub fn read_to_end_checked(&mut self, buf: &mut Vec<u8>, entry: &ZipEntry) -> Result<usize> {
+        self.runtime.block_on(self.reader.read_to_end_checked(buf, entry))
+    }
+
+    /// Reads all bytes until EOF has been reached, placing them into buf, and verifies the CRC32 values.
+    pub fn read_to_string_checked(&mut self, buf: &mut String, entry: &ZipEntry) -> Result<usize> {
+        self.runtime.block_on(self.reader.read_to_string_checked(buf, entry))
+    }
+
+    /// Copies all bytes until EOF has been reached into a writer, and verifies the CRC32 values.
+    ///
+    /// Returns the number of bytes copied.
+    pub fn copy_to_end_checked<W>(&mut self, writer: &mut W, entry: &ZipEntry) -> Result<u64>
+    where
+        W: Write,
+    {
+        self.runtime.block_on(self.reader.copy_to_end_checked(&mut SyncIo::new(writer), entry))
+    }
+}
+
+impl<'a, R> Read for ZipEntryReader<'a, R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.runtime.block_on(self.reader.read(buf))
+    }
+}
+
+/// A synchronous ZIP writer which acts over a writable destination.
+pub struct ZipFileWriter<W: Write> {
+    runtime: Runtime,
+    inner: crate::write::ZipFileWriter<SyncIo<W>>,
+}
+
+impl<W> ZipFileWriter<W>
+where
+    W: Write,
+{
+    /// Constructs a new ZIP writer from a writable destination.
+    pub fn new(writer: W) -> Result<ZipFileWriter<W>> {
+        Ok(ZipFileWriter { runtime: runtime()?, inner: crate::write::ZipFileWriter::new(SyncIo::new(writer)) })
+    }
+
+    /// Write a new ZIP entry of known size and data.
+    pub fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
+        self.runtime.block_on(self.inner.write_entry_whole(entry, data))
+    }
+
+    /// Write a new directory entry.
+    pub fn write_dir_entry<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<()> {
+        self.runtime.block_on(self.inner.write_dir_entry(entry))
+    }
+
+    /// Write an entry by streaming its data from a reader until EOF (ie. using a data descriptor).
+    ///
+    /// Returns the number of uncompressed bytes written.
+    pub fn write_entry_from_reader<E, R>(&mut self, entry: E, reader: R) -> Result<u64>
+    where
+        E: Into<ZipEntry>,
+        R: Read,
+    {
+        self.runtime.block_on(self.inner.write_entry_from_reader(entry, SyncIo::new(reader)))
+    }
+
+    /// Set the ZIP file comment.
+    pub fn comment(&mut self, comment: String) {
+        self.inner.comment(comment);
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Care should be taken when using this inner writer as doing so may invalidate internal state of this writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        self.inner.inner_mut().get_mut()
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks.
+    ///
+    /// Failiure to call this function before going out of scope would result in a corrupted ZIP file.
+    pub fn close(self) -> Result<W> {
+        Ok(self.runtime.block_on(self.inner.close())?.into_inner())
+    }
+}
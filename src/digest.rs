@@ -0,0 +1,162 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Computing additional digests (eg. SHA-256 or BLAKE3) of an entry's data whilst it's read or written.
+//!
+//! Any implementer of [`ContentDigest`] (including closures accepting a byte slice) may be attached to an entry reader
+//! or stream writer via [`Digested`]. Digests are always computed over the uncompressed data, in the same pass as the
+//! (de)compression, so content hashes for manifests or signatures never require the data to be re-read.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::digest::Digested;
+//! # use async_zip::error::Result;
+//! # use async_zip::read::seek::ZipFileReader;
+//! # use tokio::fs::File;
+//! #
+//! # async fn run() -> Result<()> {
+//! # let mut file = File::open("./foo.zip").await?;
+//! let mut reader = ZipFileReader::new(&mut file).await?;
+//!
+//! let mut length = 0;
+//! let mut entry = reader.entry(0).await?.digested(|data: &[u8]| length += data.len());
+//! tokio::io::copy(&mut entry, &mut tokio::io::sink()).await?;
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::read::io::entry::ZipEntryReader;
+use crate::write::EntryStreamWriter;
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// A digest which is updated with an entry's uncompressed data as it's read or written.
+pub trait ContentDigest {
+    /// Updates this digest with the next chunk of data.
+    fn update(&mut self, data: &[u8]);
+}
+
+impl<F> ContentDigest for F
+where
+    F: FnMut(&[u8]),
+{
+    fn update(&mut self, data: &[u8]) {
+        self(data)
+    }
+}
+
+impl<A, B> ContentDigest for (A, B)
+where
+    A: ContentDigest,
+    B: ContentDigest,
+{
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+        self.1.update(data);
+    }
+}
+
+/// A reader or writer which updates a [`ContentDigest`] with all data read or written through it.
+pub struct Digested<T, D> {
+    inner: T,
+    digest: D,
+}
+
+impl<T, D> Digested<T, D>
+where
+    D: ContentDigest,
+{
+    /// Constructs a new wrapper around the provided reader or writer and digest.
+    pub fn new(inner: T, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    /// Returns a reference to the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Returns a mutable reference to the inner reader or writer.
+    ///
+    /// Any data read or written via this reference directly won't be reflected within the digest.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper and returns the inner reader or writer along with the digest.
+    pub fn into_parts(self) -> (T, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<T, D> AsyncRead for Digested<T, D>
+where
+    T: AsyncRead + Unpin,
+    D: ContentDigest + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let prev_len = b.filled().len();
+
+        ready!(Pin::new(&mut this.inner).poll_read(c, b))?;
+        this.digest.update(&b.filled()[prev_len..]);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T, D> AsyncWrite for Digested<T, D>
+where
+    T: AsyncWrite + Unpin,
+    D: ContentDigest + Unpin,
+{
+    fn poll_write(self: Pin<&mut Self>, c: &mut Context<'_>, b: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let written = ready!(Pin::new(&mut this.inner).poll_write(c, b))?;
+
+        this.digest.update(&b[..written]);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(c)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(c)
+    }
+}
+
+impl<'a, R> ZipEntryReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Attaches a digest to this entry reader, which is updated with the entry's data as it's read.
+    pub fn digested<D: ContentDigest>(self, digest: D) -> Digested<Self, D> {
+        Digested::new(self, digest)
+    }
+}
+
+impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
+    /// Attaches a digest to this entry writer, which is updated with the entry's data as it's written.
+    pub fn digested<D: ContentDigest>(self, digest: D) -> Digested<Self, D> {
+        Digested::new(self, digest)
+    }
+}
+
+impl<'b, W, D> Digested<EntryStreamWriter<'b, W>, D>
+where
+    W: AsyncWrite + Unpin,
+    D: ContentDigest,
+{
+    /// Completes the entry (see [`EntryStreamWriter::close()`]) and returns the digest.
+    pub async fn close(self) -> Result<D> {
+        self.inner.close().await?;
+        Ok(self.digest)
+    }
+}
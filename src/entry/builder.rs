@@ -2,9 +2,47 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::entry::ZipEntry;
-use crate::spec::attribute::AttributeCompatibility;
-use crate::spec::compression::{Compression, DeflateOption};
+use crate::error::Result;
+use crate::spec::attribute::{AttributeCompatibility, Attributes};
+use crate::spec::compression::Compression;
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+use crate::spec::compression::{CompressionLevel, DeflateOption};
 use crate::spec::date::ZipDateTime;
+use crate::spec::encryption::Encryption;
+use crate::spec::extra_field::ExtraField;
+use crate::spec::filename::{FilenamePolicy, Utf8Flag};
+
+use std::fs::Metadata;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A value from which an entry's last modification date can be set (see [`ZipEntryBuilder::last_modification_date()`]).
+pub trait IntoModificationDate {
+    /// Returns the MS-DOS date & time, along with the precise Unix timestamp (in seconds) where one is known.
+    ///
+    /// None is returned if no modification date is available.
+    fn into_modification_date(self) -> Option<(ZipDateTime, Option<i64>)>;
+}
+
+impl IntoModificationDate for ZipDateTime {
+    fn into_modification_date(self) -> Option<(ZipDateTime, Option<i64>)> {
+        Some((self, None))
+    }
+}
+
+impl IntoModificationDate for SystemTime {
+    fn into_modification_date(self) -> Option<(ZipDateTime, Option<i64>)> {
+        let seconds = self.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default();
+        let seconds = i64::try_from(seconds).unwrap_or(i64::MAX);
+
+        Some((ZipDateTime::from_unix_timestamp(seconds), Some(seconds)))
+    }
+}
+
+impl IntoModificationDate for &Metadata {
+    fn into_modification_date(self) -> Option<(ZipDateTime, Option<i64>)> {
+        self.modified().ok()?.into_modification_date()
+    }
+}
 
 /// A builder for [`ZipEntry`].
 pub struct ZipEntryBuilder(pub(crate) ZipEntry);
@@ -29,6 +67,23 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets the policy used to validate & normalise the entry's filename.
+    ///
+    /// The policy is applied when the entry is written, or when the builder is consumed via
+    /// [`ZipEntryBuilder::try_build()`].
+    pub fn filename_policy(mut self, policy: FilenamePolicy) -> Self {
+        self.0.filename_policy = policy;
+        self
+    }
+
+    /// Sets whether the UTF-8 language encoding flag is set when the entry is written (see [`Utf8Flag`]).
+    ///
+    /// By default, the flag is only set if the entry's filename or comment contains non-ASCII characters.
+    pub fn utf8_flag(mut self, flag: Utf8Flag) -> Self {
+        self.0.utf8_flag = flag;
+        self
+    }
+
     /// Sets the entry's compression method.
     pub fn compression(mut self, compression: Compression) -> Self {
         self.0.compression = compression;
@@ -44,6 +99,16 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets the level of compression used when writing the entry.
+    ///
+    /// This applies to every compression method other than [`Compression::Stored`], with precise levels being
+    /// interpreted by the chosen method (see [`CompressionLevel`]).
+    #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    pub fn compression_level(mut self, level: CompressionLevel) -> Self {
+        self.0.compression_level = level.into_level();
+        self
+    }
+
     /// Sets the entry's attribute host compatibility.
     pub fn attribute_compatibility(mut self, compatibility: AttributeCompatibility) -> Self {
         self.0.attribute_compatibility = compatibility;
@@ -51,8 +116,58 @@ impl ZipEntryBuilder {
     }
 
     /// Sets the entry's last modification date.
-    pub fn last_modification_date(mut self, date: ZipDateTime) -> Self {
-        self.0.last_modification_date = date;
+    ///
+    /// This accepts a [`ZipDateTime`], a [`SystemTime`], or a file's [`Metadata`] (eg. as returned by
+    /// [`tokio::fs::metadata()`]). Where a precise time is provided, it's retained so that it may be written within an
+    /// extended timestamp extra field (see [`ZipEntryBuilder::extended_timestamp()`]). If the metadata doesn't hold a
+    /// modification time (as some platforms don't record one), the date is left unchanged.
+    ///
+    /// [`tokio::fs::metadata()`]: https://docs.rs/tokio/latest/tokio/fs/fn.metadata.html
+    pub fn last_modification_date(mut self, date: impl IntoModificationDate) -> Self {
+        if let Some((date, seconds)) = date.into_modification_date() {
+            self.0.last_modification_date = date;
+            self.0.unix_modification_time = seconds;
+        }
+        self
+    }
+
+    /// Sets the entry's last modification time, with a precision of one second.
+    ///
+    /// This sets the entry's MS-DOS last modification date (as UTC), and retains the precise time so that it may be
+    /// written within an extended timestamp extra field (see [`ZipEntryBuilder::extended_timestamp()`]). Times before
+    /// the Unix epoch are treated as the epoch itself.
+    pub fn last_modification_time(self, time: SystemTime) -> Self {
+        self.last_modification_date(time)
+    }
+
+    /// Sets whether an extended timestamp extra field (0x5455) should be written alongside the MS-DOS date.
+    ///
+    /// This holds the entry's last modification time as a timezone-independent Unix timestamp with a precision of one
+    /// second. It's only written if the time was set via [`ZipEntryBuilder::last_modification_time()`], and the entry
+    /// doesn't already hold an extended timestamp extra field.
+    pub fn extended_timestamp(mut self, value: bool) -> Self {
+        self.0.extended_timestamp = value;
+        self
+    }
+
+    /// Sets the alignment (in bytes) of the entry's data within the archive.
+    ///
+    /// When written, the local file header is padded with an extra field so that the data starts at an offset which
+    /// is a multiple of this value (eg. 4 for Android APK assets, or 4096 for page-aligned memory mapping). This only
+    /// applies to entries using [`Compression::Stored`], and values of 0 or 1 disable alignment.
+    pub fn alignment(mut self, alignment: u16) -> Self {
+        self.0.alignment = alignment;
+        self
+    }
+
+    /// Sets whether the compression method should be chosen automatically when the entry is written.
+    ///
+    /// When set, the method provided to this builder is treated as the preferred method, but [`Compression::Stored`]
+    /// is used instead if the filename has the extension of an already-compressed format (eg. `png`, `zip`, or `mp4`).
+    /// For entries of known size and data, a sample of the data is also checked and stored as-is if it appears to be
+    /// incompressible.
+    pub fn auto_compression(mut self, value: bool) -> Self {
+        self.0.auto_compression = value;
         self
     }
 
@@ -74,18 +189,80 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Appends a structured extra field to the entry's extra field data.
+    ///
+    /// The field is written within both the local file header and the central directory. The data of a field is
+    /// limited to 65535 bytes (as is the entry's extra field data as a whole).
+    pub fn add_extra_field(mut self, field: ExtraField) -> Self {
+        self.0.extra_field.extend(field.to_bytes());
+        self
+    }
+
     /// Sets the entry's file comment.
+    ///
+    /// This is only stored within the central directory, so isn't available to the stream reader.
     pub fn comment(mut self, comment: String) -> Self {
         self.0.comment = comment;
         self
     }
 
+    /// Sets whether the entry should always be written using ZIP64 structures, regardless of its size.
+    ///
+    /// This is mainly useful when streaming an entry which may exceed 4 GiB, as its size isn't known whilst its local
+    /// file header is written. When set, a ZIP64 extra field is reserved within that header, and a data descriptor
+    /// holding 8-byte sizes is written. Otherwise, ZIP64 structures are only used where they're required.
+    pub fn force_zip64(mut self, force: bool) -> Self {
+        self.0.force_zip64 = force;
+        self
+    }
+
+    /// Sets the entry's CRC32 value.
+    ///
+    /// This is only used by [`CentralDirectoryWriter`], as the value is otherwise computed when the entry is written.
+    ///
+    /// [`CentralDirectoryWriter`]: crate::write::CentralDirectoryWriter
+    pub fn crc32(mut self, crc: u32) -> Self {
+        self.0.crc32 = crc;
+        self
+    }
+
+    /// Sets the entry's uncompressed & compressed sizes.
+    ///
+    /// This is only used by [`CentralDirectoryWriter`], as the sizes are otherwise computed when the entry is written.
+    ///
+    /// [`CentralDirectoryWriter`]: crate::write::CentralDirectoryWriter
+    pub fn sizes(mut self, uncompressed_size: u64, compressed_size: u64) -> Self {
+        self.0.uncompressed_size = uncompressed_size;
+        self.0.compressed_size = compressed_size;
+        self
+    }
+
+    /// Sets the method used to encrypt the entry's data when written.
+    ///
+    /// The data is encrypted after it has been compressed, and the entry's metadata (eg. its filename) is left as-is.
+    pub fn encryption(mut self, encryption: Encryption) -> Self {
+        self.0.encryption = Some(encryption);
+        self
+    }
+
+    /// Sets the entry's external file attribute from a typed set of attributes.
+    ///
+    /// The raw attribute is derived using the entry's current attribute host compatibility, so this should be called
+    /// after [`ZipEntryBuilder::attribute_compatibility()`]. Any Unix mode is only stored with Unix compatibility.
+    pub fn attributes(mut self, attributes: Attributes) -> Self {
+        self.0.external_file_attribute = attributes.to_raw(self.0.attribute_compatibility);
+        self
+    }
+
     /// Sets the entry's Unix permissions mode.
     ///
+    /// The mode is stored within the upper 16 bits of the external file attribute (so only the file type & permission
+    /// bits are kept), which allows a value from [`std::os::unix::fs::PermissionsExt::mode()`] to be passed directly.
+    ///
     /// If the attribute host compatability isn't set to Unix, this will have no effect.
-    pub fn unix_permissions(mut self, mode: u16) -> Self {
+    pub fn unix_permissions(mut self, mode: u32) -> Self {
         if matches!(self.0.attribute_compatibility, AttributeCompatibility::Unix) {
-            self.0.external_file_attribute = (self.0.external_file_attribute & 0xFFFF) | (mode as u32) << 16;
+            self.0.external_file_attribute = (self.0.external_file_attribute & 0xFFFF) | (mode & 0xFFFF) << 16;
         }
         self
     }
@@ -102,4 +279,14 @@ impl ZipEntryBuilder {
     pub fn build(self) -> ZipEntry {
         self.into()
     }
+
+    /// Consumes this builder and returns a final [`ZipEntry`], applying its filename policy upfront.
+    ///
+    /// This allows an invalid filename to be caught before the entry is written (at which point the policy would
+    /// otherwise be applied).
+    pub fn try_build(mut self) -> Result<ZipEntry> {
+        self.0.filename_policy.apply(&mut self.0.filename)?;
+        self.0.utf8_flag.check(&self.0.filename, &self.0.comment)?;
+        Ok(self.0)
+    }
 }
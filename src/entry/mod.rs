@@ -8,33 +8,59 @@ use std::io::SeekFrom;
 use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt};
 
 use crate::entry::builder::ZipEntryBuilder;
-use crate::error::Result;
-use crate::spec::attribute::AttributeCompatibility;
+use crate::error::{Result, ZipError};
+use crate::spec::attribute::{AttributeCompatibility, Attributes};
 use crate::spec::compression::Compression;
+use crate::spec::consts::{LFH_LENGTH, LFH_SIGNATURE, SIGNATURE_LENGTH};
 use crate::spec::date::ZipDateTime;
-use crate::spec::header::LocalFileHeader;
-// use crate::spec::header::GeneralPurposeFlag;
+use crate::spec::encryption::Encryption;
+use crate::spec::filename::{FilenamePolicy, Utf8Flag};
+use crate::spec::header::{GeneralPurposeFlag, LocalFileHeader};
 
 /// An immutable store of data about a ZIP entry.
 ///
 /// This type cannot be directly constructed so instead, the [`ZipEntryBuilder`] must be used. Internally this builder
 /// stores a [`ZipEntry`] so conversions between these two types via the [`From`] implementations will be
 /// non-allocating.
+///
+/// With the `serde` feature, this type may be serialised. Options which only apply when an entry is written (ie. its
+/// compression level, encryption method, filename policy, and UTF-8 flag policy) are skipped, so aren't retained.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ZipEntry {
     pub(crate) filename: String,
     pub(crate) compression: Compression,
     #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_compression_level"))]
     pub(crate) compression_level: async_compression::Level,
     pub(crate) crc32: u32,
-    pub(crate) uncompressed_size: u32,
-    pub(crate) compressed_size: u32,
+    pub(crate) uncompressed_size: u64,
+    pub(crate) compressed_size: u64,
     pub(crate) attribute_compatibility: AttributeCompatibility,
     pub(crate) last_modification_date: ZipDateTime,
     pub(crate) internal_file_attribute: u16,
     pub(crate) external_file_attribute: u32,
     pub(crate) extra_field: Vec<u8>,
     pub(crate) comment: String,
+    pub(crate) force_zip64: bool,
+    pub(crate) unix_modification_time: Option<i64>,
+    pub(crate) extended_timestamp: bool,
+    pub(crate) alignment: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) encryption: Option<Encryption>,
+    pub(crate) auto_compression: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) filename_policy: FilenamePolicy,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) utf8_flag: Utf8Flag,
+}
+
+#[cfg(all(
+    feature = "serde",
+    any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz")
+))]
+fn default_compression_level() -> async_compression::Level {
+    async_compression::Level::Default
 }
 
 impl From<ZipEntryBuilder> for ZipEntry {
@@ -59,6 +85,14 @@ impl ZipEntry {
             external_file_attribute: 0,
             extra_field: Vec::new(),
             comment: String::new(),
+            force_zip64: false,
+            unix_modification_time: None,
+            extended_timestamp: false,
+            alignment: 0,
+            encryption: None,
+            auto_compression: false,
+            filename_policy: FilenamePolicy::default(),
+            utf8_flag: Utf8Flag::default(),
         }
     }
 
@@ -83,12 +117,12 @@ impl ZipEntry {
     }
 
     /// Returns the entry's uncompressed size.
-    pub fn uncompressed_size(&self) -> u32 {
+    pub fn uncompressed_size(&self) -> u64 {
         self.uncompressed_size
     }
 
     /// Returns the entry's compressed size.
-    pub fn compressed_size(&self) -> u32 {
+    pub fn compressed_size(&self) -> u64 {
         self.compressed_size
     }
 
@@ -134,20 +168,48 @@ impl ZipEntry {
         Some(((self.external_file_attribute) >> 16) as u16)
     }
 
+    /// Returns a typed view of the entry's external file attribute, parsed according to its attribute host
+    /// compatibility.
+    pub fn attributes(&self) -> Attributes {
+        Attributes::from_raw(self.attribute_compatibility, self.external_file_attribute)
+    }
+
     /// Returns whether or not the entry represents a directory.
     pub fn dir(&self) -> bool {
         self.filename.ends_with('/')
     }
+
+    /// Returns whether or not the entry represents a symbolic link (in which case, its data is the link's target).
+    ///
+    /// # Note
+    /// This will always return false if the attribute host compatibility is not listed as Unix.
+    pub fn symlink(&self) -> bool {
+        self.unix_permissions().map(|mode| mode & 0o170000 == 0o120000).unwrap_or(false)
+    }
+
+    /// Returns whether or not the entry is macOS metadata rather than actual content.
+    ///
+    /// This includes anything within a `__MACOSX/` directory, `.DS_Store` files, and AppleDouble `._*` files (as added
+    /// by Finder's archiver), which most consumers of user-uploaded ZIP files want to skip.
+    pub fn macos_metadata(&self) -> bool {
+        crate::utils::is_macos_metadata(&self.filename)
+    }
 }
 
 /// Stores information about a Zip entry inside of an archive. Besides storing archive independant
 /// information like the size and timestamp it can also be used to query information about how the
 /// entry is stored in an archive.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StoredZipEntry {
     pub(crate) entry: ZipEntry,
-    // pub(crate) general_purpose_flag: GeneralPurposeFlag,
+    pub(crate) general_purpose_flag: GeneralPurposeFlag,
+    // The method held within the central directory, which may differ from the entry's (eg. if it's AES-encrypted).
+    pub(crate) compression_method: u16,
     pub(crate) file_offset: u64,
+    pub(crate) disk_start: u16,
+    // Only held if the central directory is trusted, in which case it's used over reading the local file header.
+    pub(crate) data_offset: Option<u64>,
 }
 
 impl StoredZipEntry {
@@ -161,16 +223,74 @@ impl StoredZipEntry {
         self.file_offset
     }
 
+    /// Returns the raw compression method held within the central directory.
+    ///
+    /// If this method isn't supported by this crate, the inner entry's compression is instead [`Compression::Stored`]
+    /// and [`ZipError::FeatureNotSupported`] is returned when its data is read. The actual method of an AES-encrypted
+    /// entry is taken from its AES extra field.
+    pub fn compression_method(&self) -> u16 {
+        self.compression_method
+    }
+
+    /// Returns the raw number of the disk on which this entry's local file header starts.
+    ///
+    /// As with [`ZipFile::disk_number()`](crate::ZipFile::disk_number), this value is ignored when reading.
+    pub fn disk_start(&self) -> u16 {
+        self.disk_start
+    }
+
     /// Seek to the offset in bytes where the data of the entry starts.
-    pub async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<()> {
-        // Seek to the header
-        reader.seek(SeekFrom::Start(self.file_offset)).await?;
+    ///
+    /// Any error is returned along with the offset of the entry's local file header.
+    pub async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, reader: &mut R) -> Result<()> {
+        self.seek_to_data(reader).await.map(|_| ())
+    }
+
+    /// Seek to the offset in bytes where the data of the entry starts, as with [`Self::seek_to_data_offset()`], but
+    /// return [`ZipError::FeatureNotSupported`](crate::error::ZipError::FeatureNotSupported) if the entry's data can't be read by this crate (eg. if it's
+    /// encrypted).
+    pub(crate) async fn seek_to_readable_data<R: AsyncRead + AsyncSeek + Unpin>(&self, reader: &mut R) -> Result<()> {
+        if let Some(data_offset) = self.data_offset {
+            self.general_purpose_flag.check_supported()?;
+            self.check_compression()?;
+            reader.seek(SeekFrom::Start(data_offset)).await?;
+            return Ok(());
+        }
+
+        let header = self.seek_to_data(reader).await?;
+        header.flags.check_supported()?;
+        self.check_compression()
+    }
+
+    /// Returns an error if this entry's compression method isn't supported by this crate.
+    fn check_compression(&self) -> Result<()> {
+        match Compression::try_from(self.compression_method) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(ZipError::FeatureNotSupported("Compression method")),
+        }
+    }
+
+    /// Sets the offset at which this entry's data starts from the lengths of its central directory record's filename and
+    /// extra field, so that its local file header needn't be read when seeking to its data.
+    pub(crate) fn trust_central_directory(&mut self) {
+        let header_length = SIGNATURE_LENGTH + LFH_LENGTH + self.entry.filename.len() + self.entry.extra_field.len();
+        self.data_offset = Some(self.file_offset + header_length as u64);
+    }
+
+    async fn seek_to_data<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<LocalFileHeader> {
+        let seek = async {
+            // Seek to the header
+            reader.seek(SeekFrom::Start(self.file_offset)).await?;
+
+            // Skip the local file header and trailing data
+            crate::utils::assert_signature(&mut reader, LFH_SIGNATURE).await?;
+            let header = LocalFileHeader::from_reader(&mut reader).await?;
+            let trailing_length = usize::from(header.file_name_length) + usize::from(header.extra_field_length);
+            crate::read::io::skip_bytes(&mut reader, trailing_length).await?;
 
-        // Skip the local file header and trailing data
-        let header = LocalFileHeader::from_reader(&mut reader).await?;
-        let _filename = crate::read::io::read_string(&mut reader, header.file_name_length.into()).await?;
-        let _extra_field = crate::read::io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
+            Result::Ok(header)
+        };
 
-        Ok(())
+        seek.await.map_err(|err| err.at_offset(self.file_offset))
     }
 }
@@ -23,13 +23,161 @@ pub enum ZipError {
 
     #[error("unable to locate the end of central directory record")]
     UnableToLocateEOCDR,
+    #[error("found {0} bytes of trailing data after the end of central directory record")]
+    TrailingData(u64),
+    #[error("opening the ZIP file didn't complete within the configured timeout ({0:?})")]
+    OpenTimedOut(std::time::Duration),
 
     #[error("an upstream reader returned an error: {0}")]
-    UpstreamReadError(#[from] std::io::Error),
+    UpstreamReadError(#[source] std::io::Error),
     #[error("a computed CRC32 value did not match the expected value")]
     CRC32CheckError,
+    #[error("attempted to finish reading an entry before EOF was reached")]
+    EntryNotFullyRead,
     #[error("entry index was out of bounds")]
     EntryIndexOutOfBounds,
+    #[error("the central directory declared more than the limit of {0} entries")]
+    EntryLimitExceeded(u64),
+    #[error("an entry's data exceeded the allocation limit of {0} bytes")]
+    AllocationLimitExceeded(u64),
+    #[error("an entry's filename {1}: '{0}'")]
+    InvalidFilename(String, &'static str),
+    #[error("a comment exceeded the maximum length of 65535 bytes")]
+    CommentTooLong,
+    #[error("an entry's path would escape the destination directory: '{0}'")]
+    UnsafeEntryPath(String),
+    #[error("an entry's path already exists within the destination directory: '{0}'")]
+    EntryPathExists(String),
+    #[error("an entry's filename is held by multiple entries: '{0}'")]
+    DuplicateFilename(String),
+    #[error("a split archive's volume size must be between 64KiB and 4GiB (got {0} bytes)")]
+    InvalidVolumeSize(u64),
+    #[error("the operation was cancelled")]
+    Cancelled,
+    #[error("a tar header was invalid: {0}")]
+    InvalidTarHeader(&'static str),
     #[error("Encountered an unexpected header (actual: {0:#x}, expected: {1:#x}).")]
     UnexpectedHeaderError(u32, u32),
+
+    #[error("{error} (at byte offset {offset})")]
+    AtOffset {
+        offset: u64,
+        #[source]
+        error: Box<ZipError>,
+    },
+    #[error("{error} in entry {}", describe_entry(*.index, .filename))]
+    InEntry {
+        index: Option<usize>,
+        filename: String,
+        #[source]
+        error: Box<ZipError>,
+    },
+}
+
+fn describe_entry(index: Option<usize>, filename: &str) -> String {
+    match index {
+        Some(index) => format!("{index} '{filename}'"),
+        None => format!("'{filename}'"),
+    }
+}
+
+impl ZipError {
+    /// Returns the underlying error, without any of the context (ie. byte offset or entry) it was wrapped within.
+    ///
+    /// This should be preferred when matching against a specific error.
+    pub fn kind(&self) -> &ZipError {
+        match self {
+            ZipError::AtOffset { error, .. } | ZipError::InEntry { error, .. } => error.kind(),
+            error => error,
+        }
+    }
+
+    /// Returns the IO error which caused this error, if any.
+    ///
+    /// This allows callers to distinguish between (for example) [`std::io::ErrorKind::UnexpectedEof`] for a truncated
+    /// archive and [`std::io::ErrorKind::PermissionDenied`], and to retry where appropriate.
+    pub fn io_error(&self) -> Option<&std::io::Error> {
+        match self.kind() {
+            ZipError::UpstreamReadError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns the kind of the IO error which caused this error, if any.
+    pub fn io_error_kind(&self) -> Option<std::io::ErrorKind> {
+        self.io_error().map(std::io::Error::kind)
+    }
+
+    /// Returns the byte offset within the archive at which parsing failed, if known.
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            ZipError::AtOffset { offset, .. } => Some(*offset),
+            ZipError::InEntry { error, .. } => error.offset(),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the entry in which the error occurred, if known.
+    pub fn entry_index(&self) -> Option<usize> {
+        match self {
+            ZipError::InEntry { index: Some(index), .. } => Some(*index),
+            ZipError::AtOffset { error, .. } | ZipError::InEntry { error, .. } => error.entry_index(),
+            _ => None,
+        }
+    }
+
+    /// Returns the filename of the entry in which the error occurred, if known.
+    pub fn entry_filename(&self) -> Option<&str> {
+        match self {
+            ZipError::InEntry { filename, .. } => Some(filename),
+            ZipError::AtOffset { error, .. } => error.entry_filename(),
+            _ => None,
+        }
+    }
+
+    /// Wraps this error with the byte offset at which it occurred, unless it already holds one.
+    pub(crate) fn at_offset(self, offset: u64) -> ZipError {
+        match self.offset() {
+            Some(_) => self,
+            None => ZipError::AtOffset { offset, error: Box::new(self) },
+        }
+    }
+
+    /// Wraps this error with the entry in which it occurred.
+    ///
+    /// If the error already holds the entry's filename (eg. from a CRC32 check), only a missing index is filled in.
+    pub(crate) fn in_entry(self, index: Option<usize>, filename: &str) -> ZipError {
+        match self {
+            ZipError::AtOffset { offset, error } if error.entry_filename().is_some() => {
+                ZipError::AtOffset { offset, error: Box::new(error.in_entry(index, filename)) }
+            }
+            ZipError::InEntry { index: None, filename, error } => ZipError::InEntry { index, filename, error },
+            ZipError::InEntry { .. } => self,
+            error => ZipError::InEntry { index, filename: filename.to_string(), error: Box::new(error) },
+        }
+    }
+}
+
+impl From<std::io::Error> for ZipError {
+    fn from(error: std::io::Error) -> Self {
+        // A ZIP error may have been passed through an IO trait implementation (eg. an entry reader), so unwrap it
+        // rather than nesting it within another layer.
+        if error.get_ref().is_some_and(|inner| inner.is::<ZipError>()) {
+            let inner = error.into_inner().expect("inner error was present");
+            return *inner.downcast::<ZipError>().expect("inner error was a ZIP error");
+        }
+
+        ZipError::UpstreamReadError(error)
+    }
+}
+
+/// Converts a ZIP error into an IO error, preserving the kind of any underlying IO error.
+///
+/// The ZIP error itself is held as the inner error, so it can be recovered via [`std::io::Error::into_inner()`] (or by
+/// converting back into a [`ZipError`]).
+impl From<ZipError> for std::io::Error {
+    fn from(error: ZipError) -> Self {
+        let kind = error.io_error_kind().unwrap_or(std::io::ErrorKind::InvalidData);
+        std::io::Error::new(kind, error)
+    }
 }
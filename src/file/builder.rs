@@ -14,7 +14,14 @@ impl From<ZipFile> for ZipFileBuilder {
 
 impl Default for ZipFileBuilder {
     fn default() -> Self {
-        ZipFileBuilder(ZipFile { entries: Vec::new(), zip64: false, comment: String::new() })
+        ZipFileBuilder(ZipFile {
+            entries: Vec::new(),
+            zip64: false,
+            comment: String::new(),
+            warnings: Vec::new(),
+            disk_number: 0,
+            cent_dir_disk_number: 0,
+        })
     }
 }
 
@@ -0,0 +1,80 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::file::ZipFile;
+
+use std::collections::HashMap;
+
+/// The differences between the entries of two ZIP files, as returned by [`ZipFile::diff()`].
+///
+/// Entries are matched by filename. Where a ZIP file holds multiple entries with the same filename, only the last is
+/// compared (as most extractors treat it as the one which takes effect).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveDiff {
+    pub(crate) added: Vec<String>,
+    pub(crate) removed: Vec<String>,
+    pub(crate) changed: Vec<String>,
+}
+
+impl ArchiveDiff {
+    /// Returns whether both ZIP files hold the same entries.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Returns the filenames of entries which are only held by the other ZIP file.
+    pub fn added(&self) -> &[String] {
+        &self.added
+    }
+
+    /// Returns the filenames of entries which are only held by this ZIP file.
+    pub fn removed(&self) -> &[String] {
+        &self.removed
+    }
+
+    /// Returns the filenames of entries which are held by both ZIP files, but whose sizes, CRC32 values, or (where
+    /// compared) content differ.
+    pub fn changed(&self) -> &[String] {
+        &self.changed
+    }
+}
+
+/// Returns the pairs of entries which are held by both ZIP files (in the order of this file), and populates the diff's
+/// added & removed entries.
+pub(crate) fn matched<'a>(this: &'a ZipFile, other: &'a ZipFile, diff: &mut ArchiveDiff) -> Vec<(usize, usize)> {
+    let (this_map, other_map) = (last_indices(this), last_indices(other));
+    let mut matched = Vec::new();
+
+    for (index, stored_entry) in this.entries.iter().enumerate() {
+        let filename = stored_entry.entry.filename();
+
+        if this_map[filename] != index {
+            continue;
+        }
+
+        match other_map.get(filename) {
+            Some(other_index) => matched.push((index, *other_index)),
+            None => diff.removed.push(filename.to_string()),
+        }
+    }
+
+    for (index, stored_entry) in other.entries.iter().enumerate() {
+        let filename = stored_entry.entry.filename();
+
+        if other_map[filename] == index && !this_map.contains_key(filename) {
+            diff.added.push(filename.to_string());
+        }
+    }
+
+    matched
+}
+
+/// Returns whether two entries' metadata indicates that they hold the same data.
+pub(crate) fn same_metadata(this: &ZipEntry, other: &ZipEntry) -> bool {
+    this.uncompressed_size() == other.uncompressed_size() && this.crc32() == other.crc32()
+}
+
+fn last_indices(file: &ZipFile) -> HashMap<&str, usize> {
+    file.entries.iter().enumerate().map(|(index, stored_entry)| (stored_entry.entry.filename(), index)).collect()
+}
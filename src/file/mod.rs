@@ -2,16 +2,25 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 pub(crate) mod builder;
+pub(crate) mod diff;
 
 use crate::entry::StoredZipEntry;
+use crate::read::ZipWarning;
 use builder::ZipFileBuilder;
+use diff::ArchiveDiff;
+
+use std::collections::HashMap;
 
 /// An immutable store of data about a ZIP file.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ZipFile {
     pub(crate) entries: Vec<StoredZipEntry>,
     pub(crate) zip64: bool,
     pub(crate) comment: String,
+    pub(crate) warnings: Vec<ZipWarning>,
+    pub(crate) disk_number: u16,
+    pub(crate) cent_dir_disk_number: u16,
 }
 
 impl From<ZipFileBuilder> for ZipFile {
@@ -26,6 +35,47 @@ impl ZipFile {
         &self.entries
     }
 
+    /// Returns a map from each filename to the indices of the entries holding it, in ascending order.
+    ///
+    /// ZIP files may legally hold multiple entries with the same filename, so a filename may map to several indices. In
+    /// that case, most extractors treat the last entry as the one which takes effect.
+    pub fn index_map(&self) -> HashMap<&str, Vec<usize>> {
+        let mut map: HashMap<&str, Vec<usize>> = HashMap::with_capacity(self.entries.len());
+
+        for (index, stored_entry) in self.entries.iter().enumerate() {
+            map.entry(stored_entry.entry.filename()).or_default().push(index);
+        }
+
+        map
+    }
+
+    /// Returns each filename held by more than one entry, along with the indices of those entries.
+    ///
+    /// Filenames are ordered by the index of their first entry, and so this is empty if every filename is unique.
+    pub fn duplicates(&self) -> Vec<(&str, Vec<usize>)> {
+        let mut duplicates: Vec<_> = self.index_map().into_iter().filter(|(_, indices)| indices.len() > 1).collect();
+        duplicates.sort_by_key(|(_, indices)| indices[0]);
+        duplicates
+    }
+
+    /// Compares this ZIP file's entries against another's by filename, uncompressed size, and CRC32 value.
+    ///
+    /// Entries only held by the other ZIP file are reported as added, and those only held by this file as removed. To
+    /// also compare the entries' content, see [`ZipFileReader::diff()`](crate::read::seek::ZipFileReader::diff).
+    pub fn diff(&self, other: &ZipFile) -> ArchiveDiff {
+        let mut diff = ArchiveDiff::default();
+
+        for (index, other_index) in diff::matched(self, other, &mut diff) {
+            let (entry, other_entry) = (&self.entries[index].entry, &other.entries[other_index].entry);
+
+            if !diff::same_metadata(entry, other_entry) {
+                diff.changed.push(entry.filename().to_string());
+            }
+        }
+
+        diff
+    }
+
     /// Returns this ZIP file's trailing comment.
     pub fn comment(&self) -> &str {
         &self.comment
@@ -35,4 +85,26 @@ impl ZipFile {
     pub fn zip64(&self) -> bool {
         self.zip64
     }
+
+    /// Returns the raw disk number held within the end of central directory record.
+    ///
+    /// This is zero for most single-file archives, though some writers instead store a nonzero or sentinel (`0xFFFF`)
+    /// value. As spanned/split files aren't supported, this value is otherwise ignored when reading.
+    pub fn disk_number(&self) -> u16 {
+        self.disk_number
+    }
+
+    /// Returns the raw number of the disk on which the central directory starts, as held within the end of central
+    /// directory record.
+    pub fn cent_dir_disk_number(&self) -> u16 {
+        self.cent_dir_disk_number
+    }
+
+    /// Returns the non-fatal anomalies found whilst opening this ZIP file.
+    ///
+    /// This is always empty unless the file was opened in lenient mode (see
+    /// [`ReaderOptions::lenient()`](crate::read::ReaderOptions::lenient)).
+    pub fn warnings(&self) -> &[ZipWarning] {
+        &self.warnings
+    }
 }
@@ -0,0 +1,122 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::Result;
+use crate::fs::progress::{self, ProgressIo};
+use crate::fs::{ArchiveOptions, ProgressEvent};
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+use tokio::fs::File;
+use tokio::io::AsyncWrite;
+
+/// Writes the contents of a directory (and all of its subdirectories) to a ZIP file writer.
+///
+/// Entries are named relative to the provided directory (which itself isn't written as an entry), and are written in
+/// a depth-first order with each directory's children sorted by name. Each entry holds its file's last modification
+/// time (within an extended timestamp extra field) and, on Unix targets, its permissions. Symbolic links are written as
+/// such rather than being followed, and each file's contents are streamed into the writer.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::error::Result;
+/// # use async_zip::fs::ArchiveOptions;
+/// # use async_zip::write::ZipFileWriter;
+/// # use tokio::fs::File;
+/// #
+/// # async fn run() -> Result<()> {
+/// let mut writer = ZipFileWriter::new(File::create("./foo.zip").await?);
+/// async_zip::fs::write_dir_to_archive("./foo", &mut writer, ArchiveOptions::new()).await?;
+/// writer.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn write_dir_to_archive<W>(
+    path: impl AsRef<Path>,
+    writer: &mut ZipFileWriter<W>,
+    options: ArchiveOptions,
+) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut stack = read_children(path.as_ref(), "").await?;
+    let (mut entries, mut bytes) = (0, 0);
+
+    while let Some((path, name)) = stack.pop() {
+        if options.filter.excludes(&name) {
+            continue;
+        }
+
+        let metadata = tokio::fs::symlink_metadata(&path).await?;
+        let included = options.filter.allows(&name);
+
+        // Non-matching directories are still walked as their contents may match an include pattern.
+        if metadata.is_dir() && !included {
+            stack.extend(read_children(&path, &format!("{name}/")).await?);
+            continue;
+        } else if !included {
+            continue;
+        }
+        let builder = entry_builder(name.clone(), &metadata, &options);
+
+        let size = if metadata.is_file() { metadata.len() } else { 0 };
+        progress::send(&options.progress, ProgressEvent::EntryStarted { filename: name.clone(), size });
+
+        let future = async {
+            if metadata.is_dir() {
+                writer.write_dir_entry(builder).await?;
+                stack.extend(read_children(&path, &format!("{name}/")).await?);
+            } else if metadata.is_symlink() {
+                let target = tokio::fs::read_link(&path).await?;
+                writer.write_symlink_entry(builder, &target.to_string_lossy()).await?;
+            } else {
+                let reader = ProgressIo::new(File::open(&path).await?, options.progress.clone());
+                bytes += writer.write_entry_from_reader(builder, reader).await?;
+            }
+
+            Ok(())
+        };
+
+        crate::fs::cancellable(options.cancellation_token.as_ref(), future).await?;
+
+        entries += 1;
+        progress::send(&options.progress, ProgressEvent::EntryFinished { filename: name });
+    }
+
+    progress::send(&options.progress, ProgressEvent::Finished { entries, bytes });
+    Ok(())
+}
+
+/// Returns the children of a directory along with their entry names, sorted in reverse so they can be popped in order.
+async fn read_children(path: &Path, prefix: &str) -> Result<Vec<(PathBuf, String)>> {
+    let mut children = Vec::new();
+    let mut read_dir = tokio::fs::read_dir(path).await?;
+
+    while let Some(child) = read_dir.next_entry().await? {
+        let name = format!("{prefix}{}", child.file_name().to_string_lossy());
+        children.push((child.path(), name));
+    }
+
+    children.sort_by(|a, b| b.1.cmp(&a.1));
+    Ok(children)
+}
+
+/// Constructs an entry builder holding the modification time and permissions from a file's metadata.
+fn entry_builder(name: String, metadata: &Metadata, options: &ArchiveOptions) -> ZipEntryBuilder {
+    let mut builder =
+        ZipEntryBuilder::new(name, options.compression).last_modification_date(metadata).extended_timestamp(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        builder = builder
+            .attribute_compatibility(crate::AttributeCompatibility::Unix)
+            .unix_permissions(metadata.permissions().mode());
+    }
+
+    builder
+}
@@ -0,0 +1,728 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::{StoredZipEntry, ZipEntry};
+use crate::error::{Result, ZipError};
+use crate::fs::progress::{self, ProgressIo};
+use crate::fs::{ExtractOptions, ExtractionPlan, ExtractionPlanEntry, OverwritePolicy, PlannedAction, ProgressEvent};
+use crate::read::io::entry::ZipEntryReader;
+
+use std::collections::HashSet;
+use std::fs::Metadata;
+use std::future::Future;
+use std::io::{Cursor, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, BufReader};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+/// The buffer size used when reading an entry's data, equal to 8KiB (tokio's own default).
+const DEFAULT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// The max buffer size used when reading an entry's data during concurrent extraction, equal to 1MiB.
+const MAX_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// The default budget for the buffers of entries being extracted concurrently, equal to 64MiB.
+const DEFAULT_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
+/// Extracts all entries of a ZIP file into a destination directory, decompressing up to `max_tasks` entries in
+/// parallel.
+///
+/// This is a shorthand for [`extract_to_dir_concurrent()`] with [`ExtractOptions::concurrency()`] set to `max_tasks`
+/// and every other option at its default value.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::error::Result;
+/// # use tokio::fs::File;
+/// #
+/// # async fn run() -> Result<()> {
+/// async_zip::fs::extract_all_concurrent(|| File::open("./foo.zip"), "./foo", 8).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn extract_all_concurrent<F, Fut, R>(
+    reader_factory: F,
+    dest: impl AsRef<Path>,
+    max_tasks: usize,
+) -> Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = std::io::Result<R>>,
+    R: AsyncRead + AsyncSeek + Unpin + Send + 'static,
+{
+    extract_to_dir_concurrent(reader_factory, dest, ExtractOptions::new().concurrency(max_tasks)).await
+}
+
+/// Extracts all entries of a ZIP file into a destination directory, one entry at a time.
+///
+/// Every entry's path is validated before anything is written. If any path would escape the destination directory
+/// (eg. via ".." components, an absolute path, or a drive letter), extraction is aborted with
+/// [`ZipError::UnsafeEntryPath`] unless the options specify that such entries should be skipped instead. Directories are
+/// created as needed, and each file's CRC32 value is verified as it's written.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::error::Result;
+/// # use async_zip::fs::ExtractOptions;
+/// # use tokio::fs::File;
+/// #
+/// # async fn run() -> Result<()> {
+/// let file = File::open("./foo.zip").await?;
+/// async_zip::fs::extract_to_dir(file, "./foo", ExtractOptions::new()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn extract_to_dir<R>(mut reader: R, dest: impl AsRef<Path>, options: ExtractOptions) -> Result<()>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let planned = plan_checked(&mut reader, dest.as_ref(), &options).await?;
+
+    if options.prefetch {
+        extract_prefetched(&mut reader, &planned, &options).await?;
+        return finish(&planned, &options).await;
+    }
+
+    for planned_entry in &planned {
+        if let Some(path) = start_entry(planned_entry, &options).await? {
+            extract_file(&mut reader, &planned_entry.stored_entry, &path, &options, DEFAULT_BUFFER_SIZE).await?;
+            progress::send(&options.progress, planned_entry.finished_event());
+        }
+    }
+
+    finish(&planned, &options).await
+}
+
+/// Extracts each file from its compressed data held in memory, whilst the next file's compressed data is read from the
+/// source.
+async fn extract_prefetched<R>(reader: &mut R, planned: &[PlannedEntry], options: &ExtractOptions) -> Result<()>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    // Up to two files' compressed data is held at once, so each may only use half of the budget.
+    let limit = options.memory_budget.unwrap_or(DEFAULT_MEMORY_BUDGET) as u64 / 2;
+    let prefetchable = |planned_entry: &&PlannedEntry| {
+        planned_entry.is_file() && planned_entry.stored_entry.entry.compressed_size() <= limit
+    };
+    let mut prefetched = None;
+
+    for (index, planned_entry) in planned.iter().enumerate() {
+        // Any prefetched data of a file which is then skipped is discarded.
+        let data = prefetched.take();
+        let Some(path) = start_entry(planned_entry, options).await? else {
+            continue;
+        };
+
+        let stored_entry = &planned_entry.stored_entry;
+        let data = match data {
+            Some(data) => data,
+            None if prefetchable(&planned_entry) => read_compressed(&mut *reader, stored_entry).await?,
+            None => {
+                extract_file(&mut *reader, stored_entry, &path, options, DEFAULT_BUFFER_SIZE).await?;
+                progress::send(&options.progress, planned_entry.finished_event());
+                continue;
+            }
+        };
+
+        let next = planned.get(index + 1).filter(prefetchable);
+        let prefetch = async {
+            match next {
+                Some(next) => read_compressed(&mut *reader, &next.stored_entry).await.map(Some),
+                None => Ok(None),
+            }
+        };
+
+        let reader = BufReader::with_capacity(DEFAULT_BUFFER_SIZE, Cursor::new(data));
+        let future = write_entry(reader, &stored_entry.entry, path.clone(), options.progress.clone());
+        let (extracted, next_data) = tokio::join!(finish_file(future, stored_entry, &path, options), prefetch);
+
+        extracted?;
+        prefetched = next_data?;
+        progress::send(&options.progress, planned_entry.finished_event());
+    }
+
+    Ok(())
+}
+
+/// Reads an entry's compressed data into memory.
+async fn read_compressed<R>(reader: R, stored_entry: &StoredZipEntry) -> Result<Vec<u8>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let mut reader = BufReader::new(reader);
+    stored_entry.seek_to_readable_data(&mut reader).await?;
+
+    let size = stored_entry.entry.compressed_size();
+    let mut data = Vec::with_capacity(size as usize);
+    reader.take(size).read_to_end(&mut data).await?;
+
+    if (data.len() as u64) < size {
+        return Err(std::io::Error::from(ErrorKind::UnexpectedEof).into());
+    }
+
+    Ok(data)
+}
+
+/// Extracts all entries of a ZIP file into a destination directory, extracting multiple files concurrently.
+///
+/// This behaves as [`extract_to_dir()`], except that each file is extracted within its own task from an independent
+/// reader (so that both I/O and decompression can run in parallel). The `reader_factory` is called once to parse the
+/// central directory and then once per file entry. The number of files extracted at once is bounded by
+/// [`ExtractOptions::concurrency()`], and the total size of their buffers by [`ExtractOptions::memory_budget()`].
+///
+/// As files are extracted concurrently, the [`ProgressEvent::BytesProcessed`] events of different entries may be
+/// interleaved. Entries which share a destination path (eg. duplicate names) are still extracted one after another, so
+/// the overwrite policy applies between them as it would for [`extract_to_dir()`]. If any entry fails to be extracted,
+/// no further entries are started and the first error is returned once those already in progress have completed.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::error::Result;
+/// # use async_zip::fs::ExtractOptions;
+/// # use tokio::fs::File;
+/// #
+/// # async fn run() -> Result<()> {
+/// let options = ExtractOptions::new().concurrency(8);
+/// async_zip::fs::extract_to_dir_concurrent(|| File::open("./foo.zip"), "./foo", options).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn extract_to_dir_concurrent<F, Fut, R>(
+    reader_factory: F,
+    dest: impl AsRef<Path>,
+    options: ExtractOptions,
+) -> Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = std::io::Result<R>>,
+    R: AsyncRead + AsyncSeek + Unpin + Send + 'static,
+{
+    let planned = plan_checked(&mut reader_factory().await?, dest.as_ref(), &options).await?;
+    let options = Arc::new(options);
+
+    let memory_budget = options.memory_budget.unwrap_or(DEFAULT_MEMORY_BUDGET).clamp(1, Semaphore::MAX_PERMITS);
+    let concurrency = options
+        .concurrency
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|parallelism| parallelism.get()).unwrap_or(1));
+
+    let task_semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let memory_semaphore = Arc::new(Semaphore::new(memory_budget));
+    let mut tasks = JoinSet::new();
+    let mut claimed = HashSet::new();
+
+    let spawned: Result<()> = async {
+        for planned_entry in &planned {
+            // Surface errors from already-finished tasks before starting more work.
+            while let Some(result) = tasks.try_join_next() {
+                join_result(result)?;
+            }
+
+            // Entries sharing a path (eg. duplicate names) must see those before them on disk, as they would when
+            // extracted one at a time, so any tasks which may still be writing that path are waited on first.
+            if !planned_entry.stored_entry.entry.dir() && !claimed.insert(planned_entry.path.clone()) {
+                while let Some(result) = tasks.join_next().await {
+                    join_result(result)?;
+                }
+
+                claimed.clear();
+                claimed.insert(planned_entry.path.clone());
+            }
+
+            let Some(path) = start_entry(planned_entry, &options).await? else {
+                continue;
+            };
+
+            // Each file's buffer is sized by its uncompressed size, but never exceeds the overall budget.
+            let buffer_size = (planned_entry.size() as usize).clamp(DEFAULT_BUFFER_SIZE, MAX_BUFFER_SIZE);
+            let buffer_size = buffer_size.min(memory_budget);
+
+            let task_permit = task_semaphore.clone().acquire_owned().await.expect("semaphore is never closed");
+            let memory_permit = memory_semaphore.clone().acquire_many_owned(buffer_size as u32).await;
+            let memory_permit = memory_permit.expect("semaphore is never closed");
+
+            let reader = reader_factory().await?;
+            let (stored_entry, options) = (planned_entry.stored_entry.clone(), options.clone());
+            let event = planned_entry.finished_event();
+
+            tasks.spawn(async move {
+                let result = extract_file(reader, &stored_entry, &path, &options, buffer_size).await;
+                drop((task_permit, memory_permit));
+
+                progress::send(&options.progress, event);
+                result
+            });
+        }
+
+        Ok(())
+    }
+    .await;
+
+    // Tasks are always joined (rather than aborted) so that any partially-written files are cleaned up.
+    let mut result = spawned;
+    while let Some(joined) = tasks.join_next().await {
+        let joined = join_result(joined);
+        result = result.and(joined);
+    }
+
+    result?;
+    finish(&planned, &options).await
+}
+
+/// An entry which has been validated for extraction, along with its destination path.
+#[derive(Clone)]
+struct PlannedEntry {
+    stored_entry: StoredZipEntry,
+    path: PathBuf,
+    /// The link's target, if the entry should be extracted as a symlink.
+    target: Option<String>,
+}
+
+impl PlannedEntry {
+    fn is_file(&self) -> bool {
+        !self.stored_entry.entry.dir() && self.target.is_none()
+    }
+
+    fn size(&self) -> u64 {
+        if self.is_file() {
+            self.stored_entry.entry.uncompressed_size()
+        } else {
+            0
+        }
+    }
+
+    fn finished_event(&self) -> ProgressEvent {
+        ProgressEvent::EntryFinished { filename: self.stored_entry.entry.filename().to_string() }
+    }
+}
+
+/// Computes the plan of an extraction without writing anything to the file system.
+///
+/// This parses the central directory and validates every entry as [`extract_to_dir()`] would, but instead returns each
+/// entry's destination path and size, whether that path already exists, and the action which would be taken under the
+/// options' overwrite policy. Entries with unsafe paths are listed separately rather than causing an error. This allows
+/// the contents of an untrusted ZIP file to be presented for confirmation before it's extracted.
+///
+/// Note that the file system may change between planning and extraction, so the plan is only advisory.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::error::Result;
+/// # use async_zip::fs::ExtractOptions;
+/// # use tokio::fs::File;
+/// #
+/// # async fn run() -> Result<()> {
+/// let file = File::open("./foo.zip").await?;
+/// let plan = async_zip::fs::plan_extraction(file, "./foo", ExtractOptions::new()).await?;
+///
+/// for entry in plan.conflicts() {
+///     println!("{} already exists", entry.path().display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn plan_extraction<R>(
+    mut reader: R,
+    dest: impl AsRef<Path>,
+    options: ExtractOptions,
+) -> Result<ExtractionPlan>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let (planned, unsafe_entries) = plan(&mut reader, dest.as_ref(), &options).await?;
+    let mut entries = Vec::with_capacity(planned.len());
+
+    for planned_entry in planned {
+        let metadata = symlink_metadata(&planned_entry.path).await?;
+        let reused = planned_entry.stored_entry.entry.dir() && metadata.as_ref().is_some_and(Metadata::is_dir);
+
+        entries.push(ExtractionPlanEntry {
+            filename: planned_entry.stored_entry.entry.filename().to_string(),
+            size: planned_entry.size(),
+            exists: metadata.is_some() && !reused,
+            action: planned_action(&planned_entry, options.overwrite_policy).await?,
+            path: planned_entry.path,
+        });
+    }
+
+    let unsafe_entries = unsafe_entries.into_iter().map(|(filename, _)| filename).collect();
+    Ok(ExtractionPlan { entries, unsafe_entries })
+}
+
+/// Parses the central directory and validates every entry before anything is written.
+///
+/// Any entries selected by the filter whose paths (or symlink targets) are unsafe are returned separately, along with
+/// the error describing why.
+async fn plan<R>(
+    reader: &mut R,
+    dest: &Path,
+    options: &ExtractOptions,
+) -> Result<(Vec<PlannedEntry>, Vec<(String, ZipError)>)>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let file = crate::read::file(&mut *reader).await?;
+    let mut candidates = Vec::with_capacity(file.entries.len());
+    let mut unsafe_entries = Vec::new();
+
+    for stored_entry in file.entries {
+        if !options.filter.allows(&stored_entry.entry.filename().replace('\\', "/")) {
+            continue;
+        }
+
+        match plan_entry(&mut *reader, &stored_entry, options).await {
+            Ok((path, target)) => candidates.push((stored_entry, path, target)),
+            Err(err @ ZipError::UnsafeEntryPath(_)) => {
+                unsafe_entries.push((stored_entry.entry.filename().to_string(), err))
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    // A symlink's target is only validated against its own path, so no entry may be extracted through one.
+    let links: HashSet<PathBuf> =
+        candidates.iter().filter(|(_, _, target)| target.is_some()).map(|(_, path, _)| path.clone()).collect();
+    let mut planned = Vec::with_capacity(candidates.len());
+
+    for (stored_entry, path, target) in candidates {
+        match check_parents(dest, &path, &links, stored_entry.entry.filename()).await {
+            Ok(()) => planned.push(PlannedEntry { stored_entry, path: dest.join(path), target }),
+            Err(err @ ZipError::UnsafeEntryPath(_)) => {
+                unsafe_entries.push((stored_entry.entry.filename().to_string(), err))
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok((planned, unsafe_entries))
+}
+
+/// Returns an error if any parent of an entry's relative path either is an existing symlink, or will be extracted as
+/// one.
+async fn check_parents(dest: &Path, path: &Path, links: &HashSet<PathBuf>, filename: &str) -> Result<()> {
+    let mut parent = PathBuf::new();
+    let mut exists = true;
+
+    for component in path.parent().into_iter().flat_map(Path::components) {
+        parent.push(component);
+
+        if links.contains(&parent) {
+            return Err(ZipError::UnsafeEntryPath(filename.to_string()));
+        }
+
+        // Once a parent doesn't exist, none of those beneath it can either.
+        if exists {
+            match symlink_metadata(&dest.join(&parent)).await? {
+                Some(metadata) if metadata.is_symlink() => return Err(ZipError::UnsafeEntryPath(filename.to_string())),
+                Some(_) => (),
+                None => exists = false,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Plans an extraction, returning an error for the first unsafe entry (unless they should be skipped) and reporting
+/// the totals.
+async fn plan_checked<R>(reader: &mut R, dest: &Path, options: &ExtractOptions) -> Result<Vec<PlannedEntry>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let (planned, unsafe_entries) = plan(reader, dest, options).await?;
+
+    if let Some((_, err)) = unsafe_entries.into_iter().next().filter(|_| !options.skip_unsafe_paths) {
+        return Err(err);
+    }
+
+    let entries = planned.len() as u64;
+    let bytes = planned.iter().map(PlannedEntry::size).sum();
+    progress::send(&options.progress, ProgressEvent::Started { entries, bytes });
+
+    Ok(planned)
+}
+
+/// Starts the extraction of an entry, returning the path to extract it to if it's a file which still needs extracting.
+///
+/// Directories and symlinks are created immediately, and they (along with any skipped entries) are reported as finished.
+async fn start_entry(planned_entry: &PlannedEntry, options: &ExtractOptions) -> Result<Option<PathBuf>> {
+    if options.cancellation_token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+        return Err(ZipError::Cancelled);
+    }
+
+    let entry = &planned_entry.stored_entry.entry;
+    let event = ProgressEvent::EntryStarted { filename: entry.filename().to_string(), size: planned_entry.size() };
+    progress::send(&options.progress, event);
+
+    if entry.dir() {
+        tokio::fs::create_dir_all(&planned_entry.path).await?;
+    } else if let Some(path) = resolve_conflict(planned_entry, options.overwrite_policy).await? {
+        let Some(target) = &planned_entry.target else {
+            return Ok(Some(path));
+        };
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        #[cfg(unix)]
+        tokio::fs::symlink(target, path).await?;
+        #[cfg(not(unix))]
+        let _ = target;
+    }
+
+    progress::send(&options.progress, planned_entry.finished_event());
+    Ok(None)
+}
+
+/// Returns the path a file or symlink entry should be extracted to under an overwrite policy, or None if it should be
+/// skipped.
+///
+/// Any existing file or symlink which is to be replaced is removed beforehand, so that a symlink is never followed.
+async fn resolve_conflict(planned_entry: &PlannedEntry, policy: OverwritePolicy) -> Result<Option<PathBuf>> {
+    let path = &planned_entry.path;
+
+    match planned_action(planned_entry, policy).await? {
+        PlannedAction::Create => Ok(Some(path.clone())),
+        PlannedAction::Skip => Ok(None),
+        PlannedAction::Rename(path) => Ok(Some(path)),
+        PlannedAction::Error => Err(ZipError::EntryPathExists(planned_entry.stored_entry.entry.filename().to_string())),
+        // Existing directories can't be replaced by a file, so are left for creation to fail with a descriptive error.
+        PlannedAction::Overwrite => {
+            if symlink_metadata(path).await?.is_some_and(|metadata| !metadata.is_dir()) {
+                tokio::fs::remove_file(path).await?;
+            }
+
+            Ok(Some(path.clone()))
+        }
+    }
+}
+
+/// Returns the action which should be taken for an entry under an overwrite policy, without modifying anything.
+async fn planned_action(planned_entry: &PlannedEntry, policy: OverwritePolicy) -> Result<PlannedAction> {
+    let entry = &planned_entry.stored_entry.entry;
+
+    let Some(metadata) = symlink_metadata(&planned_entry.path).await?.filter(|_| !entry.dir()) else {
+        return Ok(PlannedAction::Create);
+    };
+
+    let action = match policy {
+        OverwritePolicy::Error => PlannedAction::Error,
+        OverwritePolicy::Skip => PlannedAction::Skip,
+        OverwritePolicy::Overwrite => PlannedAction::Overwrite,
+        OverwritePolicy::RenameWithSuffix => PlannedAction::Rename(unused_path(&planned_entry.path).await?),
+        OverwritePolicy::OverwriteIfNewer => match metadata.modified() {
+            Ok(time) if modification_time(entry) <= time => PlannedAction::Skip,
+            _ => PlannedAction::Overwrite,
+        },
+    };
+
+    Ok(action)
+}
+
+/// Returns the first path with a numbered suffix appended to its file stem (eg. `foo (1).txt`) which doesn't exist.
+async fn unused_path(path: &Path) -> Result<PathBuf> {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().map(|extension| format!(".{}", extension.to_string_lossy())).unwrap_or_default();
+
+    for suffix in 1.. {
+        let candidate = path.with_file_name(format!("{stem} ({suffix}){extension}"));
+
+        if symlink_metadata(&candidate).await?.is_none() {
+            return Ok(candidate);
+        }
+    }
+
+    unreachable!("an unused path is always found")
+}
+
+/// Returns the metadata of a path without following symlinks, or None if it doesn't exist.
+async fn symlink_metadata(path: &Path) -> Result<Option<Metadata>> {
+    match tokio::fs::symlink_metadata(path).await {
+        Ok(metadata) => Ok(Some(metadata)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Extracts a file entry from the source to the provided path and applies its metadata.
+async fn extract_file<R>(
+    reader: R,
+    stored_entry: &StoredZipEntry,
+    path: &Path,
+    options: &ExtractOptions,
+    buffer_size: usize,
+) -> Result<()>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let future = extract_entry(reader, stored_entry, path.to_path_buf(), options.progress.clone(), buffer_size);
+    finish_file(future, stored_entry, path, options).await
+}
+
+/// Awaits the extraction of a file entry and applies its metadata, removing the partially-written file if cancelled.
+async fn finish_file<F>(future: F, stored_entry: &StoredZipEntry, path: &Path, options: &ExtractOptions) -> Result<()>
+where
+    F: Future<Output = Result<()>>,
+{
+    let result = crate::fs::cancellable(options.cancellation_token.as_ref(), future).await;
+
+    if let Err(ZipError::Cancelled) = result {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+    result?;
+
+    if options.preserve_permissions {
+        set_permissions(&stored_entry.entry, path).await?;
+    }
+    if options.preserve_modification_times {
+        set_modification_time(&stored_entry.entry, path.to_path_buf()).await?;
+    }
+
+    Ok(())
+}
+
+/// Completes extraction once every entry has been extracted.
+async fn finish(planned: &[PlannedEntry], options: &ExtractOptions) -> Result<()> {
+    // Applied last (and innermost first) so that read-only directories don't prevent their contents being extracted.
+    if options.preserve_permissions {
+        for planned_entry in planned.iter().rev().filter(|planned_entry| planned_entry.stored_entry.entry.dir()) {
+            set_permissions(&planned_entry.stored_entry.entry, &planned_entry.path).await?;
+        }
+    }
+
+    let entries = planned.len() as u64;
+    let bytes = planned.iter().map(PlannedEntry::size).sum();
+    progress::send(&options.progress, ProgressEvent::Finished { entries, bytes });
+
+    Ok(())
+}
+
+/// Returns the validated relative path of an entry, along with its link's target if it should be extracted as a symlink.
+async fn plan_entry<R>(
+    reader: R,
+    stored_entry: &StoredZipEntry,
+    options: &ExtractOptions,
+) -> Result<(PathBuf, Option<String>)>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let entry = &stored_entry.entry;
+    let path = crate::utils::safe_path(entry.filename())?;
+
+    if path.as_os_str().is_empty() && !entry.dir() {
+        return Err(ZipError::UnsafeEntryPath(entry.filename().to_string()));
+    }
+
+    if !(cfg!(unix) && options.symlinks && entry.symlink()) {
+        return Ok((path, None));
+    }
+
+    let mut reader = BufReader::new(reader);
+    stored_entry.seek_to_readable_data(&mut reader).await?;
+
+    let mut target = String::new();
+    let mut entry_reader = ZipEntryReader::new_with_owned(reader, entry.compression(), entry.compressed_size());
+    entry_reader.read_to_string_checked(&mut target, entry).await?;
+
+    crate::utils::check_symlink_target(&path, &target)?;
+    Ok((path, Some(target)))
+}
+
+/// Applies the Unix mode bits of an entry to the provided path, if it has any.
+async fn set_permissions(entry: &ZipEntry, path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    if let Some(mode) = entry.unix_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let permissions = std::fs::Permissions::from_mode(u32::from(mode) & 0o7777);
+        tokio::fs::set_permissions(path, permissions).await?;
+    }
+
+    #[cfg(not(unix))]
+    let _ = (entry, path);
+
+    Ok(())
+}
+
+/// Returns the modification time of an entry.
+///
+/// The time held within an extended timestamp extra field is preferred, falling back to the entry's MS-DOS
+/// modification date & time (interpreted as UTC) otherwise.
+fn modification_time(entry: &ZipEntry) -> SystemTime {
+    let seconds = crate::spec::extra_field::extended_modification_time(entry.extra_field())
+        .unwrap_or_else(|| entry.last_modification_date().as_unix_timestamp());
+
+    let offset = Duration::from_secs(seconds.unsigned_abs());
+    if seconds < 0 {
+        UNIX_EPOCH - offset
+    } else {
+        UNIX_EPOCH + offset
+    }
+}
+
+/// Sets the modification time of the file at the provided path to that of an entry.
+async fn set_modification_time(entry: &ZipEntry, path: PathBuf) -> Result<()> {
+    let time = modification_time(entry);
+    let task = tokio::task::spawn_blocking(move || std::fs::File::options().write(true).open(path)?.set_modified(time));
+
+    match task.await {
+        Ok(result) => Ok(result?),
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
+
+/// Extracts a single entry from an owned reader to the provided path, verifying its CRC32 value.
+///
+/// The source is read through a buffer of the provided size, and the number of bytes written is reported to the progress channel, if one is provided.
+pub(crate) async fn extract_entry<R>(
+    reader: R,
+    stored_entry: &StoredZipEntry,
+    path: PathBuf,
+    progress: Option<UnboundedSender<ProgressEvent>>,
+    buffer_size: usize,
+) -> Result<()>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let mut reader = BufReader::with_capacity(buffer_size, reader);
+    stored_entry.seek_to_readable_data(&mut reader).await?;
+
+    write_entry(reader, &stored_entry.entry, path, progress).await
+}
+
+/// Decompresses an entry's data from a reader positioned at its start to the provided path, verifying its CRC32 value.
+async fn write_entry<R>(
+    reader: BufReader<R>,
+    entry: &ZipEntry,
+    path: PathBuf,
+    progress: Option<UnboundedSender<ProgressEvent>>,
+) -> Result<()>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut entry_reader = ZipEntryReader::new_with_owned(reader, entry.compression(), entry.compressed_size());
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let mut writer = ProgressIo::new(File::create(&path).await?, progress);
+    entry_reader.copy_to_end_checked(&mut writer, entry).await?;
+
+    Ok(())
+}
+
+/// Unwraps the result of a joined extraction task, resuming any panic which occurred within it.
+fn join_result(result: std::result::Result<Result<()>, tokio::task::JoinError>) -> Result<()> {
+    match result {
+        Ok(result) => result,
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
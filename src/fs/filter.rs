@@ -0,0 +1,72 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+/// A set of include & exclude glob patterns which select the entries processed by a helper.
+///
+/// Patterns support `*` (any characters other than `/`), `?` (any single character other than `/`), and `**` (any
+/// characters, including `/`). A pattern without a `/` is matched against each component of an entry's path (so
+/// `node_modules` matches `foo/node_modules/bar.js`), whereas any other pattern is matched against the full path and
+/// each of its parent directories. Trailing slashes are ignored.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Filter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    skip_macos_metadata: bool,
+}
+
+impl Filter {
+    pub(crate) fn include(&mut self, pattern: String) {
+        self.include.push(pattern);
+    }
+
+    pub(crate) fn exclude(&mut self, pattern: String) {
+        self.exclude.push(pattern);
+    }
+
+    pub(crate) fn skip_macos_metadata(&mut self, skip: bool) {
+        self.skip_macos_metadata = skip;
+    }
+
+    /// Returns whether the provided path matches an exclude pattern (or is skipped as macOS metadata).
+    pub(crate) fn excludes(&self, path: &str) -> bool {
+        (self.skip_macos_metadata && crate::utils::is_macos_metadata(path))
+            || self.exclude.iter().any(|pattern| matches(pattern, path))
+    }
+
+    /// Returns whether the provided path matches an include pattern (or if there are none), and no exclude pattern.
+    pub(crate) fn allows(&self, path: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|pattern| matches(pattern, path));
+        included && !self.excludes(path)
+    }
+}
+
+fn matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+    let path = path.trim_end_matches('/');
+
+    if !pattern.contains('/') {
+        return path.split('/').any(|component| glob(pattern.as_bytes(), component.as_bytes()));
+    }
+
+    let pattern = pattern.trim_start_matches('/');
+    let ancestors = path.match_indices('/').map(|(index, _)| &path[..index]);
+
+    ancestors.chain(std::iter::once(path)).any(|prefix| glob(pattern.as_bytes(), prefix.as_bytes()))
+}
+
+fn glob(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        // A leading "**/" may also match zero directories.
+        [b'*', b'*', b'/', rest @ ..] => {
+            glob(rest, text) || text.iter().enumerate().any(|(i, c)| *c == b'/' && glob(rest, &text[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob(rest, &text[i..])),
+        [b'*', rest @ ..] => {
+            let end = text.iter().position(|c| *c == b'/').unwrap_or(text.len());
+            (0..=end).any(|i| glob(rest, &text[i..]))
+        }
+        [b'?', rest @ ..] => matches!(text, [c, ..] if *c != b'/') && glob(rest, &text[1..]),
+        [c, rest @ ..] => text.first() == Some(c) && glob(rest, &text[1..]),
+    }
+}
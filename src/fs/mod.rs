@@ -0,0 +1,59 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which provides high-level helpers for working with ZIP files on the file system.
+//!
+//! Note that this requires the `fs` feature.
+
+pub(crate) mod archive;
+pub(crate) mod extract;
+pub(crate) mod filter;
+pub(crate) mod options;
+pub(crate) mod plan;
+pub(crate) mod progress;
+#[cfg(all(feature = "uring", target_os = "linux"))]
+pub mod uring;
+
+pub use archive::write_dir_to_archive;
+pub use extract::{extract_all_concurrent, extract_to_dir, extract_to_dir_concurrent, plan_extraction};
+pub use options::{ArchiveOptions, ExtractOptions, OverwritePolicy};
+pub use plan::{ExtractionPlan, ExtractionPlanEntry, PlannedAction};
+pub use progress::ProgressEvent;
+
+use crate::error::{Result, ZipError};
+
+use std::future::Future;
+use std::path::Path;
+use std::task::Poll;
+
+use tokio::fs::OpenOptions;
+use tokio_util::sync::CancellationToken;
+
+/// Replaces the comment of a ZIP file on disk in place, truncating the file if the comment was shortened.
+///
+/// See [`crate::write::update_comment()`].
+pub async fn update_comment(path: impl AsRef<Path>, comment: &str) -> Result<()> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path).await?;
+    let length = crate::write::update_comment(&mut file, comment).await?;
+    file.set_len(length).await?;
+    Ok(())
+}
+
+/// Drives a future to completion, aborting it with [`ZipError::Cancelled`] if the provided token is cancelled first.
+pub(crate) async fn cancellable<F, T>(token: Option<&CancellationToken>, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let Some(token) = token else {
+        return future.await;
+    };
+
+    let mut future = std::pin::pin!(future);
+    let mut cancelled = std::pin::pin!(token.cancelled());
+
+    std::future::poll_fn(|cx| match cancelled.as_mut().poll(cx) {
+        Poll::Ready(()) => Poll::Ready(Err(ZipError::Cancelled)),
+        Poll::Pending => future.as_mut().poll(cx),
+    })
+    .await
+}
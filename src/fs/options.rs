@@ -0,0 +1,269 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::fs::filter::Filter;
+use crate::fs::ProgressEvent;
+use crate::spec::compression::Compression;
+
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+/// A set of options which configure how a directory is written by
+/// [`write_dir_to_archive()`](crate::fs::write_dir_to_archive).
+///
+/// ### Example
+/// ```
+/// # use async_zip::fs::ArchiveOptions;
+/// # use async_zip::Compression;
+/// #
+/// let options = ArchiveOptions::new().compression(Compression::Stored);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArchiveOptions {
+    pub(crate) compression: Compression,
+    pub(crate) progress: Option<UnboundedSender<ProgressEvent>>,
+    pub(crate) cancellation_token: Option<CancellationToken>,
+    pub(crate) filter: Filter,
+}
+
+impl Default for ArchiveOptions {
+    fn default() -> Self {
+        Self { compression: Compression::Stored, progress: None, cancellation_token: None, filter: Filter::default() }
+    }
+}
+
+impl ArchiveOptions {
+    /// Constructs a new set of options with every option at its default value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compression method used for each file entry, defaulting to [`Compression::Stored`].
+    ///
+    /// Directory and symbolic link entries are always stored without compression.
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets a channel to which [`ProgressEvent`]s are sent as entries are processed.
+    ///
+    /// Events are sent without waiting, so a slow receiver never stalls processing. Any events sent after the receiver
+    /// has been dropped are discarded.
+    pub fn progress(mut self, sender: UnboundedSender<ProgressEvent>) -> Self {
+        self.progress = Some(sender);
+        self
+    }
+
+    /// Sets a token which aborts archiving with [`ZipError::Cancelled`](crate::error::ZipError::Cancelled) once
+    /// cancelled.
+    ///
+    /// Cancellation is checked whilst each entry is being written, so long-running entries are aborted promptly. As the
+    /// writer is left holding a partially-written entry, it should be discarded rather than closed.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Adds a glob pattern which files must match to be written (see [`ExtractOptions::include()`] for the syntax).
+    ///
+    /// Directories are still walked if they don't match, but are only written as entries if they do.
+    pub fn include(mut self, pattern: String) -> Self {
+        self.filter.include(pattern);
+        self
+    }
+
+    /// Adds a glob pattern which excludes any matching files or directories (along with their contents) from being
+    /// written.
+    pub fn exclude(mut self, pattern: String) -> Self {
+        self.filter.exclude(pattern);
+        self
+    }
+
+    /// Sets whether macOS metadata files & directories (ie. `__MACOSX`, `.DS_Store`, and AppleDouble `._*` files) are
+    /// excluded from being written.
+    pub fn skip_macos_metadata(mut self, skip: bool) -> Self {
+        self.filter.skip_macos_metadata(skip);
+        self
+    }
+}
+
+/// The behaviour when an entry is extracted to a path which already exists.
+///
+/// This applies to files and symlinks; existing directories are always reused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// Abort extraction with [`ZipError::EntryPathExists`](crate::error::ZipError::EntryPathExists).
+    Error,
+    /// Leave the existing path untouched and skip the entry.
+    Skip,
+    /// Replace the existing path.
+    #[default]
+    Overwrite,
+    /// Extract the entry alongside the existing path, with a numbered suffix appended to its file stem (eg.
+    /// `foo (1).txt`).
+    RenameWithSuffix,
+    /// Replace the existing path only if the entry's modification time is later than its own, skipping it otherwise.
+    OverwriteIfNewer,
+}
+
+/// A set of options which configure how a ZIP file is extracted by [`extract_to_dir()`](crate::fs::extract_to_dir).
+///
+/// ### Example
+/// ```
+/// # use async_zip::fs::ExtractOptions;
+/// #
+/// let options = ExtractOptions::new().skip_unsafe_paths(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExtractOptions {
+    pub(crate) skip_unsafe_paths: bool,
+    pub(crate) preserve_permissions: bool,
+    pub(crate) preserve_modification_times: bool,
+    pub(crate) symlinks: bool,
+    pub(crate) progress: Option<UnboundedSender<ProgressEvent>>,
+    pub(crate) cancellation_token: Option<CancellationToken>,
+    pub(crate) concurrency: Option<usize>,
+    pub(crate) memory_budget: Option<usize>,
+    pub(crate) prefetch: bool,
+    pub(crate) filter: Filter,
+    pub(crate) overwrite_policy: OverwritePolicy,
+}
+
+impl ExtractOptions {
+    /// Constructs a new set of options with every option at its default value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether entries with paths which would escape the destination directory are silently skipped.
+    ///
+    /// By default, extraction is aborted with [`ZipError::UnsafeEntryPath`](crate::error::ZipError::UnsafeEntryPath)
+    /// before anything is written if any such entry is present.
+    pub fn skip_unsafe_paths(mut self, skip: bool) -> Self {
+        self.skip_unsafe_paths = skip;
+        self
+    }
+
+    /// Sets whether Unix mode bits held within each entry's external file attribute are applied to extracted files and
+    /// directories.
+    ///
+    /// Entries without Unix attribute compatibility are left with the default permissions. Directory permissions are
+    /// applied once all entries have been extracted, so that read-only directories can still be populated. This has no
+    /// effect on non-Unix targets.
+    pub fn preserve_permissions(mut self, preserve: bool) -> Self {
+        self.preserve_permissions = preserve;
+        self
+    }
+
+    /// Sets whether each extracted file's modification time is set from its entry.
+    ///
+    /// The time held within an extended timestamp extra field is preferred, falling back to the entry's MS-DOS
+    /// modification date & time (interpreted as UTC) otherwise.
+    pub fn preserve_modification_times(mut self, preserve: bool) -> Self {
+        self.preserve_modification_times = preserve;
+        self
+    }
+
+    /// Sets whether entries which represent symbolic links are recreated as such.
+    ///
+    /// Any link whose target would resolve outside of the destination directory is treated as an unsafe path (see
+    /// [`ExtractOptions::skip_unsafe_paths()`]), as is any entry whose path passes through a symlink (whether it already
+    /// exists or is created by the same extraction). When disabled (the default) or on non-Unix targets, such entries are
+    /// instead extracted as regular files containing the link's target.
+    pub fn symlinks(mut self, symlinks: bool) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// Sets a channel to which [`ProgressEvent`]s are sent as entries are processed.
+    ///
+    /// Events are sent without waiting, so a slow receiver never stalls processing. Any events sent after the receiver
+    /// has been dropped are discarded.
+    pub fn progress(mut self, sender: UnboundedSender<ProgressEvent>) -> Self {
+        self.progress = Some(sender);
+        self
+    }
+
+    /// Sets a token which aborts extraction with [`ZipError::Cancelled`](crate::error::ZipError::Cancelled) once
+    /// cancelled.
+    ///
+    /// Cancellation is checked whilst each entry is being extracted, so long-running entries are aborted promptly. Any
+    /// partially-written file is removed, whereas entries which were fully extracted beforehand are left in place.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Sets the max number of files extracted at once by
+    /// [`extract_to_dir_concurrent()`](crate::fs::extract_to_dir_concurrent).
+    ///
+    /// This defaults to the available parallelism of the host (see [`std::thread::available_parallelism()`]).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Sets the max total size (in bytes) of the read buffers held by files being extracted at once by
+    /// [`extract_to_dir_concurrent()`](crate::fs::extract_to_dir_concurrent).
+    ///
+    /// Each file's buffer is sized by its uncompressed size (between 8KiB and 1MiB), and a file isn't started until its
+    /// buffer fits within the remaining budget. Note that the state held by decompressors isn't accounted for. This
+    /// defaults to 64MiB.
+    pub fn memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Sets whether [`extract_to_dir()`](crate::fs::extract_to_dir) reads the next file's compressed data into memory
+    /// whilst the current file is being decompressed & written.
+    ///
+    /// This double-buffering overlaps reads of the source with decompression and writes to the file system, which helps
+    /// when extracting from slow sources. As two files' compressed data may be held at once, files whose compressed
+    /// size exceeds half of the [memory budget](ExtractOptions::memory_budget()) are instead read directly from the
+    /// source. This defaults to `false`.
+    pub fn prefetch(mut self, prefetch: bool) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Adds a glob pattern which entries must match to be extracted.
+    ///
+    /// If no include patterns are added, every entry is extracted. Patterns support `*` (any characters other than
+    /// `/`), `?` (any single character other than `/`), and `**` (any characters, including `/`). A pattern without a
+    /// `/` is matched against each component of an entry's path (eg. `*.json`), whereas any other pattern is matched
+    /// against its full path and each of its parent directories (eg. `assets/**/*.png`).
+    ///
+    /// ```
+    /// # use async_zip::fs::ExtractOptions;
+    /// #
+    /// let options = ExtractOptions::new().include(String::from("*.json")).exclude(String::from("node_modules"));
+    /// ```
+    pub fn include(mut self, pattern: String) -> Self {
+        self.filter.include(pattern);
+        self
+    }
+
+    /// Adds a glob pattern which excludes any matching entries from being extracted, even if they match an include
+    /// pattern (see [`ExtractOptions::include()`] for the syntax).
+    pub fn exclude(mut self, pattern: String) -> Self {
+        self.filter.exclude(pattern);
+        self
+    }
+
+    /// Sets whether macOS metadata entries (see [`ZipEntry::macos_metadata()`](crate::ZipEntry::macos_metadata)) are
+    /// skipped rather than extracted.
+    pub fn skip_macos_metadata(mut self, skip: bool) -> Self {
+        self.filter.skip_macos_metadata(skip);
+        self
+    }
+
+    /// Sets the behaviour when an entry is extracted to a path which already exists.
+    ///
+    /// This defaults to [`OverwritePolicy::Overwrite`]. Entries which are skipped are still reported as started and
+    /// finished to any progress channel, but without any bytes processed.
+    pub fn overwrite_policy(mut self, policy: OverwritePolicy) -> Self {
+        self.overwrite_policy = policy;
+        self
+    }
+}
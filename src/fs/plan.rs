@@ -0,0 +1,92 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::path::{Path, PathBuf};
+
+/// The plan of an extraction, as computed by [`plan_extraction()`](crate::fs::plan_extraction) without writing anything
+/// to the file system.
+#[derive(Debug, Clone)]
+pub struct ExtractionPlan {
+    pub(crate) entries: Vec<ExtractionPlanEntry>,
+    pub(crate) unsafe_entries: Vec<String>,
+}
+
+impl ExtractionPlan {
+    /// Returns the entries which would be extracted, in the order they'd be extracted.
+    pub fn entries(&self) -> &[ExtractionPlanEntry] {
+        &self.entries
+    }
+
+    /// Returns the entries whose destination path already exists.
+    pub fn conflicts(&self) -> impl Iterator<Item = &ExtractionPlanEntry> {
+        self.entries.iter().filter(|entry| entry.exists)
+    }
+
+    /// Returns the filenames of entries whose paths (or symlink targets) would escape the destination directory.
+    ///
+    /// These entries are never included within [`ExtractionPlan::entries()`], regardless of whether the options specify
+    /// that they should be skipped.
+    pub fn unsafe_entries(&self) -> &[String] {
+        &self.unsafe_entries
+    }
+
+    /// Returns the total uncompressed size of the files which would be written (ie. excluding any skipped entries).
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().filter(|entry| entry.action != PlannedAction::Skip).map(|entry| entry.size).sum()
+    }
+}
+
+/// A single entry within an [`ExtractionPlan`].
+#[derive(Debug, Clone)]
+pub struct ExtractionPlanEntry {
+    pub(crate) filename: String,
+    pub(crate) path: PathBuf,
+    pub(crate) size: u64,
+    pub(crate) exists: bool,
+    pub(crate) action: PlannedAction,
+}
+
+impl ExtractionPlanEntry {
+    /// Returns the entry's filename within the ZIP file.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Returns the entry's destination path (before any renaming due to the overwrite policy).
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the uncompressed size of the file which would be written, or zero for directories and symlinks.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns whether the entry's destination path already exists.
+    ///
+    /// Directories which already exist aren't treated as conflicts, as they're reused.
+    pub fn exists(&self) -> bool {
+        self.exists
+    }
+
+    /// Returns the action which extraction would take for this entry under the overwrite policy.
+    pub fn action(&self) -> &PlannedAction {
+        &self.action
+    }
+}
+
+/// The action which extraction would take for an entry, given the state of its destination path.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlannedAction {
+    /// The destination path would be created.
+    Create,
+    /// The existing destination path would be replaced.
+    Overwrite,
+    /// The entry would be skipped, leaving the existing destination path untouched.
+    Skip,
+    /// The entry would instead be extracted to the provided path, alongside the existing destination path.
+    Rename(PathBuf),
+    /// Extraction would be aborted, as the destination path already exists.
+    Error,
+}
@@ -0,0 +1,90 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// An event reporting the progress of an extraction or archiving helper.
+///
+/// Events are sent to the channel provided via [`ExtractOptions::progress()`](crate::fs::ExtractOptions::progress) or
+/// [`ArchiveOptions::progress()`](crate::fs::ArchiveOptions::progress), and are sent in the order listed here (with the
+/// per-entry events repeating for each entry).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// The total number of entries and uncompressed bytes which are about to be processed.
+    ///
+    /// This is only sent during extraction, as the totals aren't known upfront when archiving a directory.
+    Started { entries: u64, bytes: u64 },
+    /// An entry has started being processed, along with its uncompressed size.
+    EntryStarted { filename: String, size: u64 },
+    /// A number of uncompressed bytes of the current entry have been processed since the last event.
+    BytesProcessed { bytes: u64 },
+    /// The current entry has finished being processed.
+    EntryFinished { filename: String },
+    /// All entries have been processed, along with the total number of entries and uncompressed bytes processed.
+    Finished { entries: u64, bytes: u64 },
+}
+
+/// Sends an event to an optional progress channel, ignoring the case where the receiver has been dropped.
+pub(crate) fn send(sender: &Option<UnboundedSender<ProgressEvent>>, event: ProgressEvent) {
+    if let Some(sender) = sender {
+        let _ = sender.send(event);
+    }
+}
+
+/// A wrapper around a reader or writer which reports the number of bytes passing through it to a progress channel.
+pub(crate) struct ProgressIo<T> {
+    inner: T,
+    sender: Option<UnboundedSender<ProgressEvent>>,
+}
+
+impl<T> ProgressIo<T> {
+    pub(crate) fn new(inner: T, sender: Option<UnboundedSender<ProgressEvent>>) -> Self {
+        Self { inner, sender }
+    }
+
+    fn report(&self, bytes: usize) {
+        if bytes > 0 {
+            send(&self.sender, ProgressEvent::BytesProcessed { bytes: bytes as u64 });
+        }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for ProgressIo<T> {
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let filled = b.filled().len();
+        let poll = Pin::new(&mut this.inner).poll_read(c, b);
+
+        if let Poll::Ready(Ok(())) = poll {
+            this.report(b.filled().len() - filled);
+        }
+
+        poll
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for ProgressIo<T> {
+    fn poll_write(self: Pin<&mut Self>, c: &mut Context<'_>, b: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.inner).poll_write(c, b);
+
+        if let Poll::Ready(Ok(written)) = poll {
+            this.report(written);
+        }
+
+        poll
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(c)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(c)
+    }
+}
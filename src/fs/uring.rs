@@ -0,0 +1,238 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! File system helpers backed by [`tokio-uring`](https://crates.io/crates/tokio-uring).
+//!
+//! Rather than dispatching each file operation to a blocking thread pool (as `tokio::fs` does), every read & write is
+//! submitted to the kernel as a positioned io_uring operation. This substantially cuts the per-operation overhead when
+//! working with archives of many small entries.
+//!
+//! All futures within this module must be driven by the tokio-uring runtime (ie. within [`tokio_uring::start()`]), and
+//! aren't `Send`. Note that this requires the `uring` feature and is only available on Linux.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::fs::uring::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use tokio::io::AsyncReadExt;
+//! #
+//! fn run() -> Result<()> {
+//!     tokio_uring::start(async {
+//!         let reader = ZipFileReader::new("./foo.zip").await?;
+//!
+//!         let mut data = Vec::new();
+//!         reader.entry(0).await?.read_to_end(&mut data).await?;
+//!
+//!         async_zip::fs::uring::extract_to_dir("./foo.zip", "./foo").await
+//!     })
+//! }
+//! ```
+
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::read::io::entry::ZipEntryReader;
+use crate::read::ReaderOptions;
+
+use std::future::Future;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, BufReader, ReadBuf};
+use tokio_uring::fs::File;
+use tokio_uring::BufResult;
+
+type Operation = Pin<Box<dyn Future<Output = BufResult<usize, Vec<u8>>>>>;
+
+/// A file which implements tokio's IO traits via positioned io_uring reads & writes.
+///
+/// The cursor position is tracked in userspace, so seeking never requires a syscall.
+pub struct UringFile {
+    file: Rc<File>,
+    position: u64,
+    length: u64,
+    read: Option<Operation>,
+    write: Option<Operation>,
+}
+
+impl UringFile {
+    /// Opens a file in read-only mode.
+    pub async fn open(path: impl AsRef<Path>) -> std::io::Result<UringFile> {
+        let file = File::open(path).await?;
+        let length = file.statx().await?.stx_size;
+        Ok(Self::new(file, length))
+    }
+
+    /// Opens a file in write-only mode, creating it if it doesn't exist and truncating it if it does.
+    pub async fn create(path: impl AsRef<Path>) -> std::io::Result<UringFile> {
+        Ok(Self::new(File::create(path).await?, 0))
+    }
+
+    fn new(file: File, length: u64) -> Self {
+        Self { file: Rc::new(file), position: 0, length, read: None, write: None }
+    }
+}
+
+impl AsyncRead for UringFile {
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+
+        let operation = this.read.get_or_insert_with(|| {
+            let (file, position, length) = (this.file.clone(), this.position, b.remaining());
+            Box::pin(async move { file.read_at(vec![0; length], position).await })
+        });
+
+        let (result, data) = ready!(operation.as_mut().poll(c));
+        this.read = None;
+
+        // The caller's buffer may have shrunk since the read was submitted.
+        let read = result?.min(b.remaining());
+        b.put_slice(&data[..read]);
+        this.position += read as u64;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for UringFile {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        let this = self.get_mut();
+
+        let position = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => this.length.checked_add_signed(offset),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+        };
+
+        this.position = position.ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidInput))?;
+        this.read = None;
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        Poll::Ready(Ok(self.position))
+    }
+}
+
+impl AsyncWrite for UringFile {
+    fn poll_write(self: Pin<&mut Self>, c: &mut Context<'_>, b: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+
+        let operation = this.write.get_or_insert_with(|| {
+            let (file, position, data) = (this.file.clone(), this.position, b.to_vec());
+            Box::pin(async move { file.write_at(data, position).submit().await })
+        });
+
+        let (result, _) = ready!(operation.as_mut().poll(c));
+        this.write = None;
+
+        let written = result?;
+        this.position += written as u64;
+        this.length = this.length.max(this.position);
+
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        // Writes are unbuffered, so there's nothing to flush once they've completed.
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct Inner {
+    path: PathBuf,
+    file: ZipFile,
+}
+
+/// A concurrent ZIP reader which acts over a file system path, reading via io_uring.
+#[derive(Clone)]
+pub struct ZipFileReader {
+    inner: Arc<Inner>,
+}
+
+impl ZipFileReader {
+    /// Constructs a new ZIP reader from a file system path.
+    pub async fn new<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        Self::with_options(path, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a file system path and a set of reader options.
+    pub async fn with_options<P>(path: P, options: ReaderOptions) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_owned();
+        let open = async { crate::read::file_with_options(UringFile::open(&path).await?, &options).await };
+        let file = crate::read::with_open_timeout(&options, open).await?;
+
+        Ok(ZipFileReader { inner: Arc::new(Inner { path, file }) })
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.inner.file
+    }
+
+    /// Returns the file system path provided to the reader during construction.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'_, UringFile>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut fs_file = BufReader::new(UringFile::open(&self.inner.path).await?);
+
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut fs_file).await.map_err(context)?;
+
+        Ok(ZipEntryReader::new_with_owned(
+            fs_file,
+            stored_entry.entry.compression(),
+            stored_entry.entry.compressed_size(),
+        ))
+    }
+}
+
+/// Extracts all entries of a ZIP file on disk into a destination directory, one entry at a time.
+///
+/// As with [`crate::fs::extract_to_dir()`], every entry's path is validated before anything is written, and
+/// [`ZipError::UnsafeEntryPath`] is returned if any would escape the destination directory. Directories are created as
+/// needed, and each file's CRC32 value is verified as it's written.
+pub async fn extract_to_dir(path: impl AsRef<Path>, dest: impl AsRef<Path>) -> Result<()> {
+    let reader = ZipFileReader::new(path).await?;
+    let dest = dest.as_ref();
+
+    let paths =
+        reader.file().entries().iter().map(|stored_entry| crate::utils::safe_path(stored_entry.entry.filename()));
+    let paths = paths.collect::<Result<Vec<_>>>()?;
+
+    for (index, path) in paths.into_iter().enumerate() {
+        let entry = reader.file().entries()[index].entry();
+        let path = dest.join(path);
+
+        if entry.dir() {
+            tokio_uring::fs::create_dir_all(&path).await?;
+            continue;
+        }
+
+        if let Some(parent) = path.parent() {
+            tokio_uring::fs::create_dir_all(parent).await?;
+        }
+
+        let mut writer = UringFile::create(&path).await?;
+        reader.entry(index).await?.copy_to_end_checked(&mut writer, entry).await?;
+    }
+
+    Ok(())
+}
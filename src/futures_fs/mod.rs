@@ -0,0 +1,130 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which provides file system helpers for non-tokio executors (eg. smol or async-std).
+//!
+//! Files are accessed via [`async-fs`](https://crates.io/crates/async-fs), which runs blocking file operations on a
+//! thread pool and so works under any executor. Entry readers implement the `futures-io` traits (see the
+//! [`compat`](crate::read::compat) reader). Note that this requires the `futures-fs` feature.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::futures_fs::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use futures::io::AsyncReadExt;
+//! #
+//! async fn run() -> Result<()> {
+//!     let reader = ZipFileReader::new("./foo.zip").await?;
+//!
+//!     let mut data = Vec::new();
+//!     reader.entry(0).await?.read_to_end(&mut data).await?;
+//!
+//!     async_zip::futures_fs::extract_to_dir("./foo.zip", "./foo").await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::read::compat::ZipEntryReader;
+use crate::read::ReaderOptions;
+use crate::write::io::compat::Compat;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_fs::File;
+use tokio::io::BufReader;
+
+struct Inner {
+    path: PathBuf,
+    file: ZipFile,
+}
+
+/// A concurrent ZIP reader which acts over a file system path.
+///
+/// This mirrors the tokio-based [`crate::read::fs::ZipFileReader`], with a new file being opened for each entry reader.
+#[derive(Clone)]
+pub struct ZipFileReader {
+    inner: Arc<Inner>,
+}
+
+impl ZipFileReader {
+    /// Constructs a new ZIP reader from a file system path.
+    pub async fn new<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        Self::with_options(path, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a file system path and a set of reader options.
+    pub async fn with_options<P>(path: P, options: ReaderOptions) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_owned();
+        let open = async { crate::read::file_with_options(Compat::new(File::open(&path).await?), &options).await };
+        let file = crate::read::with_open_timeout(&options, open).await?;
+
+        Ok(ZipFileReader { inner: Arc::new(Inner { path, file }) })
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.inner.file
+    }
+
+    /// Returns the file system path provided to the reader during construction.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'_, File>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut fs_file = BufReader::new(Compat::new(File::open(&self.inner.path).await?));
+
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut fs_file).await.map_err(context)?;
+
+        Ok(ZipEntryReader(crate::read::io::entry::ZipEntryReader::new_with_owned(
+            fs_file,
+            stored_entry.entry.compression(),
+            stored_entry.entry.compressed_size(),
+        )))
+    }
+}
+
+/// Extracts all entries of a ZIP file on disk into a destination directory, one entry at a time.
+///
+/// As with [`crate::fs::extract_to_dir()`], every entry's path is validated before anything is written, and
+/// [`ZipError::UnsafeEntryPath`] is returned if any would escape the destination directory. Directories are created as
+/// needed, and each file's CRC32 value is verified as it's written.
+pub async fn extract_to_dir(path: impl AsRef<Path>, dest: impl AsRef<Path>) -> Result<()> {
+    let reader = ZipFileReader::new(path).await?;
+    let dest = dest.as_ref();
+
+    let paths =
+        reader.file().entries().iter().map(|stored_entry| crate::utils::safe_path(stored_entry.entry.filename()));
+    let paths = paths.collect::<Result<Vec<_>>>()?;
+
+    for (index, path) in paths.into_iter().enumerate() {
+        let entry = reader.file().entries()[index].entry();
+        let path = dest.join(path);
+
+        if entry.dir() {
+            async_fs::create_dir_all(&path).await?;
+            continue;
+        }
+
+        if let Some(parent) = path.parent() {
+            async_fs::create_dir_all(parent).await?;
+        }
+
+        let mut writer = File::create(&path).await?;
+        reader.entry(index).await?.copy_to_end_checked(&mut writer, entry).await?;
+    }
+
+    Ok(())
+}
@@ -6,16 +6,31 @@
 //! ## Features
 //! - Asynchronous design powered by tokio.
 //! - Support for Stored, Deflate, bzip2, LZMA, zstd, and xz compression methods.
-//! - Various different reading approaches (seek, stream, filesystem, in-memory buffer).
+//! - A pure-Rust `full-wasm` feature set for targets without a C toolchain (eg. musl cross-builds or wasm32).
+//! - Various different reading approaches (seek, shared, stream, filesystem, in-memory buffer).
 //! - Support for writing complete data (u8 slices) or stream writing using data descriptors.
+//! - Serialisable archive indexes (via the `serde` feature), so parsed central directories can be cached.
+//! - Readers, writers, and their futures are `Send` (given a `Send` source), so can be used within `tokio::spawn()`.
 //! - Aims for reasonable [specification](https://pkware.cachefly.net/webdocs/casestudies/APPNOTE.TXT) compliance.
 //!
 //! [Read more.](https://github.com/Majored/rs-async-zip)
 
+pub mod digest;
 pub mod error;
 pub mod read;
 pub mod write;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "fs")]
+pub mod fs;
+#[cfg(feature = "futures-fs")]
+pub mod futures_fs;
+#[cfg(feature = "tar")]
+pub mod tar;
+#[cfg(feature = "time")]
+pub mod throttle;
+
 pub(crate) mod entry;
 pub(crate) mod file;
 pub(crate) mod spec;
@@ -24,9 +39,12 @@ pub(crate) mod utils;
 #[cfg(test)]
 pub(crate) mod tests;
 
-pub use crate::spec::attribute::AttributeCompatibility;
-pub use crate::spec::compression::{Compression, DeflateOption};
+pub use crate::spec::attribute::{AttributeCompatibility, Attributes};
+pub use crate::spec::compression::{Compression, CompressionLevel, DeflateOption};
 pub use crate::spec::date::ZipDateTime;
+pub use crate::spec::encryption::Encryption;
+pub use crate::spec::extra_field::ExtraField;
+pub use crate::spec::filename::{FilenamePolicy, Utf8Flag};
 
-pub use crate::entry::{builder::ZipEntryBuilder, StoredZipEntry, ZipEntry};
-pub use crate::file::{builder::ZipFileBuilder, ZipFile};
+pub use crate::entry::{builder::IntoModificationDate, builder::ZipEntryBuilder, StoredZipEntry, ZipEntry};
+pub use crate::file::{builder::ZipFileBuilder, diff::ArchiveDiff, ZipFile};
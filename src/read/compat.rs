@@ -0,0 +1,158 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which acts over implementers of the `futures-io` [`AsyncRead`] & [`AsyncSeek`] traits (rather than
+//! tokio's).
+//!
+//! This mirrors the API of the [`seek`](crate::read::seek) module, and so allows ZIP files to be read from non-tokio
+//! executors (eg. smol or async-std) without any additional compatibility shim. Note that this module requires the
+//! `futures` feature.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::compat::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use futures::io::Cursor;
+//! #
+//! # async fn run() -> Result<()> {
+//! let mut reader = ZipFileReader::new(Cursor::new(Vec::new())).await?;
+//!
+//! let mut data = Vec::new();
+//! let entry = reader.file().entries()[0].entry().clone();
+//! reader.entry(0).await?.read_to_end_checked(&mut data, &entry).await?;
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::file::ZipFile;
+use crate::read::ReaderOptions;
+use crate::write::io::compat::Compat;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_io::{AsyncRead, AsyncSeek, AsyncWrite};
+use tokio::io::ReadBuf;
+
+/// A ZIP reader which acts over a seekable `futures-io` source.
+#[derive(Clone)]
+pub struct ZipFileReader<R>(crate::read::seek::ZipFileReader<Compat<R>>);
+
+impl<R> ZipFileReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new ZIP reader from a seekable source.
+    pub async fn new(reader: R) -> Result<ZipFileReader<R>> {
+        Ok(Self(crate::read::seek::ZipFileReader::new(Compat::new(reader)).await?))
+    }
+
+    /// Constructs a new ZIP reader from a seekable source and a set of reader options.
+    pub async fn with_options(reader: R, options: ReaderOptions) -> Result<ZipFileReader<R>> {
+        Ok(Self(crate::read::seek::ZipFileReader::with_options(Compat::new(reader), options).await?))
+    }
+
+    /// Constructs a ZIP reader from a seekable source and zip file information derived from that source.
+    pub fn from_parts(reader: R, file: ZipFile) -> ZipFileReader<R> {
+        Self(crate::read::seek::ZipFileReader::from_parts(Compat::new(reader), file))
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        self.0.file()
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        self.0.inner_mut().get_mut()
+    }
+
+    /// Unwraps this `ZipFileReader<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.0.into_inner().into_inner()
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R>> {
+        Ok(ZipEntryReader(self.0.entry(index).await?))
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    /// Consumes self
+    pub async fn into_entry<'a>(self, index: usize) -> Result<ZipEntryReader<'a, R>>
+    where
+        R: 'a,
+    {
+        Ok(ZipEntryReader(self.0.into_entry(index).await?))
+    }
+}
+
+/// A reader over a single entry's data which implements the `futures-io` [`AsyncRead`] trait.
+pub struct ZipEntryReader<'a, R>(pub(crate) crate::read::io::entry::ZipEntryReader<'a, Compat<R>>);
+
+impl<'a, R> ZipEntryReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Sets the max number of bytes which a single checked read may read into memory.
+    ///
+    /// See [`crate::read::seek::ZipEntryReader::allocation_limit()`].
+    pub fn allocation_limit(&mut self, limit: u64) {
+        self.0.allocation_limit(limit);
+    }
+
+    /// Returns the CRC32 value of the uncompressed bytes which have been read from this reader so far.
+    ///
+    /// See [`crate::read::seek::ZipEntryReader::crc32()`].
+    pub fn crc32(&self) -> u32 {
+        self.0.crc32()
+    }
+
+    /// Returns the number of compressed bytes which have been consumed from the underlying reader so far.
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.0.compressed_bytes_read()
+    }
+
+    /// Returns the number of uncompressed bytes which have been read from this reader so far.
+    pub fn uncompressed_bytes_read(&self) -> u64 {
+        self.0.uncompressed_bytes_read()
+    }
+
+    /// Reads all bytes until EOF has been reached, appending them to buf, and verifies the CRC32 values.
+    ///
+    /// See [`crate::read::seek::ZipEntryReader::read_to_end_checked()`].
+    pub async fn read_to_end_checked(&mut self, buf: &mut Vec<u8>, entry: &ZipEntry) -> Result<usize> {
+        self.0.read_to_end_checked(buf, entry).await
+    }
+
+    /// Reads all bytes until EOF has been reached, placing them into buf, and verifies the CRC32 values.
+    ///
+    /// See [`crate::read::seek::ZipEntryReader::read_to_string_checked()`].
+    pub async fn read_to_string_checked(&mut self, buf: &mut String, entry: &ZipEntry) -> Result<usize> {
+        self.0.read_to_string_checked(buf, entry).await
+    }
+
+    /// Copies all bytes until EOF has been reached into a `futures-io` writer, and verifies the CRC32 values.
+    ///
+    /// See [`crate::read::seek::ZipEntryReader::copy_to_end_checked()`].
+    pub async fn copy_to_end_checked<W>(&mut self, writer: &mut W, entry: &ZipEntry) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        self.0.copy_to_end_checked(&mut Compat::new(writer), entry).await
+    }
+}
+
+impl<'a, R> AsyncRead for ZipEntryReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let mut buf = ReadBuf::new(b);
+        let poll = tokio::io::AsyncRead::poll_read(Pin::new(&mut self.0), c, &mut buf);
+        poll.map_ok(|()| buf.filled().len())
+    }
+}
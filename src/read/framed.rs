@@ -0,0 +1,87 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Integration with [`tokio-util`](https://crates.io/crates/tokio-util)'s stream & codec pipelines.
+//!
+//! Entry readers may be converted into a [`Stream`](futures_util::stream::Stream) of [`Bytes`] chunks, and the stream
+//! reader may be constructed from any stream of byte buffers (eg. a [`FramedRead`](tokio_util::codec::FramedRead)
+//! using [`BytesCodec`](tokio_util::codec::BytesCodec), or a HTTP request body). Note that this requires the
+//! `tokio-util` feature.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::error::Result;
+//! # use async_zip::read::stream::ZipFileReader;
+//! # use bytes::Bytes;
+//! # use futures_util::stream;
+//! #
+//! # async fn run() -> Result<()> {
+//! let body = stream::iter([Ok::<_, std::io::Error>(Bytes::from_static(b"PK..."))]);
+//! let zip = ZipFileReader::from_byte_stream(body);
+//!
+//! if let Some(mut entry) = zip.next_entry().await? {
+//!     let chunks = entry.reader().reader_stream();
+//!     // Forward `chunks` elsewhere (eg. into a `Sink`).
+//! #   let _ = chunks;
+//! }
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+#[cfg(doc)]
+use bytes::Bytes;
+
+use crate::read::io::entry::ZipEntryReader;
+use crate::read::stream::{Ready, ZipFileReader};
+use crate::read::ReaderOptions;
+
+use bytes::Buf;
+use futures_util::stream::Stream;
+use tokio::io::AsyncRead;
+use tokio_util::io::{ReaderStream, StreamReader};
+
+impl<'a, R> ZipEntryReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Converts this entry reader into a [`Stream`] of [`Bytes`] chunks over the entry's decompressed data.
+    ///
+    /// Note that the CRC32 value of the data isn't checked, as the entry reader is consumed by the stream.
+    pub fn into_reader_stream(self) -> ReaderStream<Self> {
+        ReaderStream::new(self)
+    }
+
+    /// Converts this entry reader into a [`Stream`] of [`Bytes`] chunks, each of at most the provided capacity.
+    pub fn into_reader_stream_with_capacity(self, capacity: usize) -> ReaderStream<Self> {
+        ReaderStream::with_capacity(self, capacity)
+    }
+
+    /// Returns a [`Stream`] of [`Bytes`] chunks which borrows this entry reader.
+    ///
+    /// Unlike [`ZipEntryReader::into_reader_stream()`], the entry reader may continue to be used once the stream has
+    /// been dropped (eg. to check the CRC32 value of the data, or when read via the [`stream`](crate::read::stream)
+    /// reader which only lends out its entry readers).
+    pub fn reader_stream(&mut self) -> ReaderStream<&mut Self> {
+        ReaderStream::new(self)
+    }
+}
+
+impl<S, B, E> ZipFileReader<Ready<StreamReader<S, B>>>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf,
+    E: Into<std::io::Error>,
+{
+    /// Constructs a new ZIP reader from a [`Stream`] of byte buffers (eg. a [`FramedRead`]).
+    ///
+    /// [`FramedRead`]: tokio_util::codec::FramedRead
+    pub fn from_byte_stream(stream: S) -> Self {
+        Self::new(StreamReader::new(stream))
+    }
+
+    /// Constructs a new ZIP reader from a [`Stream`] of byte buffers and a set of reader options.
+    pub fn from_byte_stream_with_options(stream: S, options: ReaderOptions) -> Self {
+        Self::with_options(StreamReader::new(stream), options)
+    }
+}
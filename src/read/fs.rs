@@ -73,12 +73,13 @@ use crate::read::seek;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 use crate::read::io::entry::ZipEntryReader;
+use crate::read::ReaderOptions;
 
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use tokio::fs::File;
-use tokio::io::{AsyncSeekExt, BufReader, SeekFrom};
+use tokio::io::BufReader;
 
 struct Inner {
     path: PathBuf,
@@ -94,11 +95,22 @@ pub struct ZipFileReader {
 impl ZipFileReader {
     /// Constructs a new ZIP reader from a file system path.
     pub async fn new<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        Self::with_options(path, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a file system path and a set of reader options.
+    pub async fn with_options<P>(path: P, options: ReaderOptions) -> Result<ZipFileReader>
     where
         P: AsRef<Path>,
     {
         let path = path.as_ref().to_owned();
-        let file = crate::read::file(File::open(&path).await?).await?;
+        let file = crate::read::with_open_timeout(&options, async {
+            crate::read::file_with_options(File::open(&path).await?, &options).await
+        })
+        .await?;
 
         Ok(ZipFileReader { inner: Arc::new(Inner { path, file }) })
     }
@@ -114,16 +126,17 @@ impl ZipFileReader {
     }
 
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<File>> {
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'_, File>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let mut fs_file = BufReader::new(File::open(&self.inner.path).await?);
 
-        stored_entry.seek_to_data_offset(&mut fs_file).await?;
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut fs_file).await.map_err(context)?;
 
         Ok(ZipEntryReader::new_with_owned(
             fs_file,
             stored_entry.entry.compression(),
-            stored_entry.entry.uncompressed_size().into(),
+            stored_entry.entry.compressed_size(),
         ))
     }
 }
@@ -0,0 +1,91 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::io::entry::ZipEntryReader;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::Stream;
+use pin_project::pin_project;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// The default max size of each chunk yielded by an [`EntryBytesStream`], equal to 64KiB.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A stream of [`Bytes`] chunks over an entry's data, as returned by [`ZipEntryReader::into_bytes_stream()`].
+///
+/// This is suitable for use directly as an HTTP response body (eg. via hyper's or axum's stream body constructors).
+/// Note that the CRC32 value of the entry's data isn't verified.
+#[pin_project]
+pub struct EntryBytesStream<'a, R> {
+    #[pin]
+    reader: ZipEntryReader<'a, R>,
+    buffer: BytesMut,
+    chunk_size: usize,
+    done: bool,
+}
+
+impl<'a, R> EntryBytesStream<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Constructs a new stream from an entry reader.
+    pub(crate) fn new(reader: ZipEntryReader<'a, R>) -> Self {
+        Self { reader, buffer: BytesMut::new(), chunk_size: DEFAULT_CHUNK_SIZE, done: false }
+    }
+
+    /// Sets the max size of each chunk yielded by this stream.
+    ///
+    /// Chunks may be smaller than this size. By default, chunks are at most 64KiB.
+    ///
+    /// # Panics
+    /// Panics if the provided size is zero.
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "chunk size must be greater than zero");
+        self.chunk_size = size;
+        self
+    }
+
+    /// Consumes this stream, returning the underlying entry reader.
+    pub fn into_inner(self) -> ZipEntryReader<'a, R> {
+        self.reader
+    }
+}
+
+impl<'a, R> Stream for EntryBytesStream<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    type Item = std::io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        this.buffer.resize(*this.chunk_size, 0);
+        let mut read_buf = ReadBuf::new(&mut this.buffer[..]);
+
+        match this.reader.poll_read(c, &mut read_buf) {
+            Poll::Ready(Ok(())) => {
+                let read = read_buf.filled().len();
+
+                if read == 0 {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+
+                Poll::Ready(Some(Ok(this.buffer.split_to(read).freeze())))
+            }
+            Poll::Ready(Err(err)) => {
+                *this.done = true;
+                Poll::Ready(Some(Err(err)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "parallel")]
+use crate::read::io::offload::OffloadedDecoder;
 use crate::spec::compression::Compression;
 
 use std::pin::Pin;
@@ -8,8 +10,6 @@ use std::task::{Context, Poll};
 
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use async_compression::tokio::bufread;
-#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
-use tokio::io::BufReader;
 
 use pin_project::pin_project;
 use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
@@ -28,6 +28,8 @@ pub(crate) enum CompressedReader<R> {
     Zstd(#[pin] bufread::ZstdDecoder<R>),
     #[cfg(feature = "xz")]
     Xz(#[pin] bufread::XzDecoder<R>),
+    #[cfg(feature = "parallel")]
+    Offloaded(#[pin] OffloadedDecoder<R>),
 }
 
 impl<R> CompressedReader<R>
@@ -51,6 +53,25 @@ where
         }
     }
 
+    /// Returns a reference to the inner value.
+    pub(crate) fn get_ref(&self) -> &R {
+        match self {
+            CompressedReader::Stored(inner) => inner,
+            #[cfg(feature = "deflate")]
+            CompressedReader::Deflate(inner) => inner.get_ref(),
+            #[cfg(feature = "bzip2")]
+            CompressedReader::Bz(inner) => inner.get_ref(),
+            #[cfg(feature = "lzma")]
+            CompressedReader::Lzma(inner) => inner.get_ref(),
+            #[cfg(feature = "zstd")]
+            CompressedReader::Zstd(inner) => inner.get_ref(),
+            #[cfg(feature = "xz")]
+            CompressedReader::Xz(inner) => inner.get_ref(),
+            #[cfg(feature = "parallel")]
+            CompressedReader::Offloaded(inner) => inner.get_ref(),
+        }
+    }
+
     /// Consumes this reader and returns the inner value.
     pub(crate) fn into_inner(self) -> R {
         match self {
@@ -65,8 +86,36 @@ where
             CompressedReader::Zstd(inner) => inner.into_inner(),
             #[cfg(feature = "xz")]
             CompressedReader::Xz(inner) => inner.into_inner(),
+            #[cfg(feature = "parallel")]
+            CompressedReader::Offloaded(inner) => inner.into_inner(),
         }
     }
+
+    /// Moves decompression onto tokio's blocking thread pool, if this isn't a stored or already-offloaded reader.
+    ///
+    /// This must be called before any data has been read, and from within a tokio runtime.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(
+        not(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz")),
+        allow(unreachable_code, unused_variables)
+    )]
+    pub(crate) fn offloaded(self) -> Self {
+        let compression = match &self {
+            CompressedReader::Stored(_) | CompressedReader::Offloaded(_) => return self,
+            #[cfg(feature = "deflate")]
+            CompressedReader::Deflate(_) => Compression::Deflate,
+            #[cfg(feature = "bzip2")]
+            CompressedReader::Bz(_) => Compression::Bz,
+            #[cfg(feature = "lzma")]
+            CompressedReader::Lzma(_) => Compression::Lzma,
+            #[cfg(feature = "zstd")]
+            CompressedReader::Zstd(_) => Compression::Zstd,
+            #[cfg(feature = "xz")]
+            CompressedReader::Xz(_) => Compression::Xz,
+        };
+
+        CompressedReader::Offloaded(OffloadedDecoder::new(self.into_inner(), compression))
+    }
 }
 
 impl<R> AsyncRead for CompressedReader<R>
@@ -86,6 +135,8 @@ where
             CompressedReaderProj::Zstd(inner) => inner.poll_read(c, b),
             #[cfg(feature = "xz")]
             CompressedReaderProj::Xz(inner) => inner.poll_read(c, b),
+            #[cfg(feature = "parallel")]
+            CompressedReaderProj::Offloaded(inner) => inner.poll_read(c, b),
         }
     }
 }
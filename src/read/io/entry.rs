@@ -6,16 +6,57 @@ use crate::error::{Result, ZipError};
 use crate::read::io::{compressed::CompressedReader, hashed::HashedReader, owned::OwnedReader};
 use crate::spec::compression::Compression;
 
+use std::io::{ErrorKind, IoSlice};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use pin_project::pin_project;
-use tokio::io::{AsyncRead, AsyncReadExt, BufReader, ReadBuf, Take};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader, ReadBuf, Take};
+
+/// The buffer size used when copying an entry's data to a writer, equal to 64KiB.
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The number of chunks the copy buffer is split into when copying to a writer which supports vectored writes.
+const VECTORED_CHUNKS: usize = 4;
+
+/// The max capacity reserved upfront when reading an entry's data into memory, equal to 1MiB.
+///
+/// An entry's uncompressed size is untrusted (as it's read from the archive), so any further capacity is only reserved
+/// as data is actually read.
+const MAX_PREALLOCATION_SIZE: u64 = 1024 * 1024;
+
+/// Returns the capacity to reserve upfront when reading an entry's data into memory.
+pub(crate) fn preallocation_size(entry: &ZipEntry) -> usize {
+    std::cmp::min(entry.uncompressed_size(), MAX_PREALLOCATION_SIZE) as usize
+}
+
+/// Writes the filled portion of each chunk of a buffer to a writer via vectored writes, until all have been written.
+async fn write_all_vectored<W>(writer: &mut W, buffer: &[u8], chunk_size: usize, filled: &[usize]) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let chunks = buffer.chunks(chunk_size).zip(filled).filter(|(_, filled)| **filled != 0);
+    let mut slices: Vec<IoSlice<'_>> = chunks.map(|(chunk, filled)| IoSlice::new(&chunk[..*filled])).collect();
+    let mut slices = &mut slices[..];
+
+    while !slices.is_empty() {
+        match writer.write_vectored(slices).await? {
+            0 => return Err(std::io::Error::from(ErrorKind::WriteZero).into()),
+            written => IoSlice::advance_slices(&mut slices, written),
+        }
+    }
+
+    Ok(())
+}
 
 #[pin_project]
 pub struct ZipEntryReader<'a, R> {
     #[pin]
     reader: HashedReader<CompressedReader<Take<OwnedReader<'a, R>>>>,
+    size: u64,
+    allocation_limit: Option<u64>,
+    // Whether a read has returned EOF (ie. the decompressor has produced all of the entry's data).
+    eof: bool,
 }
 
 impl<'a, R> ZipEntryReader<'a, R>
@@ -24,12 +65,14 @@ where
 {
     /// Constructs a new entry reader from its required parameters (incl. an owned R).
     pub(crate) fn new_with_owned(reader: BufReader<R>, compression: Compression, size: u64) -> Self {
-        Self { reader: HashedReader::new(CompressedReader::new(OwnedReader::Owned(reader).take(size), compression)) }
+        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Owned(reader).take(size), compression));
+        Self { reader, size, allocation_limit: None, eof: false }
     }
 
     /// Constructs a new entry reader from its required parameters (incl. a mutable borrow of an R).
     pub(crate) fn new_with_borrow(reader: BufReader<&'a mut R>, compression: Compression, size: u64) -> Self {
-        Self { reader: HashedReader::new(CompressedReader::new(OwnedReader::Borrow(reader).take(size), compression)) }
+        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Borrow(reader).take(size), compression));
+        Self { reader, size, allocation_limit: None, eof: false }
     }
 }
 
@@ -38,7 +81,15 @@ where
     R: AsyncRead + Unpin,
 {
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
-        self.project().reader.poll_read(c, b)
+        let this = self.project();
+        let filled = b.filled().len();
+        let poll = this.reader.poll_read(c, b);
+
+        if let Poll::Ready(Ok(())) = poll {
+            *this.eof |= b.remaining() > 0 && b.filled().len() == filled;
+        }
+
+        poll
     }
 }
 
@@ -49,20 +100,40 @@ where
     /// Computes and returns the CRC32 hash of bytes read by this reader so far.
     ///
     /// This hash should only be computed once EOF has been reached.
-    fn compute_hash(&mut self) -> u32 {
-        self.reader.swap_and_compute_hash()
+    pub(crate) fn compute_hash(&self) -> u32 {
+        self.reader.compute_hash()
+    }
+
+    /// Sets the max number of bytes which a single [`read_to_end_checked()`] or [`read_to_string_checked()`] call may
+    /// read into memory.
+    ///
+    /// If more data is available, [`ZipError::AllocationLimitExceeded`] is returned instead (with the data read so far
+    /// left within the buffer). This guards against entries whose actual size is far larger than expected, such as
+    /// decompression bombs. By default, there's no limit.
+    ///
+    /// [`read_to_end_checked()`]: Self::read_to_end_checked
+    /// [`read_to_string_checked()`]: Self::read_to_string_checked
+    pub fn allocation_limit(&mut self, limit: u64) {
+        self.allocation_limit = Some(limit);
     }
 
     /// Reads all bytes until EOF has been reached, appending them to buf, and verifies the CRC32 values.
     ///
-    /// This is a helper function synonymous to [`AsyncReadExt::read_to_end()`].
+    /// Capacity is reserved upfront based on the entry's uncompressed size, though this is capped as the size can't be
+    /// trusted. This is a helper function synonymous to [`AsyncReadExt::read_to_end()`].
     pub async fn read_to_end_checked(&mut self, buf: &mut Vec<u8>, entry: &ZipEntry) -> Result<usize> {
-        let read = self.read_to_end(buf).await?;
+        buf.reserve(preallocation_size(entry));
+
+        let read = match self.allocation_limit {
+            Some(limit) => self.limited(limit).read_to_end(buf).await?,
+            None => self.read_to_end(buf).await?,
+        };
+        self.check_allocation_limit(read)?;
 
         if self.compute_hash() == entry.crc32() {
             Ok(read)
         } else {
-            Err(ZipError::CRC32CheckError)
+            Err(ZipError::CRC32CheckError.in_entry(None, entry.filename()))
         }
     }
 
@@ -70,17 +141,147 @@ where
     ///
     /// This is a helper function synonymous to [`AsyncReadExt::read_to_string()`].
     pub async fn read_to_string_checked(&mut self, buf: &mut String, entry: &ZipEntry) -> Result<usize> {
-        let read = self.read_to_string(buf).await?;
+        let read = match self.allocation_limit {
+            Some(limit) => self.limited(limit).read_to_string(buf).await?,
+            None => self.read_to_string(buf).await?,
+        };
+        self.check_allocation_limit(read)?;
 
         if self.compute_hash() == entry.crc32() {
             Ok(read)
         } else {
-            Err(ZipError::CRC32CheckError)
+            Err(ZipError::CRC32CheckError.in_entry(None, entry.filename()))
         }
     }
 
-    /// Consumes this reader and returns the inner value.
-    pub(crate) fn into_inner(self) -> R {
+    /// Copies all bytes until EOF has been reached into a writer, and verifies the CRC32 values.
+    ///
+    /// Returns the number of bytes copied. Errors from either the underlying reader or the writer are returned rather
+    /// than causing a panic. Where the writer supports vectored writes (see [`AsyncWrite::is_write_vectored()`]), the
+    /// data of several reads is gathered into a single vectored write.
+    ///
+    /// This is a helper function synonymous to [`tokio::io::copy()`].
+    pub async fn copy_to_end_checked<W>(&mut self, writer: &mut W, entry: &ZipEntry) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut buffer = vec![0; COPY_BUFFER_SIZE];
+        let chunks = if writer.is_write_vectored() { VECTORED_CHUNKS } else { 1 };
+        let chunk_size = COPY_BUFFER_SIZE / chunks;
+        let mut copied = 0;
+
+        loop {
+            // Each chunk is filled by its own read, so that short reads (eg. of a single deflate block) can still be
+            // written together.
+            let mut filled = [0; VECTORED_CHUNKS];
+            for (chunk, filled) in buffer.chunks_mut(chunk_size).zip(&mut filled) {
+                *filled = self.read(chunk).await?;
+
+                if *filled == 0 {
+                    break;
+                }
+            }
+
+            let read: usize = filled.iter().sum();
+            if read == 0 {
+                break;
+            }
+
+            match chunks {
+                1 => writer.write_all(&buffer[..read]).await?,
+                _ => write_all_vectored(writer, &buffer, chunk_size, &filled).await?,
+            }
+            copied += read as u64;
+        }
+
+        writer.flush().await?;
+
+        if self.compute_hash() == entry.crc32() {
+            Ok(copied)
+        } else {
+            Err(ZipError::CRC32CheckError.in_entry(None, entry.filename()))
+        }
+    }
+
+    /// Returns a reader which reads at most one byte past the provided limit (so that exceeding it can be detected).
+    fn limited(&mut self, limit: u64) -> Take<&mut Self> {
+        AsyncReadExt::take(self, limit.saturating_add(1))
+    }
+
+    fn check_allocation_limit(&self, read: usize) -> Result<()> {
+        match self.allocation_limit {
+            Some(limit) if read as u64 > limit => Err(ZipError::AllocationLimitExceeded(limit)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the CRC32 value of the uncompressed bytes which have been read from this reader so far.
+    ///
+    /// This only matches the entry's stored CRC32 value once EOF has been reached, but may be used to build manifests
+    /// whilst streaming an entry's data elsewhere (without hashing it a second time).
+    pub fn crc32(&self) -> u32 {
+        self.compute_hash()
+    }
+
+    /// Returns the number of compressed bytes which have been consumed from the underlying reader so far.
+    ///
+    /// The decompressor may consume input ahead of the data it has produced, so this is best suited to progress
+    /// reporting against the entry's compressed size.
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.size - self.reader.reader.get_ref().limit()
+    }
+
+    /// Returns the number of uncompressed bytes which have been read from this reader so far.
+    pub fn uncompressed_bytes_read(&self) -> u64 {
+        self.reader.bytes_read
+    }
+
+    /// Moves the decompression of this entry's data onto tokio's blocking thread pool.
+    ///
+    /// Compressed data is still read from the source as this reader is polled, but is then decompressed on another
+    /// thread and passed back through a bounded channel. This prevents CPU-heavy decompression (eg. of large bzip2 or
+    /// xz entries) from stalling other tasks on the same runtime thread, at the cost of some copying. Readers of stored
+    /// entries, or which have already consumed some of their data, are returned unchanged.
+    ///
+    /// Note that this requires the `parallel` feature, and must be called from within a tokio runtime.
+    #[cfg(feature = "parallel")]
+    pub fn offloaded(self) -> Self {
+        if self.compressed_bytes_read() > 0 {
+            return self;
+        }
+
+        let ZipEntryReader { reader, size, allocation_limit, eof } = self;
+        Self { reader: HashedReader::new(reader.into_inner().offloaded()), size, allocation_limit, eof }
+    }
+
+    /// Returns whether a read has returned EOF.
+    pub(crate) fn reached_eof(&self) -> bool {
+        self.eof
+    }
+
+    /// Returns the number of compressed bytes which are yet to be consumed from the underlying reader.
+    pub(crate) fn compressed_bytes_remaining(&self) -> u64 {
+        self.reader.reader.get_ref().limit()
+    }
+
+    /// Consumes this reader and returns the inner buffered value.
+    pub(crate) fn into_inner(self) -> BufReader<R> {
         self.reader.into_inner().into_inner().into_inner().owned_into_inner()
     }
 }
+
+#[cfg(feature = "futures")]
+impl<'a, R> ZipEntryReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Converts this reader into a [`Stream`] of [`Bytes`] chunks over the entry's data.
+    ///
+    /// The size of each chunk can be configured via [`EntryBytesStream::chunk_size()`].
+    ///
+    /// [`Stream`]: futures_util::stream::Stream
+    /// [`Bytes`]: bytes::Bytes
+    pub fn into_bytes_stream(self) -> crate::read::EntryBytesStream<'a, R> {
+        crate::read::EntryBytesStream::new(self)
+    }
+}
@@ -16,6 +16,7 @@ pub(crate) struct HashedReader<R> {
     #[pin]
     pub(crate) reader: R,
     pub(crate) hasher: Hasher,
+    pub(crate) bytes_read: u64,
 }
 
 impl<R> HashedReader<R>
@@ -24,16 +25,15 @@ where
 {
     /// Constructs a new wrapping reader from a generic [`AsyncRead`] implementer.
     pub(crate) fn new(reader: R) -> Self {
-        Self { reader, hasher: Hasher::default() }
+        Self { reader, hasher: Hasher::default(), bytes_read: 0 }
     }
 
-    /// Swaps the internal hasher and returns the computed CRC32 hash.
+    /// Returns the computed CRC32 hash of all data read so far.
     ///
-    /// The internal hasher is taken and replaced with a newly-constructed one. As a result, this method should only be
-    /// called once EOF has been reached and it's known that no more data will be read, else the computed hash(s) won't
-    /// accurately represent the data read in.
-    pub(crate) fn swap_and_compute_hash(&mut self) -> u32 {
-        std::mem::take(&mut self.hasher).finalize()
+    /// The internal hasher is left untouched so this may be called more than once (eg. by a checked read followed by a
+    /// data descriptor comparison), but should only be relied upon once EOF has been reached.
+    pub(crate) fn compute_hash(&self) -> u32 {
+        self.hasher.clone().finalize()
     }
 
     /// Consumes this reader and returns the inner value.
@@ -52,6 +52,7 @@ where
 
         poll_result_ok!(ready!(project.reader.poll_read(c, b)));
         project.hasher.update(&b.filled()[prev_len..b.filled().len()]);
+        *project.bytes_read += (b.filled().len() - prev_len) as u64;
 
         Poll::Ready(Ok(()))
     }
@@ -1,30 +1,54 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "futures")]
+pub(crate) mod bytes_stream;
 pub(crate) mod compressed;
 pub(crate) mod entry;
 pub(crate) mod hashed;
 pub(crate) mod locator;
+#[cfg(feature = "parallel")]
+pub(crate) mod offload;
 pub(crate) mod owned;
 
+use std::io::ErrorKind;
+
 use tokio::io::{AsyncRead, AsyncReadExt};
 
+/// The size of the scratch buffer used when skipping over data, equal to 512 bytes.
+const SKIP_BUFFER_SIZE: usize = 512;
+
 /// Read and return a dynamic length string from a reader which impls AsyncRead.
 pub(crate) async fn read_string<R: AsyncRead + Unpin>(reader: R, length: usize) -> std::io::Result<String> {
-    let mut buffer = String::with_capacity(length);
-    reader.take(length as u64).read_to_string(&mut buffer).await?;
-
-    Ok(buffer)
+    let buffer = read_bytes(reader, length).await?;
+    String::from_utf8(buffer).map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))
 }
 
 /// Read and return a dynamic length vector of bytes from a reader which impls AsyncRead.
-pub(crate) async fn read_bytes<R: AsyncRead + Unpin>(reader: R, length: usize) -> std::io::Result<Vec<u8>> {
-    let mut buffer = Vec::with_capacity(length);
-    reader.take(length as u64).read_to_end(&mut buffer).await?;
+///
+/// The vector is allocated at its exact length upfront, rather than being grown (and reallocated) as data is read.
+pub(crate) async fn read_bytes<R: AsyncRead + Unpin>(mut reader: R, length: usize) -> std::io::Result<Vec<u8>> {
+    let mut buffer = vec![0; length];
+    reader.read_exact(&mut buffer).await?;
 
     Ok(buffer)
 }
 
+/// Read and discard a number of bytes from a reader which impls AsyncRead.
+///
+/// The data is read via a scratch buffer on the stack, so nothing is allocated for it.
+pub(crate) async fn skip_bytes<R: AsyncRead + Unpin>(mut reader: R, mut length: usize) -> std::io::Result<()> {
+    let mut scratch = [0; SKIP_BUFFER_SIZE];
+
+    while length > 0 {
+        let chunk = std::cmp::min(length, SKIP_BUFFER_SIZE);
+        reader.read_exact(&mut scratch[..chunk]).await?;
+        length -= chunk;
+    }
+
+    Ok(())
+}
+
 /// A macro that returns the inner value of an Ok or early-returns in the case of an Err.
 ///
 /// This is almost identical to the ? operator but handles the situation when a Result is used in combination with
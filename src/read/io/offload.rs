@@ -0,0 +1,182 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::io::compressed::CompressedReader;
+use crate::spec::compression::Compression;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, ReadBuf};
+use tokio::runtime::Handle;
+use tokio::sync::mpsc::{self, error::SendError, OwnedPermit, Receiver, Sender};
+
+/// The size of the chunks passed to & from the decompressing thread, equal to 64KiB.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The number of chunks which may be buffered within each channel.
+const CHANNEL_CAPACITY: usize = 4;
+
+type Reserve = Pin<Box<dyn Future<Output = Result<OwnedPermit<Vec<u8>>, SendError<()>>> + Send + Sync>>;
+
+/// A decompressing reader which runs its decompressor on tokio's blocking thread pool.
+///
+/// Compressed data is read from the source as this reader is polled and passed to the decompressing thread over a
+/// bounded channel, with the decompressed data flowing back over another. As such, CPU-heavy decompression never stalls
+/// the task polling this reader, and the source needn't be `Send` or `'static`.
+pub(crate) struct OffloadedDecoder<R> {
+    source: R,
+    input: Option<Sender<Vec<u8>>>,
+    reserve: Option<Reserve>,
+    permit: Option<OwnedPermit<Vec<u8>>>,
+    output: Receiver<std::io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    position: usize,
+}
+
+impl<R> OffloadedDecoder<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Spawns the decompressing thread and constructs a new reader over its output.
+    ///
+    /// This must be called from within a tokio runtime.
+    pub(crate) fn new(source: R, compression: Compression) -> Self {
+        let (input, input_receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        let (output_sender, output) = mpsc::channel(CHANNEL_CAPACITY);
+        let handle = Handle::current();
+
+        // The thread exits once either channel is closed, so it never outlives this reader for long.
+        tokio::task::spawn_blocking(move || {
+            handle.block_on(async move {
+                let chunks = ChannelReader { receiver: input_receiver, chunk: Vec::new(), position: 0 };
+                let mut decoder = CompressedReader::new(chunks, compression);
+
+                loop {
+                    let mut chunk = vec![0; CHUNK_SIZE];
+                    let result = decoder.read(&mut chunk).await.map(|read| {
+                        chunk.truncate(read);
+                        chunk
+                    });
+
+                    let done = !matches!(&result, Ok(chunk) if !chunk.is_empty());
+                    if output_sender.send(result).await.is_err() || done {
+                        break;
+                    }
+                }
+            })
+        });
+
+        Self { source, input: Some(input), reserve: None, permit: None, output, chunk: Vec::new(), position: 0 }
+    }
+
+    /// Returns a reference to the source of compressed data.
+    pub(crate) fn get_ref(&self) -> &R {
+        &self.source
+    }
+
+    /// Consumes this reader and returns the source of compressed data.
+    pub(crate) fn into_inner(self) -> R {
+        self.source
+    }
+
+    /// Passes the next chunk of compressed data to the decompressing thread.
+    fn poll_feed(&mut self, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let Some(input) = &self.input else {
+            return Poll::Pending;
+        };
+
+        if self.permit.is_none() {
+            let reserve = self.reserve.get_or_insert_with(|| Box::pin(input.clone().reserve_owned()));
+            let permit = ready!(reserve.as_mut().poll(c));
+            self.reserve = None;
+
+            match permit {
+                Ok(permit) => self.permit = Some(permit),
+                // The thread has already exited, so its output (or error) is all that's left to read.
+                Err(_) => {
+                    self.input = None;
+                    return Poll::Ready(Ok(()));
+                }
+            }
+        }
+
+        let data = ready!(Pin::new(&mut self.source).poll_fill_buf(c))?;
+
+        // Dropping the sender signals the end of the compressed data.
+        if data.is_empty() {
+            (self.input, self.permit) = (None, None);
+            return Poll::Ready(Ok(()));
+        }
+
+        let length = std::cmp::min(data.len(), CHUNK_SIZE);
+        self.permit.take().expect("permit reserved").send(data[..length].to_vec());
+        Pin::new(&mut self.source).consume(length);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R> AsyncRead for OffloadedDecoder<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.position < this.chunk.len() {
+                let length = std::cmp::min(b.remaining(), this.chunk.len() - this.position);
+                b.put_slice(&this.chunk[this.position..this.position + length]);
+                this.position += length;
+                return Poll::Ready(Ok(()));
+            }
+
+            match this.output.poll_recv(c) {
+                Poll::Ready(Some(Ok(chunk))) => (this.chunk, this.position) = (chunk, 0),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Err(err)),
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => ready!(this.poll_feed(c))?,
+            }
+        }
+    }
+}
+
+/// A reader over the chunks of compressed data received by the decompressing thread.
+struct ChannelReader {
+    receiver: Receiver<Vec<u8>>,
+    chunk: Vec<u8>,
+    position: usize,
+}
+
+impl AsyncBufRead for ChannelReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        while this.position == this.chunk.len() {
+            match ready!(this.receiver.poll_recv(c)) {
+                Some(chunk) => (this.chunk, this.position) = (chunk, 0),
+                None => return Poll::Ready(Ok(&[])),
+            }
+        }
+
+        Poll::Ready(Ok(&this.chunk[this.position..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().position += amt;
+    }
+}
+
+impl AsyncRead for ChannelReader {
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let data = ready!(self.as_mut().poll_fill_buf(c))?;
+        let length = std::cmp::min(b.remaining(), data.len());
+
+        b.put_slice(&data[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(()))
+    }
+}
@@ -21,10 +21,10 @@ impl<'a, R> OwnedReader<'a, R>
 where
     R: AsyncRead + Unpin,
 {
-    /// Consumes an owned reader and returns the inner value.
-    pub(crate) fn owned_into_inner(self) -> R {
+    /// Consumes an owned reader and returns the inner buffered value.
+    pub(crate) fn owned_into_inner(self) -> BufReader<R> {
         match self {
-            OwnedReader::Owned(inner) => inner.into_inner(),
+            OwnedReader::Owned(inner) => inner,
             OwnedReader::Borrow(_) => panic!("not OwnedReader::Owned value"),
         }
     }
@@ -3,6 +3,9 @@
 
 //! A concurrent ZIP reader which acts over an owned vector of bytes.
 //!
+//! Any other owned value which derefs to bytes (ie. implements [`AsRef<[u8]>`]) may also be used as the source, such as
+//! a memory-mapped file (see the `read::mmap` module, which requires the `mmap` feature).
+//!
 //! Concurrency is achieved as a result of:
 //! - Wrapping the provided vector of bytes within an [`Arc`] to allow shared ownership.
 //! - Wrapping this [`Arc`] around a [`Cursor`] when reading (as the [`Arc`] can deref and coerce into a `&[u8]`).
@@ -73,27 +76,50 @@ use crate::read::seek;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 use crate::read::io::entry::ZipEntryReader;
+use crate::read::ReaderOptions;
+use crate::spec::compression::Compression;
 
-use std::io::Cursor;
+use std::io::{Cursor, ErrorKind};
+use std::ops::Range;
 use std::sync::Arc;
 
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+
 use tokio::io::BufReader;
 
-struct Inner {
-    data: Vec<u8>,
+struct Inner<D> {
+    data: D,
     file: ZipFile,
 }
 
-// A concurrent ZIP reader which acts over an owned vector of bytes.
-#[derive(Clone)]
-pub struct ZipFileReader {
-    inner: Arc<Inner>,
+// A concurrent ZIP reader which acts over an owned vector of bytes (or any other owned value which derefs to bytes).
+pub struct ZipFileReader<D = Vec<u8>> {
+    inner: Arc<Inner<D>>,
+}
+
+impl<D> Clone for ZipFileReader<D> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
 }
 
-impl ZipFileReader {
+impl<D> ZipFileReader<D>
+where
+    D: AsRef<[u8]>,
+{
     /// Constructs a new ZIP reader from an owned vector of bytes.
-    pub async fn new(data: Vec<u8>) -> Result<ZipFileReader> {
-        let file = crate::read::file(Cursor::new(&data)).await?;
+    pub async fn new(data: D) -> Result<ZipFileReader<D>> {
+        Self::with_options(data, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from an owned vector of bytes and a set of reader options.
+    pub async fn with_options(data: D, options: ReaderOptions) -> Result<ZipFileReader<D>> {
+        let file = crate::read::with_open_timeout(
+            &options,
+            crate::read::file_with_options(Cursor::new(data.as_ref()), &options),
+        )
+        .await?;
         Ok(ZipFileReader { inner: Arc::new(Inner { data, file }) })
     }
 
@@ -104,20 +130,82 @@ impl ZipFileReader {
 
     /// Returns the raw bytes provided to the reader during construction.
     pub fn data(&self) -> &[u8] {
-        &self.inner.data
+        self.inner.data.as_ref()
     }
 
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<Cursor<&[u8]>>> {
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut cursor = BufReader::new(Cursor::new(&self.inner.data[..]));
+        let mut cursor = BufReader::new(Cursor::new(self.data()));
 
-        stored_entry.seek_to_data_offset(&mut cursor).await?;
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut cursor).await.map_err(context)?;
 
         Ok(ZipEntryReader::new_with_owned(
             cursor,
             stored_entry.entry.compression(),
-            stored_entry.entry.uncompressed_size().into(),
+            stored_entry.entry.compressed_size(),
         ))
     }
+
+    /// Returns a slice of a stored entry's data if the provided index is valid.
+    ///
+    /// The returned slice borrows directly from the bytes provided during construction, so no copying takes place.
+    /// As such, this is only supported for entries which use the [`Compression::Stored`] method. Note that the CRC32
+    /// value of the data isn't verified.
+    pub async fn stored_entry_data(&self, index: usize) -> Result<&[u8]> {
+        let range = self.stored_entry_range(index).await?;
+        Ok(&self.data()[range])
+    }
+
+    /// Returns a stored entry's data as [`Bytes`] if the provided index is valid.
+    ///
+    /// As with [`ZipFileReader::stored_entry_data()`], no copying takes place and this is only supported for entries
+    /// which use the [`Compression::Stored`] method. However, the returned [`Bytes`] shares ownership of the source
+    /// rather than borrowing this reader, so it can be handed off elsewhere (eg. as part of a response body).
+    ///
+    /// Note that this requires the `bytes` feature.
+    #[cfg(feature = "bytes")]
+    pub async fn read_entry_bytes(&self, index: usize) -> Result<Bytes>
+    where
+        D: Send + Sync + 'static,
+    {
+        let range = self.stored_entry_range(index).await?;
+        Ok(Bytes::from_owner(SharedData(self.inner.clone())).slice(range))
+    }
+
+    /// Returns the range of a stored entry's data within the source.
+    async fn stored_entry_range(&self, index: usize) -> Result<Range<usize>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if stored_entry.entry.compression() != Compression::Stored {
+            return Err(ZipError::FeatureNotSupported("zero-copy reads of compressed entries"));
+        }
+
+        let data = self.data();
+        let mut cursor = Cursor::new(data);
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_data_offset(&mut cursor).await.map_err(context)?;
+
+        // The compressed size is untrusted (and may be up to 64 bits wide), so the range may not fit within the source.
+        let start = usize::try_from(cursor.position()).ok();
+        let size = usize::try_from(stored_entry.entry.compressed_size()).ok();
+        let range = start.zip(size).and_then(|(start, size)| Some(start..start.checked_add(size)?));
+
+        match range.filter(|range| data.get(range.clone()).is_some()) {
+            Some(range) => Ok(range),
+            None => Err(ZipError::UpstreamReadError(ErrorKind::UnexpectedEof.into())),
+        }
+    }
+}
+
+/// The source of a [`ZipFileReader`], as owned by the [`Bytes`] returned from [`ZipFileReader::read_entry_bytes()`].
+#[cfg(feature = "bytes")]
+struct SharedData<D>(Arc<Inner<D>>);
+
+#[cfg(feature = "bytes")]
+impl<D: AsRef<[u8]>> AsRef<[u8]> for SharedData<D> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.data.as_ref()
+    }
 }
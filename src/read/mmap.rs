@@ -0,0 +1,58 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A concurrent ZIP reader which acts over a memory-mapped file.
+//!
+//! This is a thin layer over the [`mem`] module's reader with [`Mmap`] as its source. As with that reader, entries
+//! can be read concurrently, and stored entries can be borrowed directly from the mapping without any copying via
+//! [`ZipFileReader::stored_entry_data()`]. With the `bytes` feature, they can also be returned as [`Bytes`] which keep
+//! the mapping alive via `ZipFileReader::read_entry_bytes()`.
+//!
+//! [`Bytes`]: https://docs.rs/bytes/latest/bytes/struct.Bytes.html
+//!
+//! Note that this requires the `mmap` feature.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::mmap::ZipFileReader;
+//! # use async_zip::error::Result;
+//! #
+//! async fn run() -> Result<()> {
+//!     // SAFETY: The file isn't modified or truncated by any other process whilst it's mapped.
+//!     let reader = unsafe { ZipFileReader::map("./foo.zip") }.await?;
+//!     let data = reader.stored_entry_data(0).await?;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//! ```
+
+#[cfg(doc)]
+use crate::read::mem;
+
+use crate::error::Result;
+
+use std::path::Path;
+
+pub use memmap2::Mmap;
+
+/// A concurrent ZIP reader which acts over a memory-mapped file.
+pub type ZipFileReader = crate::read::mem::ZipFileReader<Mmap>;
+
+impl ZipFileReader {
+    /// Constructs a new ZIP reader by memory-mapping the file at the provided path.
+    ///
+    /// # Safety
+    /// The same requirements as [`Mmap::map()`] apply; the underlying file must not be modified or truncated whilst
+    /// it's mapped (including by other processes), else the behaviour of this reader is undefined.
+    pub async unsafe fn map<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let file = tokio::fs::File::open(path).await?.into_std().await;
+        let mmap = Mmap::map(&file)?;
+
+        ZipFileReader::new(mmap).await
+    }
+}
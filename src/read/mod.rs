@@ -3,63 +3,306 @@
 
 //! A module which supports reading ZIP files.
 
+#[cfg(feature = "futures")]
+pub mod compat;
 pub mod mem;
+pub mod nested;
+pub mod remote;
 pub mod seek;
+pub mod shared;
 pub mod stream;
 
+#[cfg(feature = "tokio-util")]
+pub mod framed;
 #[cfg(feature = "fs")]
 pub mod fs;
+#[cfg(feature = "mmap")]
+pub mod mmap;
 
 pub(crate) mod io;
+pub(crate) mod options;
+pub(crate) mod verify;
+pub(crate) mod warning;
+
+#[cfg(feature = "futures")]
+pub use io::bytes_stream::EntryBytesStream;
+pub use options::ReaderOptions;
+pub use verify::{VerificationFailure, VerificationReport};
+pub use warning::ZipWarning;
 
 use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 use crate::spec::attribute::AttributeCompatibility;
 use crate::spec::compression::Compression;
-use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::spec::consts::{
+    AES_COMPRESSION_METHOD, ARCHIVE_EXTRA_DATA_SIGNATURE, CDH_LENGTH, CDH_SIGNATURE, DATA_DESCRIPTOR_LENGTH,
+    DATA_DESCRIPTOR_SIGNATURE, SIGNATURE_LENGTH,
+};
+use crate::spec::consts::{LFH_SIGNATURE, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::consts::{
+    ZIP64_DATA_DESCRIPTOR_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDL_SIGNATURE, ZIP64_EOCDR_SIGNATURE,
+};
 use crate::spec::date::ZipDateTime;
-use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader, LocalFileHeader};
+use crate::spec::extra_field::Zip64Values;
+use crate::spec::filename::{FilenamePolicy, Utf8Flag};
+use crate::spec::header::{
+    CentralDirectoryRecord, DataDescriptor, EndOfCentralDirectoryHeader, GeneralPurposeFlag, LocalFileHeader,
+    Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
+};
+
+use std::future::Future;
 
-use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader, SeekFrom};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader, SeekFrom};
 
 /// The max buffer size used when parsing the central directory, equal to 20MiB.
 const MAX_CD_BUFFER_SIZE: usize = 20 * 1024 * 1024;
 
-pub(crate) async fn file<R>(mut reader: R) -> Result<ZipFile>
+/// Drives a future which opens a ZIP file, aborting it if the configured open timeout elapses first.
+///
+/// The timeout relies upon tokio's timer, so an error is returned where one is configured outside of a tokio runtime.
+pub(crate) async fn with_open_timeout<F, T>(options: &ReaderOptions, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    #[cfg(feature = "time")]
+    if let Some(timeout) = options.open_timeout {
+        if tokio::runtime::Handle::try_current().is_err() {
+            return Err(ZipError::FeatureNotSupported("open timeouts outside of a tokio runtime"));
+        }
+
+        return tokio::time::timeout(timeout, future).await.map_err(|_| ZipError::OpenTimedOut(timeout))?;
+    }
+
+    #[cfg(not(feature = "time"))]
+    let _ = options;
+
+    future.await
+}
+
+pub(crate) async fn file<R>(reader: R) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    file_with_options(reader, &ReaderOptions::default()).await
+}
+
+pub(crate) async fn file_with_options<R>(mut reader: R, options: &ReaderOptions) -> Result<ZipFile>
 where
     R: AsyncRead + AsyncSeek + Unpin,
 {
-    let eocdr_offset = crate::read::io::locator::eocdr(&mut reader).await?;
+    let window = options.eocdr_search_window.unwrap_or(crate::read::io::locator::EOCDR_LOWER_BOUND);
+    let eocdr_offset = crate::read::io::locator::eocdr_within(&mut reader, window).await?;
 
     reader.seek(SeekFrom::Start(eocdr_offset)).await?;
-    let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
-    let comment = crate::read::io::read_string(&mut reader, eocdr.file_comm_length.into()).await?;
+    let eocdr = async {
+        let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
+        let comment = crate::read::io::read_string(&mut reader, eocdr.file_comm_length.into()).await?;
+        Result::Ok((eocdr, comment))
+    };
+    let (eocdr, comment) = eocdr.await.map_err(|err| err.at_offset(eocdr_offset))?;
+
+    if options.strict {
+        let end = reader.stream_position().await?;
+        let length = reader.seek(SeekFrom::End(0)).await?;
 
-    // Outdated feature so unlikely to ever make it into this crate.
-    if eocdr.disk_num != eocdr.start_cent_dir_disk || eocdr.num_of_entries != eocdr.num_of_entries_disk {
+        if length > end {
+            return Err(ZipError::TrailingData(length - end).at_offset(end));
+        }
+    }
+
+    // Spanned/split files are an outdated feature so unlikely to ever make it into this crate. Some writers store nonzero
+    // or sentinel disk numbers within single-file archives, so only the entry counts are relied upon to detect them.
+    if eocdr.num_of_entries != eocdr.num_of_entries_disk {
         return Err(ZipError::FeatureNotSupported("Spanned/split files"));
     }
 
-    reader.seek(SeekFrom::Start(eocdr.cent_dir_offset.into())).await?;
+    let mut num_of_entries: u64 = eocdr.num_of_entries.into();
+    let mut size_cent_dir: u64 = eocdr.size_cent_dir.into();
+    let mut cent_dir_offset: u64 = eocdr.cent_dir_offset.into();
+
+    let zip64 = match zip64_eocdr(&mut reader, eocdr_offset, &eocdr).await? {
+        Some(zip64_eocdr) => {
+            if zip64_eocdr.num_of_entries != zip64_eocdr.num_of_entries_disk {
+                return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+            }
+
+            num_of_entries = zip64_eocdr.num_of_entries;
+            size_cent_dir = zip64_eocdr.size_cent_dir;
+            cent_dir_offset = zip64_eocdr.cent_dir_offset;
+            true
+        }
+        None => false,
+    };
+
+    if let Some(max) = options.max_entries.filter(|max| num_of_entries > *max) {
+        return Err(ZipError::EntryLimitExceeded(max));
+    }
+
+    reader.seek(SeekFrom::Start(cent_dir_offset)).await?;
 
     // To avoid lots of small reads to `reader` when parsing the central directory, we use a BufReader that can read the whole central directory at once.
-    // Because `eocdr.size_cent_dir` is a u32, we use MAX_CD_BUFFER_SIZE to prevent very large buffer sizes.
-    let buf = BufReader::with_capacity(std::cmp::min(eocdr.size_cent_dir as _, MAX_CD_BUFFER_SIZE), reader);
-    let entries = crate::read::cd(buf, eocdr.num_of_entries.into()).await?;
+    // We use MAX_CD_BUFFER_SIZE to prevent very large buffer sizes.
+    let capacity = size_cent_dir.try_into().unwrap_or(MAX_CD_BUFFER_SIZE);
+    let mut buf = BufReader::with_capacity(std::cmp::min(capacity, MAX_CD_BUFFER_SIZE), reader);
+
+    // An encrypted central directory is preceded by an archive extra data record (rather than starting with a CDH).
+    if buf.fill_buf().await?.starts_with(&ARCHIVE_EXTRA_DATA_SIGNATURE.to_le_bytes()) {
+        return Err(ZipError::FeatureNotSupported("Central directory encryption"));
+    }
+    let mut warnings = Vec::new();
+    let entries = crate::read::cd(&mut buf, num_of_entries, cent_dir_offset, options.lenient.then_some(&mut warnings));
+    let mut entries = entries.await?;
+
+    if options.trust_central_directory {
+        entries.iter_mut().for_each(StoredZipEntry::trust_central_directory);
+    }
+
+    if options.lenient {
+        for (index, stored_entry) in entries.iter().enumerate() {
+            warnings.extend(entry_warnings(&mut buf, index, stored_entry, cent_dir_offset).await);
+        }
+    }
+
+    let (disk_number, cent_dir_disk_number) = (eocdr.disk_num, eocdr.start_cent_dir_disk);
+    Ok(ZipFile { entries, comment, zip64, warnings, disk_number, cent_dir_disk_number })
+}
+
+/// Checks an entry's central directory record for anomalies, and compares it against its local file header.
+///
+/// Failures to read the local file header are also reported as warnings, so that one corrupt entry doesn't prevent the
+/// remainder from being checked.
+async fn entry_warnings<R>(
+    mut reader: R,
+    index: usize,
+    stored_entry: &StoredZipEntry,
+    cent_dir_offset: u64,
+) -> Vec<ZipWarning>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let entry = &stored_entry.entry;
+    let mut warnings = Vec::new();
+
+    if !crate::spec::extra_field::is_well_formed(&entry.extra_field) {
+        warnings.push(ZipWarning::MalformedExtraField { index });
+    }
+
+    if stored_entry.file_offset.saturating_add(entry.compressed_size) > cent_dir_offset {
+        warnings.push(ZipWarning::DataOverlapsCentralDirectory { index });
+    }
+
+    let signature = match reader.seek(SeekFrom::Start(stored_entry.file_offset)).await {
+        Ok(_) => reader.read_u32_le().await.ok(),
+        Err(_) => None,
+    };
+
+    if signature != Some(LFH_SIGNATURE) {
+        warnings.push(ZipWarning::MissingLocalFileHeader { index });
+        return warnings;
+    }
+
+    let Ok(header) = LocalFileHeader::from_reader(&mut reader).await else {
+        warnings.push(ZipWarning::UnreadableLocalFileHeader { index });
+        return warnings;
+    };
+
+    // Sizes which are saturated are instead held within a ZIP64 extra field, and are zeroed (along with the CRC32
+    // value) when followed by a data descriptor.
+    let size_differs = |size: u32, expected: u64| size != NON_ZIP64_MAX_SIZE && u64::from(size) != expected;
+    let mismatch = header.compression != stored_entry.compression_method
+        || !header.flags.data_descriptor
+            && (header.crc != entry.crc32
+                || size_differs(header.compressed_size, entry.compressed_size)
+                || size_differs(header.uncompressed_size, entry.uncompressed_size));
+
+    if mismatch {
+        warnings.push(ZipWarning::LocalFileHeaderMismatch { index });
+    }
+
+    warnings
+}
+
+/// Reads the ZIP64 end of central directory record if any of the EOCDR's fields hold their sentinel values.
+///
+/// The provided offset is that returned by the EOCDR locator (ie. directly after the EOCDR's signature).
+async fn zip64_eocdr<R>(
+    mut reader: R,
+    eocdr_offset: u64,
+    eocdr: &EndOfCentralDirectoryHeader,
+) -> Result<Option<Zip64EndOfCentralDirectoryRecord>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let saturated = eocdr.num_of_entries == NON_ZIP64_MAX_NUM_FILES
+        || eocdr.size_cent_dir == NON_ZIP64_MAX_SIZE
+        || eocdr.cent_dir_offset == NON_ZIP64_MAX_SIZE;
+
+    // The locator directly precedes the EOCDR, so we step back over both of their signatures and the locator itself.
+    let locator_offset = eocdr_offset.checked_sub((SIGNATURE_LENGTH * 2 + ZIP64_EOCDL_LENGTH) as u64);
+    let Some(locator_offset) = locator_offset.filter(|_| saturated) else {
+        return Ok(None);
+    };
+
+    reader.seek(SeekFrom::Start(locator_offset)).await?;
+
+    // An archive may legitimately hold a sentinel value without being ZIP64 (eg. exactly 65535 entries).
+    if reader.read_u32_le().await? != ZIP64_EOCDL_SIGNATURE {
+        return Ok(None);
+    }
+
+    let locator = Zip64EndOfCentralDirectoryLocator::from_reader(&mut reader).await?;
+
+    if locator.total_disks > 1 {
+        return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+    }
+
+    reader.seek(SeekFrom::Start(locator.zip64_eocdr_offset)).await?;
+    let zip64_eocdr = async {
+        crate::utils::assert_signature(&mut reader, ZIP64_EOCDR_SIGNATURE).await?;
+        Zip64EndOfCentralDirectoryRecord::from_reader(&mut reader).await
+    };
 
-    Ok(ZipFile { entries, comment, zip64: false })
+    Ok(Some(zip64_eocdr.await.map_err(|err| err.at_offset(locator.zip64_eocdr_offset))?))
 }
 
-pub(crate) async fn cd<R>(mut reader: R, num_of_entries: u64) -> Result<Vec<StoredZipEntry>>
+/// Reads a number of central directory records, the first of which starts at the provided offset within the archive.
+///
+/// If a store of warnings is provided, a record which fails to parse ends the central directory early (with a warning)
+/// rather than returning an error.
+pub(crate) async fn cd<R>(
+    mut reader: R,
+    num_of_entries: u64,
+    mut offset: u64,
+    mut warnings: Option<&mut Vec<ZipWarning>>,
+) -> Result<Vec<StoredZipEntry>>
 where
     R: AsyncRead + Unpin,
 {
     let num_of_entries = num_of_entries.try_into().map_err(|_| ZipError::TargetZip64NotSupported)?;
-    let mut entries = Vec::with_capacity(num_of_entries);
+    // The entry count is untrusted (and may be very large within a ZIP64 archive), so cap the initial allocation.
+    let mut entries = Vec::with_capacity(std::cmp::min(num_of_entries, NON_ZIP64_MAX_NUM_FILES.into()));
 
     for _ in 0..num_of_entries {
-        let entry = cd_record(&mut reader).await?;
+        // Sources which are always ready (eg. memory or a memory map) would otherwise never yield back to the runtime,
+        // so an open timeout couldn't fire until the whole central directory had been parsed.
+        #[cfg(feature = "time")]
+        tokio::task::consume_budget().await;
+
+        let entry = match (cd_record(&mut reader).await, warnings.as_mut()) {
+            (Ok(entry), _) => entry,
+            (Err(_), Some(warnings)) => {
+                let found = entries.len() as u64;
+                warnings.push(ZipWarning::TruncatedCentralDirectory { expected: num_of_entries as u64, found });
+                break;
+            }
+            (Err(err), None) => return Err(err.at_offset(offset)),
+        };
+
+        // The variable-length fields were read in full, so their lengths match those held within the record.
+        let trailing_length = entry.entry.filename.len() + entry.entry.extra_field.len() + entry.entry.comment.len();
+        offset += (SIGNATURE_LENGTH + CDH_LENGTH + trailing_length) as u64;
+
         entries.push(entry);
     }
 
@@ -74,62 +317,119 @@ where
 
     let header = CentralDirectoryRecord::from_reader(&mut reader).await?;
     let filename = crate::read::io::read_string(&mut reader, header.file_name_length.into()).await?;
-    let compression = Compression::try_from(header.compression)?;
     let extra_field = crate::read::io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
     let comment = crate::read::io::read_string(reader, header.file_comment_length.into()).await?;
 
+    // The actual method of an AES-encrypted entry is held within its AES extra field. An unsupported method is only
+    // reported once the entry's data is read, so that the rest of the archive can still be listed (or copied as-is).
+    let compression = match header.compression {
+        AES_COMPRESSION_METHOD => crate::spec::extra_field::aes_compression_method(&extra_field),
+        method => Some(method),
+    };
+    let compression = compression.and_then(|method| Compression::try_from(method).ok()).unwrap_or(Compression::Stored);
+
+    let mut zip64 = Zip64Values::new(&extra_field);
+    let uncompressed_size = zip64.resolve(header.uncompressed_size);
+    let compressed_size = zip64.resolve(header.compressed_size);
+    let file_offset = zip64.resolve(header.lh_offset);
+
     let entry = ZipEntry {
         filename,
         compression,
         #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
         compression_level: async_compression::Level::Default,
-        attribute_compatibility: AttributeCompatibility::Unix,
-        /// FIXME: Default to Unix for the moment
+        // Other hosts fall back to Unix, as most of them (eg. OS X) also store Unix modes.
+        attribute_compatibility: AttributeCompatibility::try_from(header.v_made_by >> 8)
+            .unwrap_or(AttributeCompatibility::Unix),
         crc32: header.crc,
-        uncompressed_size: header.uncompressed_size,
-        compressed_size: header.compressed_size,
+        uncompressed_size,
+        compressed_size,
         last_modification_date: ZipDateTime { date: header.mod_date, time: header.mod_time },
         internal_file_attribute: header.inter_attr,
         external_file_attribute: header.exter_attr,
         extra_field,
         comment,
+        force_zip64: false,
+        unix_modification_time: None,
+        extended_timestamp: false,
+        alignment: 0,
+        encryption: None,
+        auto_compression: false,
+        filename_policy: FilenamePolicy::default(),
+        utf8_flag: Utf8Flag::default(),
     };
 
-    // general_purpose_flag: header.flags,
-    Ok(StoredZipEntry { entry, file_offset: header.lh_offset as u64 })
+    let (general_purpose_flag, compression_method, disk_start) = (header.flags, header.compression, header.disk_start);
+    Ok(StoredZipEntry { entry, general_purpose_flag, compression_method, file_offset, disk_start, data_offset: None })
 }
 
-pub(crate) async fn lfh<R>(mut reader: R) -> Result<Option<ZipEntry>>
+/// Reads a local file header (following its signature) and its variable-length fields.
+pub(crate) async fn lfh<R>(mut reader: R) -> Result<(ZipEntry, GeneralPurposeFlag)>
 where
     R: AsyncRead + Unpin,
 {
-    match reader.read_u32_le().await? {
-        actual if actual == LFH_SIGNATURE => (),
-        actual if actual == CDH_SIGNATURE => return Ok(None),
-        actual => return Err(ZipError::UnexpectedHeaderError(actual, LFH_SIGNATURE)),
-    };
-
     let header = LocalFileHeader::from_reader(&mut reader).await?;
     let filename = crate::read::io::read_string(&mut reader, header.file_name_length.into()).await?;
     let compression = Compression::try_from(header.compression)?;
     let extra_field = crate::read::io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
 
+    let mut zip64 = Zip64Values::new(&extra_field);
+    let uncompressed_size = zip64.resolve(header.uncompressed_size);
+    let compressed_size = zip64.resolve(header.compressed_size);
+
     let entry = ZipEntry {
         filename,
         compression,
         #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
         compression_level: async_compression::Level::Default,
+        // FIXME: Default to Unix for the moment
         attribute_compatibility: AttributeCompatibility::Unix,
-        /// FIXME: Default to Unix for the moment
         crc32: header.crc,
-        uncompressed_size: header.uncompressed_size,
-        compressed_size: header.compressed_size,
+        uncompressed_size,
+        compressed_size,
         last_modification_date: ZipDateTime { date: header.mod_date, time: header.mod_time },
         internal_file_attribute: 0,
         external_file_attribute: 0,
         extra_field,
         comment: String::new(),
+        force_zip64: false,
+        unix_modification_time: None,
+        extended_timestamp: false,
+        alignment: 0,
+        encryption: None,
+        auto_compression: false,
+        filename_policy: FilenamePolicy::default(),
+        utf8_flag: Utf8Flag::default(),
+    };
+
+    Ok((entry, header.flags))
+}
+
+/// Reads a data descriptor, returning it along with its length in bytes (which varies as its signature is optional).
+///
+/// The sizes held within the descriptor are 8 bytes wide if the entry's local file header held a ZIP64 extra field.
+pub(crate) async fn data_descriptor<R>(mut reader: R, zip64: bool) -> Result<(DataDescriptor, u64)>
+where
+    R: AsyncRead + Unpin,
+{
+    let length = if zip64 { ZIP64_DATA_DESCRIPTOR_LENGTH } else { DATA_DESCRIPTOR_LENGTH };
+    let mut buffer = [0; ZIP64_DATA_DESCRIPTOR_LENGTH];
+    let first = reader.read_u32_le().await?;
+
+    // If the signature was omitted, the value read was the CRC32 value.
+    let signature_length = if first == DATA_DESCRIPTOR_SIGNATURE {
+        reader.read_exact(&mut buffer[..length]).await?;
+        SIGNATURE_LENGTH
+    } else {
+        buffer[0..4].copy_from_slice(&first.to_le_bytes());
+        reader.read_exact(&mut buffer[4..length]).await?;
+        0
+    };
+
+    let descriptor = match zip64 {
+        true => DataDescriptor::from(buffer),
+        false => DataDescriptor::from(<[u8; DATA_DESCRIPTOR_LENGTH]>::try_from(&buffer[..length]).unwrap()),
     };
 
-    Ok(Some(entry))
+    Ok((descriptor, (signature_length + length) as u64))
 }
@@ -0,0 +1,94 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Support for reading a ZIP file held within an entry of another ZIP file (eg. a JAR within a ZIP).
+//!
+//! See [`ZipFileReader::nested_entry()`](crate::read::seek::ZipFileReader::nested_entry).
+
+use crate::read::io::entry::ZipEntryReader;
+use crate::read::{seek, stream, stream::Ready};
+
+use std::io::{ErrorKind, SeekFrom};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+/// A reader over a ZIP file held within an entry of another ZIP file.
+pub enum NestedZipReader<'a, R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// The entry was stored without compression, so the inner ZIP file is read via seeks within the outer source.
+    Seek(seek::ZipFileReader<EntryRange<&'a mut R>>),
+    /// The entry was compressed, so the inner ZIP file is read sequentially as the entry is decompressed.
+    ///
+    /// Boxed as the reader holds onto any peeked entry.
+    Stream(Box<stream::ZipFileReader<Ready<ZipEntryReader<'a, R>>>>),
+}
+
+/// A seekable view over a contiguous byte range of an inner source (ie. a stored entry's data).
+///
+/// Offsets are relative to the start of the range, and reads never extend past its end.
+pub struct EntryRange<R> {
+    reader: R,
+    start: u64,
+    length: u64,
+    position: u64,
+}
+
+impl<R> EntryRange<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a view over the provided range, which the reader must already be positioned at the start of.
+    pub(crate) fn new(reader: R, start: u64, length: u64) -> Self {
+        Self { reader, start, length, position: 0 }
+    }
+}
+
+impl<R> AsyncRead for EntryRange<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let remaining = this.length.saturating_sub(this.position);
+        let limit = std::cmp::min(remaining, b.remaining() as u64) as usize;
+
+        let mut limited = ReadBuf::new(&mut b.initialize_unfilled()[..limit]);
+        ready!(Pin::new(&mut this.reader).poll_read(c, &mut limited))?;
+
+        let read = limited.filled().len();
+        b.advance(read);
+        this.position += read as u64;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R> AsyncSeek for EntryRange<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        let this = self.get_mut();
+
+        let position = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => this.length.checked_add_signed(offset),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+        };
+
+        let position = position.ok_or_else(|| std::io::Error::from(ErrorKind::InvalidInput))?;
+        Pin::new(&mut this.reader).start_seek(SeekFrom::Start(this.start + position))
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        let position = ready!(Pin::new(&mut this.reader).poll_complete(c))?;
+
+        this.position = position.saturating_sub(this.start);
+        Poll::Ready(Ok(this.position))
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::stream::UnconsumedEntryBehavior;
+
+#[cfg(feature = "time")]
+use std::time::Duration;
+
+/// The default buffer size used when reading an entry's data, equal to 8KiB (as per tokio's [`BufReader`]).
+///
+/// [`BufReader`]: tokio::io::BufReader
+const DEFAULT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// A set of options which configure how a ZIP file is opened and read.
+///
+/// ### Example
+/// ```
+/// # use async_zip::read::ReaderOptions;
+/// #
+/// let options = ReaderOptions::new();
+/// #
+/// # #[cfg(feature = "time")]
+/// # let options = options.open_timeout(std::time::Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReaderOptions {
+    #[cfg(feature = "time")]
+    pub(crate) open_timeout: Option<Duration>,
+    pub(crate) unconsumed_entry_behavior: UnconsumedEntryBehavior,
+    pub(crate) lenient: bool,
+    pub(crate) buffer_size: Option<usize>,
+    pub(crate) max_entries: Option<u64>,
+    pub(crate) eocdr_search_window: Option<u64>,
+    pub(crate) strict: bool,
+    pub(crate) trust_central_directory: bool,
+    #[cfg(feature = "parallel")]
+    pub(crate) offload_threshold: Option<u64>,
+}
+
+impl ReaderOptions {
+    /// Constructs a new set of options with every option at its default value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an overall deadline for opening the ZIP file (ie. locating and parsing the central directory).
+    ///
+    /// If the deadline passes before the ZIP file has been opened, parsing is aborted and
+    /// [`ZipError::OpenTimedOut`](crate::error::ZipError::OpenTimedOut) is returned. Parsing periodically yields back
+    /// to the runtime, so this also applies to sources which are always ready (such as an in-memory buffer).
+    ///
+    /// The deadline is tracked by tokio's timer, so it requires a tokio runtime (with its time driver enabled) even
+    /// where the reader is otherwise runtime-agnostic (eg. over futures-io). Opening outside of a tokio runtime fails
+    /// with [`ZipError::FeatureNotSupported`](crate::error::ZipError::FeatureNotSupported).
+    ///
+    /// Note that this requires the `time` feature.
+    #[cfg(feature = "time")]
+    pub fn open_timeout(mut self, timeout: Duration) -> Self {
+        self.open_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the behaviour when a streamed entry is dropped before all of its data has been consumed.
+    ///
+    /// This only applies to the [`stream`](crate::read::stream) reader and defaults to
+    /// [`UnconsumedEntryBehavior::Log`].
+    pub fn unconsumed_entry_behavior(mut self, behavior: UnconsumedEntryBehavior) -> Self {
+        self.unconsumed_entry_behavior = behavior;
+        self
+    }
+
+    /// Sets whether non-fatal anomalies are collected whilst opening the ZIP file, rather than failing the parse.
+    ///
+    /// When enabled, a central directory which ends earlier than declared yields the entries which could be parsed, and
+    /// each entry's local file header is compared against its central directory record. Any anomalies are available via
+    /// [`ZipFile::warnings()`](crate::ZipFile::warnings). Note that the latter requires a seek per entry, so opening
+    /// is slower. This defaults to `false`.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets the size of the buffer used when reading an entry's data.
+    ///
+    /// This applies to the [`seek`](crate::read::seek) and [`stream`](crate::read::stream) readers, and defaults to
+    /// 8KiB. Larger buffers reduce the number of reads made to the underlying source.
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the max number of entries which the central directory may declare.
+    ///
+    /// If more entries are declared, [`ZipError::EntryLimitExceeded`](crate::error::ZipError::EntryLimitExceeded) is
+    /// returned before any entries are parsed. This guards against archives which declare an excessive number of
+    /// entries to exhaust memory. By default, there's no limit.
+    pub fn max_entries(mut self, max: u64) -> Self {
+        self.max_entries = Some(max);
+        self
+    }
+
+    /// Sets the number of bytes from the end of the data which are searched for the end of central directory record.
+    ///
+    /// This defaults to the record's length plus the max comment length (just over 64KiB). Archives with trailing data
+    /// after the record (eg. appended signatures or padding) are otherwise read as normal, so a larger window allows
+    /// those with more trailing data to be opened.
+    pub fn eocdr_search_window(mut self, window: u64) -> Self {
+        self.eocdr_search_window = Some(window);
+        self
+    }
+
+    /// Sets whether any trailing data after the end of central directory record (and its comment) is rejected with
+    /// [`ZipError::TrailingData`](crate::error::ZipError::TrailingData), rather than being ignored.
+    ///
+    /// This defaults to `false`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets whether each entry's data offset is derived from its central directory record, rather than by reading and
+    /// parsing its local file header whenever the entry is opened.
+    ///
+    /// This saves a read per entry opened, which adds up when opening thousands of small entries. However, the offsets
+    /// are only correct if each local file header's filename and extra field match those within the central directory
+    /// (as is the case for most writers, though not those which pad the local extra field to align data). As such, this
+    /// should only be enabled for archives from a known source. This defaults to `false`.
+    pub fn trust_central_directory(mut self, trust: bool) -> Self {
+        self.trust_central_directory = trust;
+        self
+    }
+
+    /// Sets the uncompressed size (in bytes) from which an entry's data is decompressed on tokio's blocking thread pool.
+    ///
+    /// This applies to the [`seek`](crate::read::seek) reader, and prevents CPU-heavy decompression of large entries
+    /// (eg. those compressed with bzip2 or xz) from stalling other tasks on the same runtime thread. Other readers'
+    /// entries can be offloaded individually via
+    /// [`ZipEntryReader::offloaded()`](crate::read::seek::ZipEntryReader::offloaded). By default, decompression
+    /// always takes place on the task reading the entry.
+    ///
+    /// Note that this requires the `parallel` feature.
+    #[cfg(feature = "parallel")]
+    pub fn offload_threshold(mut self, bytes: u64) -> Self {
+        self.offload_threshold = Some(bytes);
+        self
+    }
+
+    pub(crate) fn buffer_size_or_default(&self) -> usize {
+        self.buffer_size.unwrap_or(DEFAULT_BUFFER_SIZE).max(1)
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::remote::RangeReader;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// The default size of a cached chunk, equal to 64KiB.
+const DEFAULT_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// The default maximum number of cached chunks (equal to 4MiB with the default chunk size).
+const DEFAULT_CAPACITY: usize = 64;
+
+struct CachedChunk {
+    data: Arc<Vec<u8>>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Cache {
+    chunks: HashMap<u64, CachedChunk>,
+    /// The index of each cached chunk, keyed by when it was last used (so the least recently used comes first).
+    recency: BTreeMap<u64, u64>,
+    clock: u64,
+}
+
+impl Cache {
+    fn contains(&self, index: u64) -> bool {
+        self.chunks.contains_key(&index)
+    }
+
+    fn get(&mut self, index: u64) -> Option<Arc<Vec<u8>>> {
+        let chunk = self.chunks.get_mut(&index)?;
+
+        self.clock += 1;
+        self.recency.remove(&chunk.last_used);
+        self.recency.insert(self.clock, index);
+        chunk.last_used = self.clock;
+
+        Some(chunk.data.clone())
+    }
+
+    fn insert(&mut self, index: u64, data: Arc<Vec<u8>>, capacity: usize) {
+        self.clock += 1;
+        self.recency.insert(self.clock, index);
+
+        if let Some(replaced) = self.chunks.insert(index, CachedChunk { data, last_used: self.clock }) {
+            self.recency.remove(&replaced.last_used);
+        }
+
+        while self.chunks.len() > capacity {
+            let Some((_, index)) = self.recency.pop_first() else { break };
+            self.chunks.remove(&index);
+        }
+    }
+}
+
+/// A [`RangeReader`] which caches fixed-size chunks of an inner [`RangeReader`], evicting the least recently used.
+///
+/// All ranged reads made against the inner reader are aligned to chunk boundaries, with adjacent missing chunks being
+/// fetched together in a single ranged read. This avoids refetching the same byte ranges from remote sources when
+/// repeatedly reading metadata or adjacent entries.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::read::remote::{CachedRangeReader, RangeReader, ZipFileReader};
+/// # use async_zip::error::Result;
+/// #
+/// async fn run<R: RangeReader>(range_reader: R) -> Result<()> {
+///     let cached = CachedRangeReader::new(range_reader).chunk_size(256 * 1024).capacity(32);
+///     let reader = ZipFileReader::new(cached).await?;
+///
+///     // Use reader within current scope.
+///
+///     Ok(())
+/// }
+/// ```
+pub struct CachedRangeReader<R> {
+    inner: R,
+    chunk_size: u64,
+    capacity: usize,
+    cache: Mutex<Cache>,
+    size: OnceLock<u64>,
+}
+
+impl<R> CachedRangeReader<R>
+where
+    R: RangeReader,
+{
+    /// Constructs a new caching reader with a chunk size of 64KiB and a capacity of 64 chunks.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            capacity: DEFAULT_CAPACITY,
+            cache: Mutex::default(),
+            size: OnceLock::new(),
+        }
+    }
+
+    /// Sets the size of each cached chunk in bytes.
+    ///
+    /// This should be set before any reads take place as existing cached chunks are discarded.
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self.cache = Mutex::default();
+        self
+    }
+
+    /// Sets the maximum number of chunks which may be cached at once.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes this reader and returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn cached_chunk(&self, index: u64) -> Option<Arc<Vec<u8>>> {
+        self.cache.lock().expect("cache lock poisoned").get(index)
+    }
+
+    /// Returns whether a chunk is cached, without marking it as used.
+    fn is_cached(&self, index: u64) -> bool {
+        self.cache.lock().expect("cache lock poisoned").contains(index)
+    }
+
+    /// Returns the size of the inner reader's source, only querying it once.
+    async fn cached_size(&self) -> std::io::Result<u64> {
+        if let Some(size) = self.size.get() {
+            return Ok(*size);
+        }
+
+        let size = self.inner.size().await?;
+        Ok(*self.size.get_or_init(|| size))
+    }
+
+    /// Fetches a contiguous run of chunks from the inner reader in a single ranged read and caches them.
+    async fn fetch_chunks(&self, first: u64, last: u64) -> std::io::Result<Vec<Arc<Vec<u8>>>> {
+        let offset = first * self.chunk_size;
+        let length = ((last - first + 1) * self.chunk_size).min(self.cached_size().await?.saturating_sub(offset));
+        let data = self.inner.read_range(offset, length).await?;
+
+        let chunks: Vec<Arc<Vec<u8>>> = data.chunks(self.chunk_size as usize).map(|c| Arc::new(c.to_vec())).collect();
+        let mut cache = self.cache.lock().expect("cache lock poisoned");
+
+        for (index, chunk) in (first..).zip(&chunks) {
+            cache.insert(index, chunk.clone(), self.capacity);
+        }
+
+        Ok(chunks)
+    }
+}
+
+impl<R> RangeReader for CachedRangeReader<R>
+where
+    R: RangeReader,
+{
+    async fn size(&self) -> std::io::Result<u64> {
+        self.cached_size().await
+    }
+
+    async fn read_range(&self, offset: u64, length: u64) -> std::io::Result<Vec<u8>> {
+        // Ranges are clamped to the end of the source, so that nothing is fetched for those which lie past it.
+        let length = length.min(self.cached_size().await?.saturating_sub(offset));
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+
+        let first = offset / self.chunk_size;
+        let last = (offset + length - 1) / self.chunk_size;
+
+        let mut chunks = Vec::with_capacity((last - first + 1) as usize);
+        let mut index = first;
+
+        while index <= last {
+            if let Some(chunk) = self.cached_chunk(index) {
+                chunks.push(chunk);
+                index += 1;
+                continue;
+            }
+
+            let mut missing_last = index;
+            while missing_last < last && !self.is_cached(missing_last + 1) {
+                missing_last += 1;
+            }
+
+            let fetched = self.fetch_chunks(index, missing_last).await?;
+            let end_reached = fetched.len() as u64 != missing_last - index + 1;
+            chunks.extend(fetched);
+
+            if end_reached {
+                break;
+            }
+
+            index = missing_last + 1;
+        }
+
+        let start = (offset - first * self.chunk_size) as usize;
+        let mut data = Vec::with_capacity(length as usize);
+
+        for (position, chunk) in chunks.iter().enumerate() {
+            let chunk = if position == 0 { chunk.get(start..).unwrap_or_default() } else { &chunk[..] };
+            data.extend_from_slice(chunk);
+        }
+
+        data.truncate(length as usize);
+
+        Ok(data)
+    }
+}
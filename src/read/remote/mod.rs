@@ -0,0 +1,152 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A concurrent ZIP reader which acts over a remote source supporting ranged reads (eg. HTTP range requests).
+//!
+//! Only the byte ranges which are actually needed are fetched from the source. When opening a ZIP file, the tail of
+//! the source is fetched (covering the end of central directory record, and often the whole central directory), and
+//! any remaining part of the central directory is then fetched in a single request. When reading an entry, only the
+//! local file header and the entry's compressed data are fetched. This allows listing or extracting individual
+//! entries from very large remote ZIP files without downloading them in full.
+//!
+//! A [`CachedRangeReader`] can be layered over any [`RangeReader`] so that repeated reads of the same byte ranges
+//! (eg. metadata or adjacent entries) are served from memory rather than refetched.
+//!
+//! ### Example
+//! ```
+//! # use async_zip::read::remote::{RangeReader, ZipFileReader};
+//! # use async_zip::error::Result;
+//! # use tokio::io::AsyncReadExt;
+//! #
+//! /// A stand-in for a client which would issue `Range: bytes={offset}-{offset + length - 1}` requests.
+//! struct Client(Vec<u8>);
+//!
+//! impl RangeReader for Client {
+//!     async fn size(&self) -> std::io::Result<u64> {
+//!         Ok(self.0.len() as u64)
+//!     }
+//!
+//!     async fn read_range(&self, offset: u64, length: u64) -> std::io::Result<Vec<u8>> {
+//!         Ok(self.0[offset as usize..(offset + length) as usize].to_vec())
+//!     }
+//! }
+//!
+//! async fn run(client: Client) -> Result<()> {
+//!     let reader = ZipFileReader::new(client).await?;
+//!
+//!     let mut data = Vec::new();
+//!     let mut entry = reader.entry(0).await?;
+//!     entry.read_to_end(&mut data).await?;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//! ```
+
+pub(crate) mod cache;
+pub(crate) mod source;
+
+pub use cache::CachedRangeReader;
+pub use source::RangeSource;
+
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::read::io::entry::ZipEntryReader;
+use crate::read::ReaderOptions;
+
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::io::BufReader;
+
+/// A source of bytes which supports reading arbitrary byte ranges.
+///
+/// This would typically be implemented over an HTTP client (using range requests) or an object storage client.
+pub trait RangeReader: Send + Sync + 'static {
+    /// Returns the total length of the source in bytes.
+    fn size(&self) -> impl Future<Output = std::io::Result<u64>> + Send;
+
+    /// Returns up to `length` bytes from the source, starting at `offset`.
+    ///
+    /// Fewer bytes may only be returned if the end of the source has been reached.
+    fn read_range(&self, offset: u64, length: u64) -> impl Future<Output = std::io::Result<Vec<u8>>> + Send;
+}
+
+struct Inner<R> {
+    range_reader: Arc<R>,
+    size: u64,
+    file: ZipFile,
+}
+
+/// A concurrent ZIP reader which acts over a remote source supporting ranged reads.
+pub struct ZipFileReader<R> {
+    inner: Arc<Inner<R>>,
+}
+
+impl<R> Clone for ZipFileReader<R> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<R> ZipFileReader<R>
+where
+    R: RangeReader,
+{
+    /// Constructs a new ZIP reader from a remote source.
+    pub async fn new(range_reader: R) -> Result<ZipFileReader<R>> {
+        Self::with_options(range_reader, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a remote source and a set of reader options.
+    pub async fn with_options(range_reader: R, options: ReaderOptions) -> Result<ZipFileReader<R>> {
+        let range_reader = Arc::new(range_reader);
+
+        let options = &options;
+
+        crate::read::with_open_timeout(options, async move {
+            let size = range_reader.size().await?;
+            let mut source = RangeSource::new(range_reader.clone(), size);
+            source.prefetch_tail().await?;
+
+            let file = crate::read::file_with_options(source, options).await?;
+            Ok(ZipFileReader { inner: Arc::new(Inner { range_reader, size, file }) })
+        })
+        .await
+    }
+
+    /// Constructs a ZIP reader from a remote source, its total length in bytes, and zip file information derived from
+    /// that source.
+    ///
+    /// This avoids fetching & parsing the central directory again, such as where the zip file information has been
+    /// cached (see the `serde` feature).
+    pub fn from_parts(range_reader: R, size: u64, file: ZipFile) -> ZipFileReader<R> {
+        ZipFileReader { inner: Arc::new(Inner { range_reader: Arc::new(range_reader), size, file }) }
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.inner.file
+    }
+
+    /// Returns a reference to the remote source provided during construction.
+    pub fn range_reader(&self) -> &R {
+        &self.inner.range_reader
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'_, RangeSource<R>>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut source = BufReader::new(RangeSource::new(self.inner.range_reader.clone(), self.inner.size));
+
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut source).await.map_err(context)?;
+
+        Ok(ZipEntryReader::new_with_owned(
+            source,
+            stored_entry.entry.compression(),
+            stored_entry.entry.compressed_size(),
+        ))
+    }
+}
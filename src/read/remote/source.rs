@@ -0,0 +1,136 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::remote::RangeReader;
+use crate::spec::consts::{EOCDR_LENGTH, SIGNATURE_LENGTH};
+
+use std::future::Future;
+use std::io::{Error, ErrorKind, SeekFrom};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+/// The minimum number of bytes fetched by a single ranged read, equal to 64KiB.
+const MIN_FETCH_SIZE: u64 = 64 * 1024;
+
+/// The number of trailing bytes fetched when opening a ZIP file, covering an EOCDR with the maximum length comment.
+const TAIL_FETCH_SIZE: u64 = (SIGNATURE_LENGTH + EOCDR_LENGTH) as u64 + u16::MAX as u64;
+
+type FetchFuture = Pin<Box<dyn Future<Output = std::io::Result<Vec<u8>>> + Send>>;
+
+/// A wrapping reader which implements [`AsyncRead`] and [`AsyncSeek`] over a [`RangeReader`].
+///
+/// Reads are served from the most recently fetched range where possible. Otherwise, a new range is fetched starting at
+/// the current position which is at least as large as the read requested (and at least 64KiB in size).
+pub struct RangeSource<R> {
+    range_reader: Arc<R>,
+    size: u64,
+    position: u64,
+    buffer: Vec<u8>,
+    buffer_offset: u64,
+    fetch: Option<(u64, FetchFuture)>,
+}
+
+impl<R> RangeSource<R>
+where
+    R: RangeReader,
+{
+    /// Constructs a new wrapping reader from a shared [`RangeReader`] and the total size of its source.
+    pub(crate) fn new(range_reader: Arc<R>, size: u64) -> Self {
+        Self { range_reader, size, position: 0, buffer: Vec::new(), buffer_offset: 0, fetch: None }
+    }
+
+    /// Fetches the tail of the source so that the EOCDR can be located without any further requests.
+    pub(crate) async fn prefetch_tail(&mut self) -> std::io::Result<()> {
+        let offset = self.size.saturating_sub(TAIL_FETCH_SIZE);
+        self.buffer = self.range_reader.read_range(offset, self.size - offset).await?;
+        self.buffer_offset = offset;
+
+        Ok(())
+    }
+
+    /// Returns the total size of the source in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the fetched bytes available from the current position onwards, if any.
+    fn buffered(&self) -> Option<&[u8]> {
+        let start = self.position.checked_sub(self.buffer_offset)?;
+        self.buffer.get(usize::try_from(start).ok()?..).filter(|available| !available.is_empty())
+    }
+}
+
+impl<R> AsyncRead for RangeSource<R>
+where
+    R: RangeReader,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.position >= this.size || b.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+
+            if let Some(available) = this.buffered() {
+                let length = std::cmp::min(available.len(), b.remaining());
+                b.put_slice(&available[..length]);
+                this.position += length as u64;
+
+                return Poll::Ready(Ok(()));
+            }
+
+            let (offset, fetch) = match this.fetch {
+                Some(ref mut fetch) => fetch,
+                None => {
+                    let offset = this.position;
+                    let length = std::cmp::max(b.remaining() as u64, MIN_FETCH_SIZE).min(this.size - offset);
+                    let range_reader = this.range_reader.clone();
+                    let fetch: FetchFuture = Box::pin(async move { range_reader.read_range(offset, length).await });
+
+                    this.fetch.insert((offset, fetch))
+                }
+            };
+
+            let offset = *offset;
+            let result = ready!(fetch.as_mut().poll(c));
+            this.fetch = None;
+
+            match result {
+                Ok(data) if data.is_empty() => return Poll::Ready(Err(Error::from(ErrorKind::UnexpectedEof))),
+                Ok(data) => {
+                    this.buffer = data;
+                    this.buffer_offset = offset;
+                }
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+    }
+}
+
+impl<R> AsyncSeek for RangeSource<R>
+where
+    R: RangeReader,
+{
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        let this = self.get_mut();
+
+        let position = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => this.size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+        };
+
+        this.position = position.ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid seek position"))?;
+        this.fetch = None;
+
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        Poll::Ready(Ok(self.position))
+    }
+}
@@ -25,16 +25,27 @@
 //! ```
 
 use crate::error::{Result, ZipError};
-use crate::file::ZipFile;
+use crate::file::{diff::ArchiveDiff, ZipFile};
 pub use crate::read::io::entry::ZipEntryReader;
+use crate::read::nested::{EntryRange, NestedZipReader};
+use crate::read::{stream, ReaderOptions, VerificationFailure, VerificationReport};
+use crate::spec::compression::Compression;
 
-use tokio::io::{AsyncRead, AsyncSeek, BufReader};
+use std::collections::HashMap;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader};
+
+/// The size of the chunks compared when diffing the content of two entries, equal to 64KiB.
+const DIFF_CHUNK_SIZE: usize = 64 * 1024;
 
 /// A ZIP reader which acts over a seekable source.
 #[derive(Clone)]
 pub struct ZipFileReader<R> {
     reader: R,
     file: ZipFile,
+    buffer_size: usize,
+    #[cfg(feature = "parallel")]
+    offload_threshold: Option<u64>,
 }
 
 impl<R> ZipFileReader<R>
@@ -42,9 +53,21 @@ where
     R: AsyncRead + AsyncSeek + Unpin,
 {
     /// Constructs a new ZIP reader from a seekable source.
-    pub async fn new(mut reader: R) -> Result<ZipFileReader<R>> {
-        let file = crate::read::file(&mut reader).await?;
-        Ok(ZipFileReader { reader, file })
+    pub async fn new(reader: R) -> Result<ZipFileReader<R>> {
+        Self::with_options(reader, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a seekable source and a set of reader options.
+    pub async fn with_options(mut reader: R, options: ReaderOptions) -> Result<ZipFileReader<R>> {
+        let file =
+            crate::read::with_open_timeout(&options, crate::read::file_with_options(&mut reader, &options)).await?;
+        Ok(ZipFileReader {
+            reader,
+            file,
+            buffer_size: options.buffer_size_or_default(),
+            #[cfg(feature = "parallel")]
+            offload_threshold: options.offload_threshold,
+        })
     }
 
     /// Constructs a ZIP reader from a seekable source and zip file information
@@ -70,7 +93,13 @@ where
     /// ```
     ///
     pub fn from_parts(reader: R, file: ZipFile) -> ZipFileReader<R> {
-        ZipFileReader { reader, file }
+        ZipFileReader {
+            reader,
+            file,
+            buffer_size: ReaderOptions::default().buffer_size_or_default(),
+            #[cfg(feature = "parallel")]
+            offload_threshold: None,
+        }
     }
 
     /// Returns this ZIP file's information.
@@ -88,19 +117,173 @@ where
         self.reader
     }
 
+    /// Returns the capacity of the buffer used when reading an entry's data.
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    /// Sets the capacity of the buffer used when reading an entry's data, for all entries opened after this call.
+    ///
+    /// This overrides the size set via [`ReaderOptions::buffer_size()`] (or the 8KiB default for readers constructed
+    /// via [`ZipFileReader::from_parts()`]).
+    pub fn set_buffer_size(&mut self, capacity: usize) {
+        self.buffer_size = capacity.max(1);
+    }
+
     /// Returns a new entry reader if the provided index is valid.
     pub async fn entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R>> {
+        self.entry_with_capacity(index, self.buffer_size).await
+    }
+
+    /// Returns a new entry reader if the provided index is valid, reading its data through a buffer of the provided
+    /// capacity.
+    ///
+    /// The ideal capacity depends on the source; small buffers suit low-latency local files, whereas large buffers
+    /// reduce the number of round trips made to high-latency sources (eg. network filesystems).
+    pub async fn entry_with_capacity(&mut self, index: usize, capacity: usize) -> Result<ZipEntryReader<'_, R>> {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
 
-        let mut reader = BufReader::new(&mut self.reader);
+        #[cfg(feature = "parallel")]
+        let offload =
+            self.offload_threshold.is_some_and(|threshold| stored_entry.entry.uncompressed_size() >= threshold);
+        let mut reader = BufReader::with_capacity(capacity.max(1), &mut self.reader);
 
-        stored_entry.seek_to_data_offset(&mut reader).await?;
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut reader).await.map_err(context)?;
 
-        Ok(ZipEntryReader::new_with_borrow(
+        let entry_reader = ZipEntryReader::new_with_borrow(
             reader,
             stored_entry.entry.compression(),
-            stored_entry.entry.uncompressed_size().into(),
-        ))
+            stored_entry.entry.compressed_size(),
+        );
+
+        #[cfg(feature = "parallel")]
+        if offload {
+            return Ok(entry_reader.offloaded());
+        }
+
+        Ok(entry_reader)
+    }
+
+    /// Returns a reader over a ZIP file held within the entry at the provided index (eg. a JAR within a ZIP).
+    ///
+    /// If the entry is stored without compression, the inner ZIP file is read via seeks within this reader's source,
+    /// so its entries can be accessed in any order without buffering. Otherwise, the entry is decompressed as it's read
+    /// and the inner ZIP file can only be read sequentially via the [`stream`](crate::read::stream) reader.
+    pub async fn nested_entry(&mut self, index: usize) -> Result<NestedZipReader<'_, R>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if stored_entry.entry.compression() != Compression::Stored {
+            return Ok(NestedZipReader::Stream(Box::new(stream::ZipFileReader::new(self.entry(index).await?))));
+        }
+
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut self.reader).await.map_err(context)?;
+
+        let start = self.reader.stream_position().await?;
+        let range = EntryRange::new(&mut self.reader, start, stored_entry.entry.compressed_size());
+        let options = ReaderOptions::new().buffer_size(self.buffer_size);
+
+        Ok(NestedZipReader::Seek(ZipFileReader::with_options(range, options).await?))
+    }
+
+    /// Returns a reader over this ZIP file's entries in the order in which their data is stored within the file.
+    ///
+    /// The central directory doesn't need to list entries in the same order as their data, so reading entries by index
+    /// may require seeking backwards. Reading entries via the returned reader only ever seeks forwards, allowing all
+    /// entries to be read in a single pass over slow-seeking sources (eg. spinning disks or network filesystems).
+    pub fn entries_by_offset(&mut self) -> OffsetOrderedEntries<'_, R> {
+        let indices = self.offset_order();
+        OffsetOrderedEntries { reader: self, indices: indices.into_iter() }
+    }
+
+    /// Returns the indices of this ZIP file's entries in the order in which their data is stored within the file.
+    fn offset_order(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.file.entries.len()).collect();
+        indices.sort_by_key(|index| self.file.entries[*index].file_offset);
+        indices
+    }
+
+    /// Reads the data of each entry at the provided indices, verifying their CRC32 values.
+    ///
+    /// The entries are read in the order in which their data is stored within the file so that only forward seeks
+    /// are required, but the returned data is in the same order as the provided indices (duplicate indices are only
+    /// read once). [`ZipError::EntryIndexOutOfBounds`] is returned before any reads take place if an index is invalid.
+    pub async fn entries(&mut self, indices: &[usize]) -> Result<Vec<Vec<u8>>> {
+        if indices.iter().any(|index| *index >= self.file.entries.len()) {
+            return Err(ZipError::EntryIndexOutOfBounds);
+        }
+
+        let mut ordered = indices.to_vec();
+        ordered.sort_by_key(|index| (self.file.entries[*index].file_offset, *index));
+        ordered.dedup();
+
+        let mut data = HashMap::with_capacity(ordered.len());
+
+        for index in ordered {
+            let entry = self.file.entries[index].entry.clone();
+            let mut buffer = Vec::new();
+            let context = |err: ZipError| err.in_entry(Some(index), entry.filename());
+            self.entry(index).await?.read_to_end_checked(&mut buffer, &entry).await.map_err(context)?;
+            data.insert(index, buffer);
+        }
+
+        // Only clone the data of entries which are requested again later on.
+        let results =
+            indices.iter().enumerate().map(|(position, index)| match indices[position + 1..].contains(index) {
+                true => data[index].clone(),
+                false => data.remove(index).expect("entry was read"),
+            });
+
+        Ok(results.collect())
+    }
+
+    /// Verifies the integrity of every entry, reporting any which fail rather than stopping at the first.
+    ///
+    /// Each entry's data is decompressed and discarded with its CRC32 value being checked, in the order in which the
+    /// data is stored within the file (as with [`ZipFileReader::entries_by_offset()`]). This is the equivalent of
+    /// `unzip -t`. Note that encrypted entries can't currently be read, and so are reported as failures.
+    pub async fn verify(&mut self) -> VerificationReport {
+        let mut report = VerificationReport { verified: 0, failures: Vec::new() };
+
+        for index in self.offset_order() {
+            let entry = self.file.entries[index].entry.clone();
+            let result = match self.entry(index).await {
+                Ok(mut reader) => reader.copy_to_end_checked(&mut tokio::io::sink(), &entry).await.map(|_| ()),
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(()) => report.verified += 1,
+                Err(error) => report.failures.push(VerificationFailure { index, filename: entry.filename, error }),
+            }
+        }
+
+        report
+    }
+
+    /// Compares this ZIP file's entries against another's, including their content.
+    ///
+    /// This extends [`ZipFile::diff()`] by reading the data of every pair of entries whose metadata matches, and
+    /// reporting them as changed if their content differs. Entries whose metadata differs aren't read.
+    pub async fn diff<O>(&mut self, other: &mut ZipFileReader<O>) -> Result<ArchiveDiff>
+    where
+        O: AsyncRead + AsyncSeek + Unpin,
+    {
+        let mut diff = ArchiveDiff::default();
+
+        for (index, other_index) in crate::file::diff::matched(&self.file, &other.file, &mut diff) {
+            let (entry, other_entry) = (self.file.entries[index].entry.clone(), &other.file.entries[other_index].entry);
+
+            let same = crate::file::diff::same_metadata(&entry, other_entry)
+                && same_content(self.entry(index).await?, other.entry(other_index).await?).await?;
+
+            if !same {
+                diff.changed.push(entry.filename);
+            }
+        }
+
+        Ok(diff)
     }
 
     /// Returns a new entry reader if the provided index is valid.
@@ -111,14 +294,60 @@ where
     {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
 
-        let mut reader = BufReader::new(self.reader);
+        let mut reader = BufReader::with_capacity(self.buffer_size, self.reader);
 
-        stored_entry.seek_to_data_offset(&mut reader).await?;
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut reader).await.map_err(context)?;
 
         Ok(ZipEntryReader::new_with_owned(
             reader,
             stored_entry.entry.compression(),
-            stored_entry.entry.uncompressed_size().into(),
+            stored_entry.entry.compressed_size(),
         ))
     }
 }
+
+/// Returns whether two entry readers yield the same data, reading both in chunks.
+async fn same_content<A, B>(mut this: ZipEntryReader<'_, A>, mut other: ZipEntryReader<'_, B>) -> Result<bool>
+where
+    A: AsyncRead + Unpin,
+    B: AsyncRead + Unpin,
+{
+    let (mut this_buffer, mut other_buffer) =
+        (Vec::with_capacity(DIFF_CHUNK_SIZE), Vec::with_capacity(DIFF_CHUNK_SIZE));
+
+    loop {
+        this_buffer.clear();
+        other_buffer.clear();
+
+        let read = (&mut this).take(DIFF_CHUNK_SIZE as u64).read_to_end(&mut this_buffer).await?;
+        (&mut other).take(DIFF_CHUNK_SIZE as u64).read_to_end(&mut other_buffer).await?;
+
+        if this_buffer != other_buffer {
+            return Ok(false);
+        } else if read == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// A reader over a ZIP file's entries in the order in which their data is stored within the file.
+///
+/// This is constructed via [`ZipFileReader::entries_by_offset()`].
+pub struct OffsetOrderedEntries<'a, R> {
+    reader: &'a mut ZipFileReader<R>,
+    indices: std::vec::IntoIter<usize>,
+}
+
+impl<'a, R> OffsetOrderedEntries<'a, R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Returns the index of the next entry along with a reader over its data, or `None` once all entries have been read.
+    pub async fn next_entry(&mut self) -> Result<Option<(usize, ZipEntryReader<'_, R>)>> {
+        match self.indices.next() {
+            Some(index) => Ok(Some((index, self.reader.entry(index).await?))),
+            None => Ok(None),
+        }
+    }
+}
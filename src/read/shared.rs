@@ -0,0 +1,126 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which acts over a seekable source shared behind an async mutex.
+//!
+//! Unlike the [`seek`] module, opening an entry only requires a shared reference to the reader. Access to the inner
+//! source is serialised internally, with each [`ZipEntryReader`] holding the lock until it's dropped. The reader is
+//! also cheap to clone, so it can be stored in shared state (eg. an [`Arc`] within a web server's handler state)
+//! without any further wrapping.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::shared::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use tokio::io::AsyncReadExt;
+//! # use tokio::fs::File;
+//! #
+//! async fn run() -> Result<()> {
+//!     let reader = ZipFileReader::new(File::open("./foo.zip").await?).await?;
+//!
+//!     let mut data = Vec::new();
+//!     let mut entry = reader.entry(0).await?;
+//!     entry.read_to_end(&mut data).await?;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//! ```
+
+#[cfg(doc)]
+use crate::read::seek;
+
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::read::io::entry::ZipEntryReader;
+use crate::read::ReaderOptions;
+
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, BufReader, ReadBuf};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// A ZIP reader which acts over a seekable source shared behind an async mutex.
+pub struct ZipFileReader<R> {
+    reader: Arc<Mutex<R>>,
+    file: Arc<ZipFile>,
+}
+
+impl<R> Clone for ZipFileReader<R> {
+    fn clone(&self) -> Self {
+        Self { reader: self.reader.clone(), file: self.file.clone() }
+    }
+}
+
+impl<R> ZipFileReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new ZIP reader from a seekable source.
+    pub async fn new(reader: R) -> Result<ZipFileReader<R>> {
+        Self::with_options(reader, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a seekable source and a set of reader options.
+    pub async fn with_options(mut reader: R, options: ReaderOptions) -> Result<ZipFileReader<R>> {
+        let file =
+            crate::read::with_open_timeout(&options, crate::read::file_with_options(&mut reader, &options)).await?;
+        Ok(Self::from_parts(reader, file))
+    }
+
+    /// Constructs a ZIP reader from a seekable source and zip file information derived from that source.
+    pub fn from_parts(reader: R, file: ZipFile) -> ZipFileReader<R> {
+        ZipFileReader { reader: Arc::new(Mutex::new(reader)), file: Arc::new(file) }
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    ///
+    /// This waits until any previously-returned entry readers (from this reader or its clones) have been dropped.
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'_, LockedReader<R>>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut reader = BufReader::new(LockedReader(self.reader.clone().lock_owned().await));
+
+        let context = |err: ZipError| err.in_entry(Some(index), stored_entry.entry.filename());
+        stored_entry.seek_to_readable_data(&mut reader).await.map_err(context)?;
+
+        Ok(ZipEntryReader::new_with_owned(
+            reader,
+            stored_entry.entry.compression(),
+            stored_entry.entry.compressed_size(),
+        ))
+    }
+}
+
+/// A locked handle to the source of a shared [`ZipFileReader`], which is released once dropped.
+pub struct LockedReader<R>(OwnedMutexGuard<R>);
+
+impl<R> AsyncRead for LockedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        Pin::new(&mut *self.get_mut().0).poll_read(c, b)
+    }
+}
+
+impl<R> AsyncSeek for LockedReader<R>
+where
+    R: AsyncSeek + Unpin,
+{
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        Pin::new(&mut *self.get_mut().0).start_seek(position)
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        Pin::new(&mut *self.get_mut().0).poll_complete(c)
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::read::stream::{Reading, Ready, ZipFileReader};
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::oneshot::{self, Receiver, Sender};
+
+enum State<R>
+where
+    R: AsyncRead + Unpin + 'static,
+{
+    // Boxed as the reader holds onto any peeked entry.
+    Ready(Box<ZipFileReader<Ready<R>>>),
+    Reading(Receiver<ZipFileReader<Reading<'static, R>>>),
+    Done,
+}
+
+/// A reader over a single entry's data, as yielded by [`ZipFileReader::into_stream()`].
+///
+/// Once dropped, the underlying source is handed back to the stream which skips any remaining data of this entry before
+/// yielding the next one.
+pub struct StreamEntryReader<R>
+where
+    R: AsyncRead + Unpin + 'static,
+{
+    reading: Option<ZipFileReader<Reading<'static, R>>>,
+    sender: Option<Sender<ZipFileReader<Reading<'static, R>>>>,
+}
+
+impl<R> AsyncRead for StreamEntryReader<R>
+where
+    R: AsyncRead + Unpin + 'static,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let reading = self.get_mut().reading.as_mut().expect("entry reader taken before drop");
+        Pin::new(reading.reader()).poll_read(c, b)
+    }
+}
+
+impl<R> Drop for StreamEntryReader<R>
+where
+    R: AsyncRead + Unpin + 'static,
+{
+    fn drop(&mut self) {
+        if let (Some(reading), Some(sender)) = (self.reading.take(), self.sender.take()) {
+            // If the stream itself has already been dropped, there's nothing left to hand the source back to.
+            let _ = sender.send(reading);
+        }
+    }
+}
+
+impl<R> ZipFileReader<Ready<R>>
+where
+    R: AsyncRead + Unpin + 'static,
+{
+    /// Converts this reader into a [`Stream`] of entries along with readers over their data.
+    ///
+    /// The sequential invariant of this reader is enforced internally: the next entry isn't yielded until the reader of
+    /// the previous entry has been dropped, at which point any of its remaining data is skipped. As such, polling the
+    /// stream whilst holding onto the previous entry's reader will never complete.
+    ///
+    /// Note that this requires the `futures` feature.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use async_zip::error::Result;
+    /// # use async_zip::read::stream::ZipFileReader;
+    /// # use futures_util::{pin_mut, StreamExt};
+    /// # use tokio::io::AsyncReadExt;
+    /// #
+    /// # async fn run() -> Result<()> {
+    /// let stream = ZipFileReader::new(tokio::io::stdin()).into_stream();
+    /// pin_mut!(stream);
+    ///
+    /// while let Some(result) = stream.next().await {
+    ///     let (entry, mut reader) = result?;
+    ///     let mut data = Vec::new();
+    ///     reader.read_to_end(&mut data).await?;
+    /// }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_stream(self) -> impl Stream<Item = Result<(ZipEntry, StreamEntryReader<R>)>> {
+        futures_util::stream::unfold(State::Ready(Box::new(self)), |state| async move {
+            let zip = match state {
+                State::Ready(zip) => *zip,
+                State::Reading(receiver) => match receiver.await {
+                    Ok(reading) => match reading.skip().await {
+                        Ok(zip) => zip,
+                        Err(err) => return Some((Err(err), State::Done)),
+                    },
+                    // Only reachable if the entry reader was leaked, as it always sends the source back when dropped.
+                    Err(_) => return None,
+                },
+                State::Done => return None,
+            };
+
+            match zip.next_entry().await {
+                Ok(Some(reading)) => {
+                    let (sender, receiver) = oneshot::channel();
+                    let entry = reading.entry().clone();
+                    let reader = StreamEntryReader { reading: Some(reading), sender: Some(sender) };
+
+                    Some((Ok((entry, reader)), State::Reading(receiver)))
+                }
+                Ok(None) => None,
+                Err(err) => Some((Err(err), State::Done)),
+            }
+        })
+    }
+}
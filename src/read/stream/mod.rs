@@ -0,0 +1,510 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which acts over a non-seekable source.
+//!
+//! # API Design
+//! As opposed to other readers provided by this crate, it's important that the data of an entry is fully read before
+//! the proceeding entry is read. This is as a result of not being able to seek forwards or backwards, so we must end
+//! up at the start of the next entry.
+//!
+//! **We encode this invariant within Rust's type system so that it can be enforced at compile time.**
+//!
+//! This requires that any transition methods between these encoded types consume the reader and provide a new owned
+//! reader back. This is certainly something to keep in mind when working with this reader, but idiomatic code can
+//! still be produced nevertheless.
+//!
+//! # Considerations
+//! As the central directory of a ZIP archive is stored at the end of it, a non-seekable reader doesn't have access
+//! to it. We have to rely on information provided within the local file header which may not be accurate or complete.
+//! This results in:
+//! - No file comment being avaliable (defaults to an empty string).
+//! - No internal or external file attributes being avaliable (defaults to 0).
+//! - The extra field data potentially being inconsistent with what's stored in the central directory.
+//! - None of the following being avaliable when the entry was written with a data descriptor (defaults to 0):
+//!     - CRC
+//!     - compressed size
+//!     - uncompressed size
+//!
+//! Such entries are instead read until their decompressor reaches EOF (so they must be compressed), and the trailing
+//! data descriptor is verified by [`ZipFileReader::done()`].
+//!
+//! # Example
+//! ```no_run
+//! # use std::io::Cursor;
+//! # use async_zip::error::Result;
+//! # use async_zip::read::stream::ZipFileReader;
+//! #
+//! # async fn run() -> Result<()> {
+//! let mut zip = ZipFileReader::new(Cursor::new([0; 0]));
+//!     
+//! // Print the name of every file in a ZIP archive.
+//! while let Some(entry) = zip.next_entry().await? {
+//!     println!("File: {}", entry.entry().filename());
+//!     zip = entry.skip().await?;
+//! }
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "futures")]
+pub(crate) mod adapter;
+
+#[cfg(feature = "futures")]
+pub use adapter::StreamEntryReader;
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::error::ZipError;
+use crate::read::io::entry::ZipEntryReader;
+use crate::read::ReaderOptions;
+use crate::spec::compression::Compression;
+use crate::spec::consts::{CDH_LENGTH, CDH_SIGNATURE, EOCDR_LENGTH, EOCDR_SIGNATURE};
+use crate::spec::consts::{LFH_LENGTH, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH};
+use crate::spec::consts::{ZIP64_EOCDL_LENGTH, ZIP64_EOCDL_SIGNATURE, ZIP64_EOCDR_SIGNATURE};
+use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader, GeneralPurposeFlag};
+
+use std::io::{ErrorKind, SeekFrom};
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt};
+use tokio::io::{AsyncRead, AsyncSeek, BufReader};
+
+/// The buffer size used when reading through an entry's data to discard it, equal to 64KiB.
+const SKIP_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The behaviour when a streamed entry is dropped before all of its compressed data has been consumed.
+///
+/// Dropping a partially-read entry leaves the underlying source positioned within that entry's data, so the reader
+/// can't be used to read any proceeding entries. To avoid this, [`ZipFileReader::close()`] should be called instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnconsumedEntryBehavior {
+    /// Silently allow the entry to be dropped.
+    Allow,
+    /// Log a warning via the [`log`] crate's facade.
+    #[default]
+    Log,
+    /// Panic, unless the current thread is already panicking (in which case we fall back to logging).
+    Panic,
+}
+
+pub struct Ready<R> {
+    reader: BufReader<R>,
+    offset: u64,
+    options: ReaderOptions,
+    // The next record within the archive, if it has already been peeked.
+    peeked: Option<Record>,
+}
+
+/// A record which may follow the end of an entry's data (or the start of an archive).
+enum Record {
+    LocalFileHeader(ZipEntry, GeneralPurposeFlag),
+    CentralDirectory,
+    Zip64EndOfCentralDirectory,
+    EndOfCentralDirectory,
+}
+
+pub struct Reading<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    // Only ever taken by the transition methods back into the Ready state.
+    reader: Option<ZipEntryReader<'a, R>>,
+    entry: ZipEntry,
+    // Whether a data descriptor follows the entry's data, whether its compressed size is known upfront, and whether it
+    // holds ZIP64 values (which widens the sizes within its data descriptor).
+    data_descriptor: bool,
+    known_size: bool,
+    zip64: bool,
+    data_offset: u64,
+    options: ReaderOptions,
+}
+
+impl<'a, R> Drop for Reading<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn drop(&mut self) {
+        // An entry of a known size may be fully consumed without a read having returned EOF.
+        match &self.reader {
+            Some(reader) if reader.reached_eof() => return,
+            Some(reader) if self.known_size && reader.compressed_bytes_remaining() == 0 => return,
+            Some(_) => (),
+            None => return,
+        };
+
+        let filename = self.entry.filename();
+
+        match self.options.unconsumed_entry_behavior {
+            UnconsumedEntryBehavior::Allow => (),
+            UnconsumedEntryBehavior::Panic if !std::thread::panicking() => {
+                panic!("streamed entry '{filename}' was dropped before all of its data was consumed")
+            }
+            UnconsumedEntryBehavior::Log | UnconsumedEntryBehavior::Panic => {
+                log::warn!("streamed entry '{filename}' was dropped before all of its data was consumed")
+            }
+        }
+    }
+}
+
+/// A ZIP reader which acts over a non-seekable source.
+///
+/// See the [module-level docs](.) for more information.
+#[derive(Clone)]
+pub struct ZipFileReader<S>(S);
+
+impl<'a, R> ZipFileReader<Ready<R>>
+where
+    R: AsyncRead + Unpin + 'a,
+{
+    /// Constructs a new ZIP reader from a non-seekable source.
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ReaderOptions::default())
+    }
+
+    /// Constructs a new ZIP reader from a non-seekable source and a set of reader options.
+    pub fn with_options(reader: R, options: ReaderOptions) -> Self {
+        Self(Ready {
+            reader: BufReader::with_capacity(options.buffer_size_or_default(), reader),
+            offset: 0,
+            options,
+            peeked: None,
+        })
+    }
+
+    /// Returns the current byte offset within the archive (ie. the number of bytes consumed from the source so far).
+    ///
+    /// A local file header which has only been peeked isn't counted, so this remains the offset of that header.
+    pub fn offset(&self) -> u64 {
+        self.0.offset
+    }
+
+    /// Parses the next local file header and returns its entry's data without transitioning into the Reading state.
+    ///
+    /// Subsequent calls (and the proceeding call to [`ZipFileReader::next_entry()`]) reuse the parsed header, so this
+    /// allows deciding whether to read or skip an entry before any decompressor is constructed. `None` is returned if
+    /// the central directory has been reached.
+    pub async fn peek_entry(&mut self) -> Result<Option<&ZipEntry>> {
+        match self.peek_record().await? {
+            Record::LocalFileHeader(entry, _) => Ok(Some(entry)),
+            Record::CentralDirectory | Record::Zip64EndOfCentralDirectory | Record::EndOfCentralDirectory => Ok(None),
+        }
+    }
+
+    /// Opens the next entry for reading if the central directory hasn’t yet been reached.
+    pub async fn next_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, R>>>> {
+        self.peek_record().await?;
+
+        let (entry, flags) = match self.0.peeked.take() {
+            Some(Record::LocalFileHeader(entry, flags)) => (entry, flags),
+            _ => return Ok(None),
+        };
+        flags.check_supported()?;
+
+        // Entries written with a data descriptor may hold zeroed sizes within their local file header, so we instead
+        // rely on the decompressor to find the end of the data (which isn't possible for stored entries).
+        let known_size = !flags.data_descriptor || entry.compressed_size != 0;
+        let size = if known_size {
+            entry.compressed_size
+        } else if entry.compression == Compression::Stored {
+            return Err(ZipError::FeatureNotSupported("stored entries of an unknown size within the stream reader"));
+        } else {
+            u64::MAX
+        };
+
+        let zip64 = crate::spec::extra_field::has_zip64_extra_field(&entry.extra_field);
+        let header_length = (SIGNATURE_LENGTH + LFH_LENGTH + entry.filename.len() + entry.extra_field.len()) as u64;
+        let Ready { reader, offset, options, .. } = self.0;
+        let reader = ZipEntryReader::new_with_owned(reader, entry.compression, size);
+
+        Ok(Some(ZipFileReader(Reading {
+            reader: Some(reader),
+            entry,
+            data_descriptor: flags.data_descriptor,
+            zip64,
+            known_size,
+            data_offset: offset + header_length,
+            options,
+        })))
+    }
+
+    /// Skips the remainder of the current archive and moves onto the next archive concatenated directly after it.
+    ///
+    /// Any remaining entries are skipped, followed by the current archive's central directory and end of central
+    /// directory record. `None` is returned if the source reaches EOF directly after the current archive. Note that
+    /// [`ZipFileReader::offset()`] continues to be relative to the start of the source rather than the next archive.
+    pub async fn next_archive(mut self) -> Result<Option<Self>> {
+        while let Record::LocalFileHeader(..) = self.peek_record().await? {
+            let entry = self.next_entry().await?.expect("local file header was peeked");
+            self = entry.skip().await?;
+        }
+
+        let mut record = self.0.peeked.take().expect("record was peeked");
+        self.0.offset += SIGNATURE_LENGTH as u64;
+
+        while let Record::CentralDirectory = record {
+            let header = CentralDirectoryRecord::from_reader(&mut self.0.reader).await?;
+            self.0.offset += CDH_LENGTH as u64;
+
+            let trailing_length = u64::from(header.file_name_length)
+                + u64::from(header.extra_field_length)
+                + u64::from(header.file_comment_length);
+            self.discard(trailing_length).await?;
+
+            record = match self.0.reader.read_u32_le().await? {
+                CDH_SIGNATURE => Record::CentralDirectory,
+                ZIP64_EOCDR_SIGNATURE => Record::Zip64EndOfCentralDirectory,
+                EOCDR_SIGNATURE => Record::EndOfCentralDirectory,
+                actual => return Err(ZipError::UnexpectedHeaderError(actual, EOCDR_SIGNATURE)),
+            };
+            self.0.offset += SIGNATURE_LENGTH as u64;
+        }
+
+        // A ZIP64 EOCDR and its locator precede the EOCDR, both of which are skipped over by their lengths.
+        if let Record::Zip64EndOfCentralDirectory = record {
+            let length = self.0.reader.read_u64_le().await?;
+            self.0.offset += 8;
+            self.discard(length).await?;
+
+            crate::utils::assert_signature(&mut self.0.reader, ZIP64_EOCDL_SIGNATURE).await?;
+            self.0.offset += SIGNATURE_LENGTH as u64;
+            self.discard(ZIP64_EOCDL_LENGTH as u64).await?;
+
+            crate::utils::assert_signature(&mut self.0.reader, EOCDR_SIGNATURE).await?;
+            self.0.offset += SIGNATURE_LENGTH as u64;
+        }
+
+        let header = EndOfCentralDirectoryHeader::from_reader(&mut self.0.reader).await?;
+        self.0.offset += EOCDR_LENGTH as u64;
+        self.discard(header.file_comm_length.into()).await?;
+
+        if self.0.reader.fill_buf().await?.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+
+    /// Parses the next record from the source if it hasn't already been peeked, and returns a reference to it.
+    async fn peek_record(&mut self) -> Result<&Record> {
+        let record = match self.0.peeked.take() {
+            Some(record) => record,
+            None => {
+                let offset = self.0.offset;
+                let record = async {
+                    match self.0.reader.read_u32_le().await? {
+                        LFH_SIGNATURE => {
+                            let (entry, flags) = crate::read::lfh(&mut self.0.reader).await?;
+                            Ok(Record::LocalFileHeader(entry, flags))
+                        }
+                        CDH_SIGNATURE => Ok(Record::CentralDirectory),
+                        ZIP64_EOCDR_SIGNATURE => Ok(Record::Zip64EndOfCentralDirectory),
+                        EOCDR_SIGNATURE => Ok(Record::EndOfCentralDirectory),
+                        actual => Err(ZipError::UnexpectedHeaderError(actual, LFH_SIGNATURE)),
+                    }
+                };
+
+                record.await.map_err(|err| err.at_offset(offset))?
+            }
+        };
+
+        Ok(self.0.peeked.insert(record))
+    }
+
+    /// Discards a number of bytes from the source.
+    async fn discard(&mut self, length: u64) -> Result<()> {
+        let discarded = tokio::io::copy_buf(&mut (&mut self.0.reader).take(length), &mut tokio::io::sink()).await?;
+
+        if discarded != length {
+            return Err(ZipError::UpstreamReadError(ErrorKind::UnexpectedEof.into()));
+        }
+
+        self.0.offset += length;
+        Ok(())
+    }
+
+    /// Reads the data descriptor which follows an entry's data, verifying its CRC32 value against the provided hash.
+    async fn read_data_descriptor(&mut self, hash: Option<u32>, zip64: bool) -> Result<()> {
+        let offset = self.0.offset;
+        let context = |err: ZipError| err.at_offset(offset);
+
+        let (descriptor, length) = crate::read::data_descriptor(&mut self.0.reader, zip64).await.map_err(context)?;
+        self.0.offset += length;
+
+        match hash {
+            Some(hash) if hash != descriptor.crc => Err(context(ZipError::CRC32CheckError)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a, R> ZipFileReader<Reading<'a, R>>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Returns the current entry's data.
+    pub fn entry(&self) -> &ZipEntry {
+        &self.0.entry
+    }
+
+    /// Returns a mutable reference to the inner entry reader.
+    pub fn reader(&mut self) -> &mut ZipEntryReader<'a, R> {
+        self.0.reader.as_mut().expect("entry reader taken before transition")
+    }
+
+    /// Returns the number of compressed bytes of the current entry which have been consumed from the source so far.
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.0.reader.as_ref().expect("entry reader taken before transition").compressed_bytes_read()
+    }
+
+    /// Returns the current byte offset within the archive (ie. the number of bytes consumed from the source so far).
+    pub fn offset(&self) -> u64 {
+        self.0.data_offset + self.compressed_bytes_read()
+    }
+
+    /// Converts the reader back into the Ready state if EOF has been reached.
+    ///
+    /// If the entry was written with a data descriptor, it's read and its CRC32 value is verified against the data read.
+    pub async fn done(mut self) -> Result<ZipFileReader<Ready<R>>> {
+        match self.reader().read(&mut [0; 1]).await {
+            Ok(0) => (),
+            result => {
+                // The error is returned to the caller, so there's no need for the drop check to also report it.
+                self.0.reader = None;
+                result?;
+                return Err(ZipError::EntryNotFullyRead.in_entry(None, &self.0.entry.filename));
+            }
+        }
+
+        let hash = self.reader().compute_hash();
+        let (data_descriptor, zip64) = (self.0.data_descriptor, self.zip64_data_descriptor());
+        let filename = data_descriptor.then(|| self.0.entry.filename.clone());
+        let mut zip = self.into_ready();
+
+        if let Some(filename) = filename {
+            zip.read_data_descriptor(Some(hash), zip64).await.map_err(|err| err.in_entry(None, &filename))?;
+        }
+
+        Ok(zip)
+    }
+
+    /// Skips the remainder of the current entry and converts the reader back into the Ready state.
+    ///
+    /// Where the compressed size of the entry is known, its remaining compressed data is discarded directly from the
+    /// source without any decompression taking place. Otherwise, the entry is read until EOF.
+    pub async fn skip(mut self) -> Result<ZipFileReader<Ready<R>>> {
+        if !self.0.known_size {
+            self.read_to_eof().await?;
+        }
+
+        let (data_descriptor, zip64) = (self.0.data_descriptor, self.zip64_data_descriptor());
+
+        let mut zip = if self.0.known_size {
+            let remaining = self.compressed_bytes_remaining();
+            let mut zip = self.into_ready();
+            zip.discard(remaining).await?;
+            zip
+        } else {
+            self.into_ready()
+        };
+
+        if data_descriptor {
+            zip.read_data_descriptor(None, zip64).await?;
+        }
+
+        Ok(zip)
+    }
+
+    /// Consumes any remaining bytes of the current entry, verifies its CRC32 value, and converts the reader back into
+    /// the Ready state.
+    ///
+    /// This is the explicit alternative to dropping a partially-read entry (see [`UnconsumedEntryBehavior`]). For
+    /// entries written with a data descriptor, the CRC32 value is verified against that held within the descriptor.
+    pub async fn close(mut self) -> Result<ZipFileReader<Ready<R>>> {
+        self.read_to_eof().await?;
+
+        let hash = self.reader().compute_hash();
+        let (data_descriptor, zip64) = (self.0.data_descriptor, self.zip64_data_descriptor());
+        let (filename, crc) = (self.0.entry.filename.clone(), self.0.entry.crc32());
+        let mut zip = self.into_ready();
+
+        if data_descriptor {
+            zip.read_data_descriptor(Some(hash), zip64).await.map_err(|err| err.in_entry(None, &filename))?;
+        } else if hash != crc {
+            return Err(ZipError::CRC32CheckError.in_entry(None, &filename));
+        }
+
+        Ok(zip)
+    }
+
+    async fn read_to_eof(&mut self) -> Result<()> {
+        let mut buffer = vec![0; SKIP_BUFFER_SIZE];
+
+        loop {
+            match self.reader().read(&mut buffer).await {
+                Ok(0) => return Ok(()),
+                Ok(_) => (),
+                Err(err) => {
+                    // As with done(), the error is returned in place of the drop check reporting it.
+                    self.0.reader = None;
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    /// Returns whether the entry's data descriptor holds ZIP64 (8-byte) sizes.
+    ///
+    /// Writers which stream an entry of unknown size may only widen these sizes once they grow past 4 GiB, without a
+    /// ZIP64 extra field being reserved within the local file header. As such, the amount of data is also considered.
+    fn zip64_data_descriptor(&self) -> bool {
+        let reader = self.0.reader.as_ref().expect("entry reader taken before transition");
+        let compressed_size =
+            if self.0.known_size { self.0.entry.compressed_size } else { reader.compressed_bytes_read() };
+
+        self.0.zip64
+            || compressed_size > NON_ZIP64_MAX_SIZE as u64
+            || reader.uncompressed_bytes_read() > NON_ZIP64_MAX_SIZE as u64
+    }
+
+    fn compressed_bytes_remaining(&self) -> u64 {
+        self.0.reader.as_ref().expect("entry reader taken before transition").compressed_bytes_remaining()
+    }
+
+    fn into_ready(mut self) -> ZipFileReader<Ready<R>> {
+        let offset = self.offset();
+        let reader = self.0.reader.take().expect("entry reader taken before transition");
+        let options = std::mem::take(&mut self.0.options);
+
+        ZipFileReader(Ready { reader: reader.into_inner(), offset, options, peeked: None })
+    }
+}
+
+impl<'a, R> ZipFileReader<Reading<'a, R>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Skips the remainder of the current entry by seeking past it, and converts the reader back into the Ready state.
+    ///
+    /// This falls back to [`ZipFileReader::skip()`] where the compressed size of the entry isn't known.
+    pub async fn skip_by_seeking(self) -> Result<ZipFileReader<Ready<R>>> {
+        if !self.0.known_size {
+            return self.skip().await;
+        }
+
+        let (data_descriptor, zip64) = (self.0.data_descriptor, self.zip64_data_descriptor());
+        let remaining = self.compressed_bytes_remaining();
+        let mut zip = self.into_ready();
+
+        let offset =
+            i64::try_from(remaining).map_err(|_| ZipError::UpstreamReadError(ErrorKind::InvalidInput.into()))?;
+        zip.0.reader.seek(SeekFrom::Current(offset)).await?;
+        zip.0.offset += remaining;
+
+        if data_descriptor {
+            zip.read_data_descriptor(None, zip64).await?;
+        }
+
+        Ok(zip)
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+
+/// A report of a ZIP file's verification, as returned by [`ZipFileReader::verify()`].
+///
+/// [`ZipFileReader::verify()`]: crate::read::seek::ZipFileReader::verify
+#[derive(Debug)]
+pub struct VerificationReport {
+    pub(crate) verified: usize,
+    pub(crate) failures: Vec<VerificationFailure>,
+}
+
+impl VerificationReport {
+    /// Returns whether every entry was verified successfully.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the number of entries which were verified successfully.
+    pub fn verified(&self) -> usize {
+        self.verified
+    }
+
+    /// Returns the entries which failed verification, ordered by the offset of their data within the file.
+    pub fn failures(&self) -> &[VerificationFailure] {
+        &self.failures
+    }
+}
+
+/// An entry which failed verification.
+#[derive(Debug)]
+pub struct VerificationFailure {
+    pub(crate) index: usize,
+    pub(crate) filename: String,
+    pub(crate) error: ZipError,
+}
+
+impl VerificationFailure {
+    /// Returns the index of the entry.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the entry's filename.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Returns the error encountered whilst reading the entry (eg. [`ZipError::CRC32CheckError`]).
+    pub fn error(&self) -> &ZipError {
+        &self.error
+    }
+}
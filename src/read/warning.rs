@@ -0,0 +1,32 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use thiserror::Error;
+
+/// A non-fatal anomaly found whilst opening a ZIP file in lenient mode.
+///
+/// See [`ReaderOptions::lenient()`](crate::read::ReaderOptions::lenient) and [`ZipFile::warnings()`](crate::ZipFile).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ZipWarning {
+    /// Fewer central directory records could be parsed than were declared by the end of central directory record.
+    #[error("the central directory declared {expected} entries, but only {found} could be parsed")]
+    TruncatedCentralDirectory { expected: u64, found: u64 },
+    /// An entry's extra field data isn't made up of well-formed fields.
+    #[error("entry {index} holds a malformed extra field")]
+    MalformedExtraField { index: usize },
+    /// An entry's compressed data would extend past the start of the central directory.
+    #[error("entry {index}'s data overlaps the central directory")]
+    DataOverlapsCentralDirectory { index: usize },
+    /// No local file header was found at the offset held within an entry's central directory record.
+    #[error("entry {index}'s local file header is missing")]
+    MissingLocalFileHeader { index: usize },
+    /// An entry's local file header was found but couldn't be read in full (eg. as the file is truncated).
+    #[error("entry {index}'s local file header couldn't be read")]
+    UnreadableLocalFileHeader { index: usize },
+    /// An entry's CRC32 value, sizes, or compression method differ between its local file header and its central
+    /// directory record.
+    #[error("entry {index}'s local file header doesn't match its central directory record")]
+    LocalFileHeaderMismatch { index: usize },
+}
@@ -6,7 +6,11 @@ use crate::error::{Result, ZipError};
 /// An attribute host compatibility supported by this crate.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttributeCompatibility {
+    /// MS-DOS & Windows, where the external file attribute holds `FILE_ATTRIBUTE_*` flags within its lower byte.
+    Dos,
+    /// Unix, where the external file attribute holds a mode within its upper 16 bits.
     Unix,
 }
 
@@ -17,6 +21,7 @@ impl TryFrom<u16> for AttributeCompatibility {
     // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4422
     fn try_from(value: u16) -> Result<Self> {
         match value {
+            0 => Ok(AttributeCompatibility::Dos),
             3 => Ok(AttributeCompatibility::Unix),
             _ => Err(ZipError::AttributeCompatibilityNotSupported(value)),
         }
@@ -28,6 +33,7 @@ impl From<&AttributeCompatibility> for u16 {
     // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4422
     fn from(compatibility: &AttributeCompatibility) -> Self {
         match compatibility {
+            AttributeCompatibility::Dos => 0,
             AttributeCompatibility::Unix => 3,
         }
     }
@@ -39,3 +45,151 @@ impl From<AttributeCompatibility> for u16 {
         (&compatibility).into()
     }
 }
+
+/// The MS-DOS `FILE_ATTRIBUTE_READONLY` flag.
+const DOS_READONLY: u32 = 0x01;
+/// The MS-DOS `FILE_ATTRIBUTE_HIDDEN` flag.
+const DOS_HIDDEN: u32 = 0x02;
+/// The MS-DOS `FILE_ATTRIBUTE_SYSTEM` flag.
+const DOS_SYSTEM: u32 = 0x04;
+/// The MS-DOS `FILE_ATTRIBUTE_DIRECTORY` flag.
+const DOS_DIRECTORY: u32 = 0x10;
+
+/// The Unix file type bits (ie. `S_IFMT`) and the directory file type (ie. `S_IFDIR`).
+const UNIX_TYPE_MASK: u32 = 0o170000;
+const UNIX_DIRECTORY_TYPE: u32 = 0o040000;
+
+/// A typed view of an entry's external file attribute, independent of its attribute host compatibility.
+///
+/// Unix modes are stored within the upper 16 bits of the raw attribute, whilst the MS-DOS flags (as used by Windows)
+/// are stored within the lower byte. When read, the read-only flag is derived from the Unix mode where one is present.
+/// When written with Unix compatibility, both representations are stored (as Info-ZIP does), so that either kind of
+/// extractor can apply them.
+///
+/// ### Example
+/// ```
+/// # use async_zip::{Attributes, Compression, ZipEntryBuilder};
+/// #
+/// let mut attributes = Attributes::from_unix_mode(0o100444);
+/// attributes.set_hidden(true);
+///
+/// let entry = ZipEntryBuilder::new(String::from(".foo.txt"), Compression::Stored).attributes(attributes).build();
+/// assert!(entry.attributes().readonly() && entry.attributes().hidden());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Attributes {
+    unix_mode: Option<u32>,
+    readonly: bool,
+    hidden: bool,
+    system: bool,
+    directory: bool,
+}
+
+impl Attributes {
+    /// Constructs a new set of attributes with no flags set and no Unix mode.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a new set of attributes from a Unix mode (eg. from [`std::os::unix::fs::PermissionsExt::mode()`]).
+    ///
+    /// The read-only and directory flags are derived from the mode.
+    pub fn from_unix_mode(mode: u32) -> Self {
+        let mut attributes = Self::default();
+        attributes.set_unix_mode(mode);
+        attributes
+    }
+
+    /// Returns the Unix mode (ie. file type & permission bits), if present.
+    pub fn unix_mode(&self) -> Option<u32> {
+        self.unix_mode
+    }
+
+    /// Returns whether the read-only flag is set.
+    pub fn readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Returns whether the hidden flag is set.
+    pub fn hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Returns whether the system flag is set.
+    pub fn system(&self) -> bool {
+        self.system
+    }
+
+    /// Returns whether the directory flag is set.
+    pub fn directory(&self) -> bool {
+        self.directory
+    }
+
+    /// Sets the Unix mode, updating the read-only and directory flags to match.
+    pub fn set_unix_mode(&mut self, mode: u32) {
+        let mode = mode & 0xFFFF;
+        self.unix_mode = Some(mode);
+        self.readonly = mode & 0o222 == 0;
+        self.directory = mode & UNIX_TYPE_MASK == UNIX_DIRECTORY_TYPE;
+    }
+
+    /// Sets the read-only flag, clearing or restoring the owner's write permission within any Unix mode to match.
+    pub fn set_readonly(&mut self, readonly: bool) {
+        self.readonly = readonly;
+
+        if let Some(mode) = self.unix_mode.as_mut() {
+            *mode = if readonly { *mode & !0o222 } else { *mode | 0o200 };
+        }
+    }
+
+    /// Sets the hidden flag.
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+    }
+
+    /// Sets the system flag.
+    pub fn set_system(&mut self, system: bool) {
+        self.system = system;
+    }
+
+    /// Sets the directory flag.
+    pub fn set_directory(&mut self, directory: bool) {
+        self.directory = directory;
+    }
+
+    /// Parses a raw external file attribute according to the provided attribute host compatibility.
+    pub(crate) fn from_raw(compatibility: AttributeCompatibility, raw: u32) -> Self {
+        let mut attributes = Self {
+            unix_mode: None,
+            readonly: raw & DOS_READONLY != 0,
+            hidden: raw & DOS_HIDDEN != 0,
+            system: raw & DOS_SYSTEM != 0,
+            directory: raw & DOS_DIRECTORY != 0,
+        };
+
+        if compatibility == AttributeCompatibility::Unix && raw >> 16 != 0 {
+            let mode = raw >> 16;
+            attributes.unix_mode = Some(mode);
+            attributes.readonly = mode & 0o222 == 0;
+            attributes.directory |= mode & UNIX_TYPE_MASK == UNIX_DIRECTORY_TYPE;
+        }
+
+        attributes
+    }
+
+    /// Returns the raw external file attribute for the provided attribute host compatibility.
+    pub(crate) fn to_raw(self, compatibility: AttributeCompatibility) -> u32 {
+        let flags = [
+            (self.readonly, DOS_READONLY),
+            (self.hidden, DOS_HIDDEN),
+            (self.system, DOS_SYSTEM),
+            (self.directory, DOS_DIRECTORY),
+        ];
+        let raw = flags.into_iter().filter(|(set, _)| *set).fold(0, |raw, (_, flag)| raw | flag);
+
+        match (compatibility, self.unix_mode) {
+            (AttributeCompatibility::Unix, Some(mode)) => raw | mode << 16,
+            _ => raw,
+        }
+    }
+}
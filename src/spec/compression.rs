@@ -9,6 +9,7 @@ use async_compression::Level;
 /// A compression method supported by this crate.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Compression {
     Stored,
     #[cfg(feature = "deflate")]
@@ -72,6 +73,38 @@ impl From<Compression> for u16 {
     }
 }
 
+/// A level of compression which trades off compression speed against the size of the compressed data.
+///
+/// A precise level is interpreted by the entry's compression method, and is clamped to that method's range:
+/// - Deflate: a level from 0 to 9.
+/// - bzip2: a block size from 1 to 9 (in units of 100KB).
+/// - LZMA & xz: a preset from 0 to 9.
+/// - zstd: a level from 1 to 21.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionLevel {
+    /// The fastest level of compression supported by the compression method.
+    Fastest,
+    /// The level of compression which produces the smallest data supported by the compression method.
+    Best,
+    /// The compression method's library-defined default level.
+    #[default]
+    Default,
+    /// A precise level specific to the compression method.
+    Precise(u32),
+}
+
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+impl CompressionLevel {
+    pub(crate) fn into_level(self) -> Level {
+        match self {
+            Self::Fastest => Level::Fastest,
+            Self::Best => Level::Best,
+            Self::Default => Level::Default,
+            Self::Precise(level) => Level::Precise(level),
+        }
+    }
+}
+
 /// Level of compression data should be compressed with for deflate.
 #[derive(Debug, Clone, Copy)]
 pub enum DeflateOption {
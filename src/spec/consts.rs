@@ -21,5 +21,41 @@ pub const CDH_LENGTH: usize = 42;
 pub const EOCDR_SIGNATURE: u32 = 0x6054b50;
 pub const EOCDR_LENGTH: usize = 18;
 
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4311
+pub const ARCHIVE_EXTRA_DATA_SIGNATURE: u32 = 0x8064b50;
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#439
 pub const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x8074b50;
+pub const DATA_DESCRIPTOR_LENGTH: usize = 12;
+pub const ZIP64_DATA_DESCRIPTOR_LENGTH: usize = 20;
+
+// ZIP64 end of central directory record & locator constants
+//
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4314
+pub const ZIP64_EOCDR_SIGNATURE: u32 = 0x6064b50;
+pub const ZIP64_EOCDR_LENGTH: usize = 52;
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4315
+pub const ZIP64_EOCDL_SIGNATURE: u32 = 0x7064b50;
+pub const ZIP64_EOCDL_LENGTH: usize = 16;
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4352
+pub const ZIP64_EXTRA_FIELD_HEADER_ID: u16 = 0x1;
+
+// Third-party extra field header IDs
+//
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#461
+pub const EXTENDED_TIMESTAMP_HEADER_ID: u16 = 0x5455;
+pub const UNIX_OWNER_HEADER_ID: u16 = 0x7875;
+
+// The header ID used by Android's zipalign/apksigner tooling when padding local file headers for alignment.
+pub const ALIGNMENT_EXTRA_FIELD_HEADER_ID: u16 = 0xd935;
+
+// The header ID of WinZip's AES extra field, and the placeholder compression method of AES-encrypted entries.
+// https://www.winzip.com/en/support/aes-encryption/
+pub const AES_EXTRA_FIELD_HEADER_ID: u16 = 0x9901;
+pub const AES_COMPRESSION_METHOD: u16 = 99;
+
+// The maximum values which can be held by the non-ZIP64 structures (which also act as the sentinel values marking
+// that the actual value is held within a ZIP64 structure).
+pub const NON_ZIP64_MAX_SIZE: u32 = u32::MAX;
+pub const NON_ZIP64_MAX_NUM_FILES: u16 = u16::MAX;
@@ -2,13 +2,16 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 #[cfg(feature = "chrono")]
-use chrono::{DateTime, Datelike, LocalResult, TimeZone, Timelike, Utc};
+use chrono::{DateTime, LocalResult, TimeZone, Utc};
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#446
 // https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-dosdatetimetovarianttime
 
 /// A date and time stored as per the MS-DOS representation used by ZIP files.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ZipDateTime {
     pub(crate) date: u16,
     pub(crate) time: u16,
@@ -47,6 +50,69 @@ impl ZipDateTime {
         ((self.time & 0x1F) << 1).into()
     }
 
+    /// Constructs this date & time from a Unix timestamp (in seconds), interpreted as UTC.
+    ///
+    /// Timestamps outside of the range representable by MS-DOS (1980 to 2107) are clamped to its bounds.
+    pub(crate) fn from_unix_timestamp(seconds: i64) -> Self {
+        let days = seconds.div_euclid(86400);
+        let second_of_day = seconds.rem_euclid(86400);
+
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let day_of_era = z - era * 146097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        if year < 1980 {
+            return ZipDateTime { date: 1 << 5 | 1, time: 0 };
+        } else if year > 2107 {
+            return ZipDateTime { date: 127 << 9 | 12 << 5 | 31, time: 23 << 11 | 59 << 5 | 29 };
+        }
+
+        let date = ((year - 1980) << 9 | month << 5 | day) as u16;
+        let time = ((second_of_day / 3600) << 11 | (second_of_day % 3600 / 60) << 5 | (second_of_day % 60) >> 1) as u16;
+
+        ZipDateTime { date, time }
+    }
+
+    /// Constructs this date & time from a [`SystemTime`], interpreted as UTC.
+    ///
+    /// Times outside of the range representable by MS-DOS (1980 to 2107) are clamped to its bounds, and odd seconds
+    /// are rounded down (as MS-DOS has a maximum granularity of two seconds).
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let seconds = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => i64::try_from(duration.as_secs()).unwrap_or(i64::MAX),
+            Err(err) => i64::try_from(err.duration().as_secs()).map(|seconds| -seconds).unwrap_or(i64::MIN),
+        };
+
+        Self::from_unix_timestamp(seconds)
+    }
+
+    /// Returns the [`SystemTime`] representation of this date & time, interpreted as UTC.
+    pub fn as_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.as_unix_timestamp().max(0) as u64)
+    }
+
+    /// Returns the Unix timestamp (in seconds) of this date & time, interpreted as UTC.
+    pub(crate) fn as_unix_timestamp(&self) -> i64 {
+        let (year, month, day) = (self.year() as i64, self.month() as i64, self.day() as i64);
+
+        // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146097 + day_of_era - 719468;
+
+        days * 86400 + (self.hour() * 3600 + self.minute() * 60 + self.second()) as i64
+    }
+
     /// Constructs chrono's [`DateTime`] representation of this date & time.
     ///
     /// Note that this requires the `chrono` feature.
@@ -57,17 +123,22 @@ impl ZipDateTime {
 
     /// Constructs this date & time from chrono's [`DateTime`] representation.
     ///
-    /// Note that this requires the `chrono` feature.
+    /// As with [`ZipDateTime::from_system_time()`], dates outside of the range representable by MS-DOS are clamped to
+    /// its bounds. Note that this requires the `chrono` feature.
     #[cfg(feature = "chrono")]
     pub fn from_chrono(dt: &DateTime<Utc>) -> Self {
-        let year: u16 = (((dt.date_naive().year() - 1980) << 9) & 0xFE00).try_into().unwrap();
-        let month: u16 = ((dt.date_naive().month() << 5) & 0x1E0).try_into().unwrap();
-        let day: u16 = (dt.date_naive().day() & 0x1F).try_into().unwrap();
+        Self::from_unix_timestamp(dt.timestamp())
+    }
+}
 
-        let hour: u16 = ((dt.time().hour() << 11) & 0xF800).try_into().unwrap();
-        let min: u16 = ((dt.time().minute() << 5) & 0x7E0).try_into().unwrap();
-        let second: u16 = ((dt.time().second() >> 1) & 0x1F).try_into().unwrap();
+impl From<SystemTime> for ZipDateTime {
+    fn from(time: SystemTime) -> Self {
+        Self::from_system_time(time)
+    }
+}
 
-        ZipDateTime { date: year | month | day, time: hour | min | second }
+impl From<ZipDateTime> for SystemTime {
+    fn from(date: ZipDateTime) -> Self {
+        date.as_system_time()
     }
 }
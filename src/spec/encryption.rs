@@ -0,0 +1,30 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+/// An encryption method supported by this crate when writing entries.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub enum Encryption {
+    /// The traditional PKWARE encryption method (also known as ZipCrypto), keyed from the provided password.
+    ///
+    /// This method is considered weak and shouldn't be relied upon to protect sensitive data, but is supported by
+    /// almost every ZIP consumer.
+    ZipCrypto(Vec<u8>),
+    /// WinZip's AES-256 encryption method (AE-2), keyed from the provided password.
+    ///
+    /// Keys are derived using PBKDF2 with a random per-entry salt, and the encrypted data is authenticated with an
+    /// HMAC-SHA1 code. Note that this requires the `aes` feature.
+    #[cfg(feature = "aes")]
+    Aes256(Vec<u8>),
+}
+
+impl std::fmt::Debug for Encryption {
+    // The password is intentionally omitted so it isn't leaked into logs.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encryption::ZipCrypto(_) => f.write_str("ZipCrypto"),
+            #[cfg(feature = "aes")]
+            Encryption::Aes256(_) => f.write_str("Aes256"),
+        }
+    }
+}
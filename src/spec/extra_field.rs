@@ -0,0 +1,187 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4352
+
+use crate::spec::consts::{AES_EXTRA_FIELD_HEADER_ID, EXTENDED_TIMESTAMP_HEADER_ID, UNIX_OWNER_HEADER_ID};
+use crate::spec::consts::{NON_ZIP64_MAX_SIZE, ZIP64_EXTRA_FIELD_HEADER_ID};
+
+/// A structured extra field which may be attached to an entry.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtraField {
+    /// The extended timestamp field (0x5455), holding Unix timestamps in seconds.
+    ExtendedTimestamp { modified: Option<i32>, accessed: Option<i32>, created: Option<i32> },
+    /// The Info-ZIP Unix field (0x7875), holding the IDs of the entry's owning user and group.
+    UnixOwner { uid: u32, gid: u32 },
+    /// An arbitrary field, made of its header ID and raw data.
+    ///
+    /// Note that any ZIP64 extended information field (0x0001) is managed by the writer itself, and is dropped.
+    Custom { id: u16, data: Vec<u8> },
+}
+
+impl ExtraField {
+    /// Returns the field's header ID.
+    pub fn header_id(&self) -> u16 {
+        match self {
+            ExtraField::ExtendedTimestamp { .. } => EXTENDED_TIMESTAMP_HEADER_ID,
+            ExtraField::UnixOwner { .. } => UNIX_OWNER_HEADER_ID,
+            ExtraField::Custom { id, .. } => *id,
+        }
+    }
+
+    /// Serialises the field (along with its header ID and data length).
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        match self {
+            ExtraField::ExtendedTimestamp { modified, accessed, created } => {
+                let times = [modified, accessed, created];
+                let flags = times.iter().enumerate().filter(|(_, time)| time.is_some()).map(|(bit, _)| 1 << bit);
+
+                data.push(flags.sum::<u8>());
+                for time in times.into_iter().flatten() {
+                    data.extend_from_slice(&time.to_le_bytes());
+                }
+            }
+            ExtraField::UnixOwner { uid, gid } => {
+                // Version 1, followed by each ID prefixed with its size.
+                data.push(1);
+                data.push(4);
+                data.extend_from_slice(&uid.to_le_bytes());
+                data.push(4);
+                data.extend_from_slice(&gid.to_le_bytes());
+            }
+            ExtraField::Custom { data: custom, .. } => data.extend_from_slice(custom),
+        }
+
+        let mut field = Vec::with_capacity(4 + data.len());
+        field.extend_from_slice(&self.header_id().to_le_bytes());
+        field.extend_from_slice(&(data.len() as u16).to_le_bytes());
+        field.extend(data);
+        field
+    }
+}
+
+/// Constructs a ZIP64 extended information extra field holding the provided values in order.
+pub(crate) fn zip64_extra_field(values: &[u64]) -> Vec<u8> {
+    let mut field = Vec::with_capacity(4 + values.len() * 8);
+    field.extend_from_slice(&ZIP64_EXTRA_FIELD_HEADER_ID.to_le_bytes());
+    field.extend_from_slice(&((values.len() * 8) as u16).to_le_bytes());
+
+    for value in values {
+        field.extend_from_slice(&value.to_le_bytes());
+    }
+
+    field
+}
+
+/// Returns a copy of the provided extra field data with any ZIP64 extended information extra fields removed.
+///
+/// This is used when writing an entry which was read from another archive, as the writer reconstructs this field from
+/// the entry's actual values where required.
+pub(crate) fn without_zip64_extra_field(mut extra_field: &[u8]) -> Vec<u8> {
+    let mut retained = Vec::with_capacity(extra_field.len());
+
+    while extra_field.len() >= 4 {
+        let id = u16::from_le_bytes([extra_field[0], extra_field[1]]);
+        let length = u16::from_le_bytes([extra_field[2], extra_field[3]]) as usize;
+        let end = std::cmp::min(4 + length, extra_field.len());
+
+        if id != ZIP64_EXTRA_FIELD_HEADER_ID {
+            retained.extend_from_slice(&extra_field[..end]);
+        }
+
+        extra_field = &extra_field[end..];
+    }
+
+    // Any trailing bytes too short to form a field header are kept as-is.
+    retained.extend_from_slice(extra_field);
+    retained
+}
+
+/// Returns whether the provided extra field data contains a ZIP64 extended information extra field.
+pub(crate) fn has_zip64_extra_field(extra_field: &[u8]) -> bool {
+    has_field(extra_field, ZIP64_EXTRA_FIELD_HEADER_ID)
+}
+
+/// Returns whether the provided extra field data contains a field with the provided header ID.
+pub(crate) fn has_field(extra_field: &[u8], header_id: u16) -> bool {
+    find_field(extra_field, header_id).is_some()
+}
+
+/// Returns the data of the first field with the provided header ID within the provided extra field data.
+pub(crate) fn find_field(mut extra_field: &[u8], header_id: u16) -> Option<&[u8]> {
+    while extra_field.len() >= 4 {
+        let id = u16::from_le_bytes([extra_field[0], extra_field[1]]);
+        let length = u16::from_le_bytes([extra_field[2], extra_field[3]]) as usize;
+        let data = extra_field.get(4..4 + length)?;
+
+        if id == header_id {
+            return Some(data);
+        }
+
+        extra_field = &extra_field[4 + length..];
+    }
+
+    None
+}
+
+/// Returns the actual compression method held within an AES extra field, if any.
+pub(crate) fn aes_compression_method(extra_field: &[u8]) -> Option<u16> {
+    match find_field(extra_field, AES_EXTRA_FIELD_HEADER_ID)? {
+        [_, _, b'A', b'E', _, a, b, ..] => Some(u16::from_le_bytes([*a, *b])),
+        _ => None,
+    }
+}
+
+/// Returns whether the provided extra field data is made up entirely of complete fields.
+pub(crate) fn is_well_formed(mut extra_field: &[u8]) -> bool {
+    while extra_field.len() >= 4 {
+        let length = u16::from_le_bytes([extra_field[2], extra_field[3]]) as usize;
+
+        match extra_field.get(4 + length..) {
+            Some(remaining) => extra_field = remaining,
+            None => return false,
+        }
+    }
+
+    extra_field.is_empty()
+}
+
+/// Returns the modification time (as a Unix timestamp in seconds) held within an extended timestamp extra field, if any.
+#[cfg(feature = "fs")]
+pub(crate) fn extended_modification_time(extra_field: &[u8]) -> Option<i64> {
+    match find_field(extra_field, EXTENDED_TIMESTAMP_HEADER_ID)? {
+        [flags, a, b, c, d, ..] if flags & 1 != 0 => Some(i32::from_le_bytes([*a, *b, *c, *d]).into()),
+        _ => None,
+    }
+}
+
+/// A cursor over the values held within a ZIP64 extended information extra field.
+///
+/// The field only holds values for those header fields which were set to their sentinel value, and always in the same
+/// order (uncompressed size, compressed size, then local file header offset). As such, each header field must be
+/// resolved in that order.
+pub(crate) struct Zip64Values<'a>(&'a [u8]);
+
+impl<'a> Zip64Values<'a> {
+    /// Constructs a new cursor from an entry's extra field data (which is empty if no ZIP64 field is present).
+    pub(crate) fn new(extra_field: &'a [u8]) -> Self {
+        Self(find_field(extra_field, ZIP64_EXTRA_FIELD_HEADER_ID).unwrap_or_default())
+    }
+
+    /// Resolves a header field's value, taking the next ZIP64 value if it's set to the sentinel value.
+    ///
+    /// If no ZIP64 value remains, the sentinel value itself is returned.
+    pub(crate) fn resolve(&mut self, value: u32) -> u64 {
+        if value != NON_ZIP64_MAX_SIZE || self.0.len() < 8 {
+            return value.into();
+        }
+
+        let (resolved, remaining) = self.0.split_at(8);
+        self.0 = remaining;
+
+        u64::from_le_bytes(resolved.try_into().unwrap())
+    }
+}
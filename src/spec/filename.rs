@@ -0,0 +1,121 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::{Result, ZipError};
+
+/// The policy which determines how an entry's filename is validated & normalised when it's written.
+///
+/// Filenames containing NUL bytes are always rejected, as they're truncated by many extractors. By default, backslashes
+/// are converted to forward slashes (as required by the specification), but filenames with parent directory
+/// components or absolute paths are written as-is. [`FilenamePolicy::strict()`] rejects both of the latter, which
+/// prevents archives from being written which would trip an extractor's path traversal checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FilenamePolicy {
+    pub(crate) normalize_separators: bool,
+    pub(crate) reject_parent_components: bool,
+    pub(crate) reject_absolute: bool,
+}
+
+impl Default for FilenamePolicy {
+    fn default() -> Self {
+        Self { normalize_separators: true, reject_parent_components: false, reject_absolute: false }
+    }
+}
+
+impl FilenamePolicy {
+    /// Constructs the default filename policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a filename policy which rejects parent directory components and absolute paths.
+    pub fn strict() -> Self {
+        Self::default().reject_parent_components(true).reject_absolute(true)
+    }
+
+    /// Sets whether backslashes should be converted to forward slashes.
+    pub fn normalize_separators(mut self, value: bool) -> Self {
+        self.normalize_separators = value;
+        self
+    }
+
+    /// Sets whether filenames containing a parent directory (`..`) component should be rejected.
+    pub fn reject_parent_components(mut self, value: bool) -> Self {
+        self.reject_parent_components = value;
+        self
+    }
+
+    /// Sets whether absolute filenames (ie. those with a leading slash or a Windows drive prefix) should be rejected.
+    pub fn reject_absolute(mut self, value: bool) -> Self {
+        self.reject_absolute = value;
+        self
+    }
+
+    /// Normalises the provided filename in place, or returns an error if it's rejected by this policy.
+    pub(crate) fn apply(&self, filename: &mut String) -> Result<()> {
+        if filename.contains('\0') {
+            return rejected(filename, "contains a NUL byte");
+        }
+
+        if self.normalize_separators && filename.contains('\\') {
+            *filename = filename.replace('\\', "/");
+        }
+
+        // Backslashes are also treated as separators here so that these checks hold even without normalisation.
+        let is_drive_prefixed =
+            |name: &str| matches!(name.as_bytes(), [letter, b':', ..] if letter.is_ascii_alphabetic());
+        if self.reject_absolute && (filename.starts_with(['/', '\\']) || is_drive_prefixed(filename)) {
+            return rejected(filename, "is an absolute path");
+        }
+
+        if self.reject_parent_components && filename.split(['/', '\\']).any(|component| component == "..") {
+            return rejected(filename, "contains a parent directory component");
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether the UTF-8 language encoding flag (bit 11 of the general purpose flag) is set when an entry is written.
+///
+/// The flag declares that an entry's filename & comment are encoded as UTF-8 rather than IBM Code Page 437. Some legacy
+/// consumers misbehave when it's set unexpectedly, whereas others mangle non-ASCII filenames when it's missing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Utf8Flag {
+    /// Set the flag only if the entry's filename or comment contains non-ASCII characters.
+    #[default]
+    Auto,
+    /// Always set the flag.
+    Always,
+    /// Never set the flag, rejecting entries whose filename or comment contains non-ASCII characters (as they'd
+    /// otherwise be misread as Code Page 437).
+    Never,
+}
+
+impl Utf8Flag {
+    /// Returns an error if the provided filename & comment can't be written consistently with this flag policy.
+    pub(crate) fn check(&self, filename: &str, comment: &str) -> Result<()> {
+        match self {
+            Utf8Flag::Never if !filename.is_ascii() => {
+                rejected(filename, "is non-ASCII but the UTF-8 flag is disabled")
+            }
+            Utf8Flag::Never if !comment.is_ascii() => {
+                rejected(filename, "has a non-ASCII comment but the UTF-8 flag is disabled")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns whether the flag should be set for the provided filename & comment.
+    pub(crate) fn enabled(&self, filename: &str, comment: &str) -> bool {
+        match self {
+            Utf8Flag::Auto => !filename.is_ascii() || !comment.is_ascii(),
+            Utf8Flag::Always => true,
+            Utf8Flag::Never => false,
+        }
+    }
+}
+
+fn rejected(filename: &str, reason: &'static str) -> Result<()> {
+    Err(ZipError::InvalidFilename(filename.to_string(), reason))
+}
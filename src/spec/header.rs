@@ -17,10 +17,25 @@ pub struct LocalFileHeader {
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#444
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GeneralPurposeFlag {
     pub encrypted: bool,
     pub data_descriptor: bool,
     pub filename_unicode: bool,
+    // The below are only ever read, as this crate doesn't write entries which use them.
+    pub patched: bool,
+    pub strong_encryption: bool,
+    pub masked_header: bool,
+}
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#439
+//
+// Only the CRC32 value is currently verified by the stream reader, so the sizes are left unread.
+#[allow(dead_code)]
+pub struct DataDescriptor {
+    pub crc: u32,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4312
@@ -53,3 +68,23 @@ pub struct EndOfCentralDirectoryHeader {
     pub(crate) cent_dir_offset: u32,
     pub(crate) file_comm_length: u16,
 }
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4314
+pub struct Zip64EndOfCentralDirectoryRecord {
+    pub(crate) size_of_record: u64,
+    pub(crate) v_made_by: u16,
+    pub(crate) v_needed: u16,
+    pub(crate) disk_num: u32,
+    pub(crate) start_cent_dir_disk: u32,
+    pub(crate) num_of_entries_disk: u64,
+    pub(crate) num_of_entries: u64,
+    pub(crate) size_cent_dir: u64,
+    pub(crate) cent_dir_offset: u64,
+}
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4315
+pub struct Zip64EndOfCentralDirectoryLocator {
+    pub(crate) zip64_eocdr_disk: u32,
+    pub(crate) zip64_eocdr_offset: u64,
+    pub(crate) total_disks: u32,
+}
@@ -5,6 +5,9 @@ pub(crate) mod attribute;
 pub(crate) mod compression;
 pub(crate) mod consts;
 pub(crate) mod date;
+pub(crate) mod encryption;
+pub(crate) mod extra_field;
+pub(crate) mod filename;
 pub(crate) mod header;
 pub(crate) mod parse;
 pub(crate) mod version;
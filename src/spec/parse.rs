@@ -1,14 +1,19 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use crate::error::Result;
-use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader, GeneralPurposeFlag, LocalFileHeader};
+use crate::error::{Result, ZipError};
+use crate::spec::consts::{CDH_LENGTH, DATA_DESCRIPTOR_LENGTH, EOCDR_LENGTH, LFH_LENGTH};
+use crate::spec::consts::{ZIP64_DATA_DESCRIPTOR_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDR_LENGTH};
+use crate::spec::header::{
+    CentralDirectoryRecord, DataDescriptor, EndOfCentralDirectoryHeader, GeneralPurposeFlag, LocalFileHeader,
+    Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
+};
 
 use tokio::io::{AsyncRead, AsyncReadExt};
 
 impl LocalFileHeader {
-    pub fn as_slice(&self) -> [u8; 26] {
-        let mut array = [0; 26];
+    pub fn as_slice(&self) -> [u8; LFH_LENGTH] {
+        let mut array = [0; LFH_LENGTH];
         let mut cursor = 0;
 
         array_push!(array, cursor, self.version.to_le_bytes());
@@ -43,11 +48,27 @@ impl GeneralPurposeFlag {
 
         (encrypted | data_descriptor | filename_unicode).to_le_bytes()
     }
+
+    /// Returns an error if an entry with these flags requires a feature which this crate can't read.
+    ///
+    /// This is checked before an entry's data is read, so that a clear error is returned rather than a decompression
+    /// or CRC32 failure part way through the data.
+    pub(crate) fn check_supported(&self) -> Result<()> {
+        let unsupported = match self {
+            flags if flags.masked_header => "Central directory encryption",
+            flags if flags.strong_encryption => "Strong encryption",
+            flags if flags.encrypted => "Reading encrypted entries",
+            flags if flags.patched => "Patched data",
+            _ => return Ok(()),
+        };
+
+        Err(ZipError::FeatureNotSupported(unsupported))
+    }
 }
 
 impl CentralDirectoryRecord {
-    pub fn as_slice(&self) -> [u8; 42] {
-        let mut array = [0; 42];
+    pub fn as_slice(&self) -> [u8; CDH_LENGTH] {
+        let mut array = [0; CDH_LENGTH];
         let mut cursor = 0;
 
         array_push!(array, cursor, self.v_made_by.to_le_bytes());
@@ -72,8 +93,8 @@ impl CentralDirectoryRecord {
 }
 
 impl EndOfCentralDirectoryHeader {
-    pub fn as_slice(&self) -> [u8; 18] {
-        let mut array = [0; 18];
+    pub fn as_slice(&self) -> [u8; EOCDR_LENGTH] {
+        let mut array = [0; EOCDR_LENGTH];
         let mut cursor = 0;
 
         array_push!(array, cursor, self.disk_num.to_le_bytes());
@@ -88,8 +109,40 @@ impl EndOfCentralDirectoryHeader {
     }
 }
 
-impl From<[u8; 26]> for LocalFileHeader {
-    fn from(value: [u8; 26]) -> LocalFileHeader {
+impl Zip64EndOfCentralDirectoryRecord {
+    pub fn as_slice(&self) -> [u8; ZIP64_EOCDR_LENGTH] {
+        let mut array = [0; ZIP64_EOCDR_LENGTH];
+        let mut cursor = 0;
+
+        array_push!(array, cursor, self.size_of_record.to_le_bytes());
+        array_push!(array, cursor, self.v_made_by.to_le_bytes());
+        array_push!(array, cursor, self.v_needed.to_le_bytes());
+        array_push!(array, cursor, self.disk_num.to_le_bytes());
+        array_push!(array, cursor, self.start_cent_dir_disk.to_le_bytes());
+        array_push!(array, cursor, self.num_of_entries_disk.to_le_bytes());
+        array_push!(array, cursor, self.num_of_entries.to_le_bytes());
+        array_push!(array, cursor, self.size_cent_dir.to_le_bytes());
+        array_push!(array, cursor, self.cent_dir_offset.to_le_bytes());
+
+        array
+    }
+}
+
+impl Zip64EndOfCentralDirectoryLocator {
+    pub fn as_slice(&self) -> [u8; ZIP64_EOCDL_LENGTH] {
+        let mut array = [0; ZIP64_EOCDL_LENGTH];
+        let mut cursor = 0;
+
+        array_push!(array, cursor, self.zip64_eocdr_disk.to_le_bytes());
+        array_push!(array, cursor, self.zip64_eocdr_offset.to_le_bytes());
+        array_push!(array, cursor, self.total_disks.to_le_bytes());
+
+        array
+    }
+}
+
+impl From<[u8; LFH_LENGTH]> for LocalFileHeader {
+    fn from(value: [u8; LFH_LENGTH]) -> LocalFileHeader {
         LocalFileHeader {
             version: u16::from_le_bytes(value[0..2].try_into().unwrap()),
             flags: GeneralPurposeFlag::from(u16::from_le_bytes(value[2..4].try_into().unwrap())),
@@ -110,13 +163,16 @@ impl From<u16> for GeneralPurposeFlag {
         let encrypted = !matches!(value & 0x1, 0);
         let data_descriptor = !matches!((value & 0x8) >> 3, 0);
         let filename_unicode = !matches!((value & 0x800) >> 11, 0);
+        let patched = !matches!((value & 0x20) >> 5, 0);
+        let strong_encryption = !matches!((value & 0x40) >> 6, 0);
+        let masked_header = !matches!((value & 0x2000) >> 13, 0);
 
-        GeneralPurposeFlag { encrypted, data_descriptor, filename_unicode }
+        GeneralPurposeFlag { encrypted, data_descriptor, filename_unicode, patched, strong_encryption, masked_header }
     }
 }
 
-impl From<[u8; 42]> for CentralDirectoryRecord {
-    fn from(value: [u8; 42]) -> CentralDirectoryRecord {
+impl From<[u8; CDH_LENGTH]> for CentralDirectoryRecord {
+    fn from(value: [u8; CDH_LENGTH]) -> CentralDirectoryRecord {
         CentralDirectoryRecord {
             v_made_by: u16::from_le_bytes(value[0..2].try_into().unwrap()),
             v_needed: u16::from_le_bytes(value[2..4].try_into().unwrap()),
@@ -138,8 +194,28 @@ impl From<[u8; 42]> for CentralDirectoryRecord {
     }
 }
 
-impl From<[u8; 18]> for EndOfCentralDirectoryHeader {
-    fn from(value: [u8; 18]) -> EndOfCentralDirectoryHeader {
+impl From<[u8; DATA_DESCRIPTOR_LENGTH]> for DataDescriptor {
+    fn from(value: [u8; DATA_DESCRIPTOR_LENGTH]) -> DataDescriptor {
+        DataDescriptor {
+            crc: u32::from_le_bytes(value[0..4].try_into().unwrap()),
+            compressed_size: u32::from_le_bytes(value[4..8].try_into().unwrap()).into(),
+            uncompressed_size: u32::from_le_bytes(value[8..12].try_into().unwrap()).into(),
+        }
+    }
+}
+
+impl From<[u8; ZIP64_DATA_DESCRIPTOR_LENGTH]> for DataDescriptor {
+    fn from(value: [u8; ZIP64_DATA_DESCRIPTOR_LENGTH]) -> DataDescriptor {
+        DataDescriptor {
+            crc: u32::from_le_bytes(value[0..4].try_into().unwrap()),
+            compressed_size: u64::from_le_bytes(value[4..12].try_into().unwrap()),
+            uncompressed_size: u64::from_le_bytes(value[12..20].try_into().unwrap()),
+        }
+    }
+}
+
+impl From<[u8; EOCDR_LENGTH]> for EndOfCentralDirectoryHeader {
+    fn from(value: [u8; EOCDR_LENGTH]) -> EndOfCentralDirectoryHeader {
         EndOfCentralDirectoryHeader {
             disk_num: u16::from_le_bytes(value[0..2].try_into().unwrap()),
             start_cent_dir_disk: u16::from_le_bytes(value[2..4].try_into().unwrap()),
@@ -152,9 +228,35 @@ impl From<[u8; 18]> for EndOfCentralDirectoryHeader {
     }
 }
 
+impl From<[u8; ZIP64_EOCDR_LENGTH]> for Zip64EndOfCentralDirectoryRecord {
+    fn from(value: [u8; ZIP64_EOCDR_LENGTH]) -> Zip64EndOfCentralDirectoryRecord {
+        Zip64EndOfCentralDirectoryRecord {
+            size_of_record: u64::from_le_bytes(value[0..8].try_into().unwrap()),
+            v_made_by: u16::from_le_bytes(value[8..10].try_into().unwrap()),
+            v_needed: u16::from_le_bytes(value[10..12].try_into().unwrap()),
+            disk_num: u32::from_le_bytes(value[12..16].try_into().unwrap()),
+            start_cent_dir_disk: u32::from_le_bytes(value[16..20].try_into().unwrap()),
+            num_of_entries_disk: u64::from_le_bytes(value[20..28].try_into().unwrap()),
+            num_of_entries: u64::from_le_bytes(value[28..36].try_into().unwrap()),
+            size_cent_dir: u64::from_le_bytes(value[36..44].try_into().unwrap()),
+            cent_dir_offset: u64::from_le_bytes(value[44..52].try_into().unwrap()),
+        }
+    }
+}
+
+impl From<[u8; ZIP64_EOCDL_LENGTH]> for Zip64EndOfCentralDirectoryLocator {
+    fn from(value: [u8; ZIP64_EOCDL_LENGTH]) -> Zip64EndOfCentralDirectoryLocator {
+        Zip64EndOfCentralDirectoryLocator {
+            zip64_eocdr_disk: u32::from_le_bytes(value[0..4].try_into().unwrap()),
+            zip64_eocdr_offset: u64::from_le_bytes(value[4..12].try_into().unwrap()),
+            total_disks: u32::from_le_bytes(value[12..16].try_into().unwrap()),
+        }
+    }
+}
+
 impl LocalFileHeader {
     pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<LocalFileHeader> {
-        let mut buffer: [u8; 26] = [0; 26];
+        let mut buffer: [u8; LFH_LENGTH] = [0; LFH_LENGTH];
         reader.read_exact(&mut buffer).await?;
         Ok(LocalFileHeader::from(buffer))
     }
@@ -162,7 +264,7 @@ impl LocalFileHeader {
 
 impl EndOfCentralDirectoryHeader {
     pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<EndOfCentralDirectoryHeader> {
-        let mut buffer: [u8; 18] = [0; 18];
+        let mut buffer: [u8; EOCDR_LENGTH] = [0; EOCDR_LENGTH];
         reader.read_exact(&mut buffer).await?;
         Ok(EndOfCentralDirectoryHeader::from(buffer))
     }
@@ -170,12 +272,28 @@ impl EndOfCentralDirectoryHeader {
 
 impl CentralDirectoryRecord {
     pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<CentralDirectoryRecord> {
-        let mut buffer: [u8; 42] = [0; 42];
+        let mut buffer: [u8; CDH_LENGTH] = [0; CDH_LENGTH];
         reader.read_exact(&mut buffer).await?;
         Ok(CentralDirectoryRecord::from(buffer))
     }
 }
 
+impl Zip64EndOfCentralDirectoryRecord {
+    pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Zip64EndOfCentralDirectoryRecord> {
+        let mut buffer: [u8; ZIP64_EOCDR_LENGTH] = [0; ZIP64_EOCDR_LENGTH];
+        reader.read_exact(&mut buffer).await?;
+        Ok(Zip64EndOfCentralDirectoryRecord::from(buffer))
+    }
+}
+
+impl Zip64EndOfCentralDirectoryLocator {
+    pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Zip64EndOfCentralDirectoryLocator> {
+        let mut buffer: [u8; ZIP64_EOCDL_LENGTH] = [0; ZIP64_EOCDL_LENGTH];
+        reader.read_exact(&mut buffer).await?;
+        Ok(Zip64EndOfCentralDirectoryLocator::from(buffer))
+    }
+}
+
 /// Replace elements of an array at a given cursor index for use with a zero-initialised array.
 macro_rules! array_push {
     ($arr:ident, $cursor:ident, $value:expr) => {{
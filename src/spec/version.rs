@@ -2,11 +2,18 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::entry::ZipEntry;
+use crate::spec::attribute::AttributeCompatibility;
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use crate::spec::compression::Compression;
 
 pub(crate) const SPEC_VERSION_MADE_BY: u16 = 63;
 
+/// The minimum version needed to extract an entry which makes use of ZIP64 structures.
+pub(crate) const ZIP64_VERSION_NEEDED: u16 = 45;
+
+/// The minimum version needed to extract an entry which is encrypted using AES.
+pub(crate) const AES_VERSION_NEEDED: u16 = 51;
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#443
 pub fn as_needed_to_extract(entry: &ZipEntry) -> u16 {
     let mut version = match entry.compression() {
@@ -19,15 +26,20 @@ pub fn as_needed_to_extract(entry: &ZipEntry) -> u16 {
         _ => 10,
     };
 
-    if entry.filename().ends_with('/') {
+    if entry.filename().ends_with('/') || entry.encryption.is_some() {
         version = std::cmp::max(version, 20);
     }
 
+    #[cfg(feature = "aes")]
+    if matches!(entry.encryption, Some(crate::spec::encryption::Encryption::Aes256(_))) {
+        version = std::cmp::max(version, AES_VERSION_NEEDED);
+    }
+
     version
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#442
-pub fn as_made_by() -> u16 {
-    // Default to UNIX mapping for the moment.
-    3 << 8 | SPEC_VERSION_MADE_BY
+pub fn as_made_by(compatibility: AttributeCompatibility) -> u16 {
+    // The upper byte defines the host which the external file attribute's mapping is compatible with.
+    u16::from(compatibility) << 8 | SPEC_VERSION_MADE_BY
 }
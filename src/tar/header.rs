@@ -0,0 +1,238 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Parsing & encoding of ustar headers, along with the GNU & pax extensions used for long paths and large sizes.
+
+use crate::error::{Result, ZipError};
+
+/// The length of a tar block (and so of every header), equal to 512 bytes.
+pub(crate) const BLOCK_LENGTH: usize = 512;
+
+pub(crate) const REGULAR_TYPE: u8 = b'0';
+pub(crate) const LEGACY_REGULAR_TYPE: u8 = b'\0';
+pub(crate) const CONTIGUOUS_TYPE: u8 = b'7';
+pub(crate) const SYMLINK_TYPE: u8 = b'2';
+pub(crate) const DIRECTORY_TYPE: u8 = b'5';
+pub(crate) const GNU_LONG_NAME_TYPE: u8 = b'L';
+pub(crate) const GNU_LONG_LINK_TYPE: u8 = b'K';
+pub(crate) const PAX_TYPE: u8 = b'x';
+
+/// The name given to the pseudo-entries which hold GNU long names & link targets.
+const GNU_LONG_LINK_NAME: &str = "././@LongLink";
+
+/// The largest value which fits within a 12-byte octal field (ie. 11 octal digits).
+const MAX_OCTAL_SIZE: u64 = 0o77777777777;
+
+/// A parsed tar header, with any GNU or pax overrides already applied by the caller.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TarHeader {
+    pub(crate) name: String,
+    pub(crate) mode: u32,
+    pub(crate) size: u64,
+    pub(crate) mtime: u64,
+    pub(crate) typeflag: u8,
+    pub(crate) linkname: String,
+}
+
+impl TarHeader {
+    /// Parses a header from a single block, verifying its checksum.
+    pub(crate) fn parse(block: &[u8; BLOCK_LENGTH]) -> Result<TarHeader> {
+        let checksum = parse_number(&block[148..156])?;
+        let computed: u64 =
+            block.iter().enumerate().map(|(i, b)| if (148..156).contains(&i) { 32 } else { *b as u64 }).sum();
+
+        if checksum != computed {
+            return Err(ZipError::InvalidTarHeader("checksum mismatch"));
+        }
+
+        let mut name = parse_string(&block[0..100]);
+
+        // The ustar prefix field extends the name for paths longer than 100 bytes.
+        if &block[257..262] == b"ustar" {
+            let prefix = parse_string(&block[345..500]);
+            if !prefix.is_empty() {
+                name = format!("{prefix}/{name}");
+            }
+        }
+
+        Ok(TarHeader {
+            name,
+            mode: parse_number(&block[100..108])? as u32,
+            size: parse_number(&block[124..136])?,
+            mtime: parse_number(&block[136..148])?,
+            typeflag: block[156],
+            linkname: parse_string(&block[157..257]),
+        })
+    }
+
+    /// Applies the records of a pax extended header to this header.
+    ///
+    /// Unrecognised records are ignored, as are those with malformed values.
+    pub(crate) fn apply_pax(&mut self, data: &[u8]) {
+        let mut remaining = data;
+
+        // Each record is of the form "<length> <key>=<value>\n", where the length includes the whole record.
+        while let Some(space) = remaining.iter().position(|b| *b == b' ') {
+            let Some(length) = std::str::from_utf8(&remaining[..space]).ok().and_then(|l| l.parse::<usize>().ok())
+            else {
+                return;
+            };
+
+            if length <= space + 1 || length > remaining.len() {
+                return;
+            }
+
+            let record = &remaining[space + 1..length];
+            let record = record.strip_suffix(b"\n").unwrap_or(record);
+            remaining = &remaining[length..];
+
+            let Some(equals) = record.iter().position(|b| *b == b'=') else { continue };
+            let value = String::from_utf8_lossy(&record[equals + 1..]);
+
+            match &record[..equals] {
+                b"path" => self.name = value.into_owned(),
+                b"linkpath" => self.linkname = value.into_owned(),
+                b"size" => self.size = value.parse().unwrap_or(self.size),
+                // Times may hold a fractional part, which isn't retained.
+                b"mtime" => self.mtime = value.split('.').next().and_then(|s| s.parse().ok()).unwrap_or(self.mtime),
+                _ => (),
+            }
+        }
+    }
+
+    /// Encodes this header, preceded by any GNU long name & link target pseudo-entries it requires.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(BLOCK_LENGTH);
+        let (prefix, name) = split_name(&self.name);
+
+        if name.is_none() {
+            encoded.extend(long_link_entry(GNU_LONG_NAME_TYPE, &self.name));
+        }
+
+        if self.linkname.len() > 100 {
+            encoded.extend(long_link_entry(GNU_LONG_LINK_TYPE, &self.linkname));
+        }
+
+        let linkname = truncate(&self.linkname, 100);
+        let name = name.unwrap_or_else(|| truncate(&self.name, 100));
+        encoded.extend(encode_block(name, prefix, self.mode, self.size, self.mtime, self.typeflag, linkname));
+        encoded
+    }
+}
+
+/// Returns the number of padding bytes which follow data of the provided size.
+pub(crate) fn padding(size: u64) -> u64 {
+    (BLOCK_LENGTH as u64 - size % BLOCK_LENGTH as u64) % BLOCK_LENGTH as u64
+}
+
+/// Returns a GNU pseudo-entry holding a long name or link target.
+fn long_link_entry(typeflag: u8, value: &str) -> Vec<u8> {
+    let size = value.len() as u64 + 1;
+    let mut encoded = encode_block(GNU_LONG_LINK_NAME, "", 0, size, 0, typeflag, "").to_vec();
+
+    encoded.extend_from_slice(value.as_bytes());
+    encoded.resize(encoded.len() + 1 + padding(size) as usize, 0);
+    encoded
+}
+
+fn encode_block(
+    name: &str,
+    prefix: &str,
+    mode: u32,
+    size: u64,
+    mtime: u64,
+    typeflag: u8,
+    linkname: &str,
+) -> [u8; BLOCK_LENGTH] {
+    let mut block = [0; BLOCK_LENGTH];
+
+    block[0..name.len()].copy_from_slice(name.as_bytes());
+    encode_octal(&mut block[100..108], mode as u64);
+    encode_octal(&mut block[108..116], 0);
+    encode_octal(&mut block[116..124], 0);
+    encode_number(&mut block[124..136], size);
+    encode_number(&mut block[136..148], mtime);
+    block[156] = typeflag;
+    block[157..157 + linkname.len()].copy_from_slice(linkname.as_bytes());
+    block[257..263].copy_from_slice(b"ustar\0");
+    block[263..265].copy_from_slice(b"00");
+    block[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    // The checksum is computed with its own field filled with spaces, and is followed by a NUL and a space.
+    block[148..156].fill(b' ');
+    let checksum: u64 = block.iter().map(|b| *b as u64).sum();
+    encode_octal(&mut block[148..155], checksum);
+    block[155] = b' ';
+
+    block
+}
+
+/// Splits a name into a ustar prefix & name, or returns None for the name if it can't be represented.
+fn split_name(name: &str) -> (&str, Option<&str>) {
+    if name.len() <= 100 {
+        return ("", Some(name));
+    }
+
+    // The split must be at a separator, with the prefix fitting within 155 bytes and the rest within 100.
+    let split =
+        name.char_indices().filter(|(_, c)| *c == '/').map(|(i, _)| i).find(|i| *i <= 155 && name.len() - i - 1 <= 100);
+
+    match split {
+        Some(i) if i > 0 => (&name[..i], Some(&name[i + 1..])),
+        _ => ("", None),
+    }
+}
+
+/// Truncates a string to at most the provided number of bytes, at a character boundary.
+fn truncate(value: &str, max: usize) -> &str {
+    let mut end = std::cmp::min(value.len(), max);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+fn parse_string(field: &[u8]) -> String {
+    let end = field.iter().position(|b| *b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// Parses a numeric field, which is either NUL/space-terminated octal or (as a GNU extension) big-endian base-256.
+pub(crate) fn parse_number(field: &[u8]) -> Result<u64> {
+    if field.first().is_some_and(|b| b & 0x80 != 0) {
+        return field[1..]
+            .iter()
+            .try_fold(0u64, |value, b| value.checked_mul(256).map(|value| value | *b as u64))
+            .ok_or(ZipError::InvalidTarHeader("numeric field overflowed"));
+    }
+
+    let digits = field.iter().skip_while(|b| **b == b' ').take_while(|b| (b'0'..=b'7').contains(*b));
+    let mut value = 0u64;
+
+    for digit in digits {
+        value = value.checked_mul(8).ok_or(ZipError::InvalidTarHeader("numeric field overflowed"))?;
+        value += (digit - b'0') as u64;
+    }
+
+    Ok(value)
+}
+
+/// Encodes a value as NUL-terminated octal within the provided field (which must be large enough).
+fn encode_octal(field: &mut [u8], value: u64) {
+    let digits = field.len() - 1;
+    let encoded = format!("{value:0digits$o}");
+    field[..digits].copy_from_slice(&encoded.as_bytes()[encoded.len() - digits..]);
+    field[digits] = 0;
+}
+
+/// Encodes a 12-byte numeric field, falling back to base-256 for values too large to be held in octal.
+fn encode_number(field: &mut [u8], value: u64) {
+    if value <= MAX_OCTAL_SIZE {
+        encode_octal(field, value);
+    } else {
+        field.fill(0);
+        field[0] = 0x80;
+        let start = field.len() - 8;
+        field[start..].copy_from_slice(&value.to_be_bytes());
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Streaming conversions between tar archives and ZIP files.
+//!
+//! Entry data is piped between the two formats without being held in memory in full. Entry metadata is mapped on a
+//! best-effort basis: paths, Unix permissions, modification times, directories, and symbolic links are carried over,
+//! whilst entry types without a ZIP equivalent (eg. hard links or device files) are skipped. Both ustar archives and
+//! the GNU & pax extensions for long paths and large sizes are supported. Note that this requires the `tar` feature.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::seek::ZipFileReader;
+//! # use async_zip::write::ZipFileWriter;
+//! # use async_zip::error::Result;
+//! # use tokio::fs::File;
+//! #
+//! async fn run() -> Result<()> {
+//!     let mut writer = ZipFileWriter::new(File::create("./foo.zip").await?);
+//!     async_zip::tar::tar_to_zip(File::open("./foo.tar").await?, &mut writer).await?;
+//!     writer.close().await?;
+//!
+//!     let mut reader = ZipFileReader::new(File::open("./foo.zip").await?).await?;
+//!     async_zip::tar::zip_to_tar(&mut reader, File::create("./bar.tar").await?).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+pub(crate) mod header;
+
+use crate::error::{Result, ZipError};
+use crate::read::seek::ZipFileReader;
+use crate::spec::attribute::AttributeCompatibility;
+use crate::write::ZipFileWriter;
+use header::{TarHeader, BLOCK_LENGTH};
+
+use std::io::ErrorKind;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncWrite, AsyncWriteExt};
+
+/// The permissions given to entries which don't specify any.
+const DEFAULT_FILE_MODE: u32 = 0o644;
+const DEFAULT_DIRECTORY_MODE: u32 = 0o755;
+
+/// The Unix file type bits of regular files (ie. `S_IFREG`) and directories (ie. `S_IFDIR`).
+const UNIX_REGULAR_TYPE: u32 = 0o100000;
+const UNIX_DIRECTORY_TYPE: u32 = 0o040000;
+
+/// The max length of the data held by a GNU long name or pax extended header, equal to 1MiB.
+const MAX_EXTENSION_LENGTH: u64 = 1024 * 1024;
+
+/// Converts a tar archive into entries written to the provided ZIP file writer.
+///
+/// Entries are written using the writer's default compression method (see [`ZipFileWriter::entry_builder()`]), and
+/// the writer isn't closed so that further entries (or a comment) may be added. Returns the number of entries written.
+///
+/// A tar archive which ends without its end-of-archive marker is accepted, but one which ends mid-entry isn't.
+pub async fn tar_to_zip<R, W>(mut reader: R, writer: &mut ZipFileWriter<W>) -> Result<u64>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut written = 0;
+    let mut overrides = TarHeader::default();
+
+    while let Some(mut header) = next_header(&mut reader).await? {
+        match header.typeflag {
+            header::GNU_LONG_NAME_TYPE => {
+                overrides.name = trim_nul(read_extension(&mut reader, &header).await?);
+                continue;
+            }
+            header::GNU_LONG_LINK_TYPE => {
+                overrides.linkname = trim_nul(read_extension(&mut reader, &header).await?);
+                continue;
+            }
+            header::PAX_TYPE => {
+                let data = read_extension(&mut reader, &header).await?;
+                overrides.apply_pax(&data);
+                continue;
+            }
+            _ => (),
+        }
+
+        let overrides = std::mem::take(&mut overrides);
+        if !overrides.name.is_empty() {
+            header.name = overrides.name;
+        }
+        if !overrides.linkname.is_empty() {
+            header.linkname = overrides.linkname;
+        }
+        if overrides.size != 0 {
+            header.size = overrides.size;
+        }
+        if overrides.mtime != 0 {
+            header.mtime = overrides.mtime;
+        }
+
+        // The tar mode only holds permission bits, whilst a ZIP's Unix mode also holds the file type (which symbolic
+        // links have set when they're written).
+        let file_type = match header.typeflag {
+            header::DIRECTORY_TYPE => UNIX_DIRECTORY_TYPE,
+            header::SYMLINK_TYPE => 0,
+            _ => UNIX_REGULAR_TYPE,
+        };
+
+        let modified = UNIX_EPOCH
+            .checked_add(Duration::from_secs(header.mtime))
+            .ok_or(ZipError::InvalidTarHeader("modification time out of range"))?;
+        let builder = writer
+            .entry_builder(std::mem::take(&mut header.name))
+            .attribute_compatibility(AttributeCompatibility::Unix)
+            .unix_permissions(file_type | header.mode & 0o7777)
+            .last_modification_date(modified);
+
+        let mut remaining = header.size;
+        let converted = match header.typeflag {
+            header::DIRECTORY_TYPE => writer.write_dir_entry(builder).await.map(|_| true)?,
+            header::SYMLINK_TYPE => writer.write_symlink_entry(builder, &header.linkname).await.map(|_| true)?,
+            header::REGULAR_TYPE | header::LEGACY_REGULAR_TYPE | header::CONTIGUOUS_TYPE => {
+                remaining -= writer.write_entry_from_reader(builder, (&mut reader).take(header.size)).await?;
+                if remaining != 0 {
+                    return Err(ZipError::UpstreamReadError(ErrorKind::UnexpectedEof.into()));
+                }
+                true
+            }
+            // Entry types without a ZIP equivalent are skipped.
+            _ => false,
+        };
+
+        // Any data which wasn't converted is skipped, along with the padding which follows it.
+        skip(&mut reader, remaining + header::padding(header.size)).await?;
+        written += u64::from(converted);
+    }
+
+    Ok(written)
+}
+
+/// Converts every entry of a ZIP file into a tar archive written to the provided writer.
+///
+/// Each entry's data is decompressed and its CRC32 value verified as it's written. The end-of-archive marker is written
+/// and the writer flushed once all entries have been converted, and the writer is then returned.
+pub async fn zip_to_tar<R, W>(reader: &mut ZipFileReader<R>, mut writer: W) -> Result<W>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    for index in 0..reader.file().entries().len() {
+        let entry = reader.file().entries()[index].entry().clone();
+
+        let mut header = TarHeader {
+            name: entry.filename().to_string(),
+            mode: entry.unix_permissions().map(|mode| mode as u32 & 0o7777).unwrap_or(DEFAULT_FILE_MODE),
+            size: 0,
+            mtime: unix_seconds(entry.unix_modification_time, entry.last_modification_date().as_system_time()),
+            typeflag: header::REGULAR_TYPE,
+            linkname: String::new(),
+        };
+
+        if entry.dir() {
+            header.typeflag = header::DIRECTORY_TYPE;
+            if entry.unix_permissions().is_none() {
+                header.mode = DEFAULT_DIRECTORY_MODE;
+            }
+
+            writer.write_all(&header.encode()).await?;
+            continue;
+        }
+
+        let mut entry_reader = reader.entry(index).await?;
+
+        if entry.symlink() {
+            header.typeflag = header::SYMLINK_TYPE;
+            entry_reader.read_to_string_checked(&mut header.linkname, &entry).await?;
+
+            writer.write_all(&header.encode()).await?;
+            continue;
+        }
+
+        header.size = entry.uncompressed_size();
+        writer.write_all(&header.encode()).await?;
+
+        let copied = entry_reader.copy_to_end_checked(&mut writer, &entry).await?;
+        if copied != header.size {
+            let error = std::io::Error::new(ErrorKind::InvalidData, "entry's data didn't match its uncompressed size");
+            return Err(ZipError::UpstreamReadError(error).in_entry(Some(index), entry.filename()));
+        }
+
+        writer.write_all(&vec![0; header::padding(header.size) as usize]).await?;
+    }
+
+    writer.write_all(&[0; BLOCK_LENGTH * 2]).await?;
+    writer.flush().await?;
+
+    Ok(writer)
+}
+
+/// Reads the next header, returning None at the end of the archive.
+async fn next_header<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<TarHeader>> {
+    let mut block = [0; BLOCK_LENGTH];
+    let mut filled = 0;
+
+    while filled < BLOCK_LENGTH {
+        match reader.read(&mut block[filled..]).await? {
+            0 if filled == 0 => return Ok(None),
+            0 => return Err(ZipError::UpstreamReadError(ErrorKind::UnexpectedEof.into())),
+            read => filled += read,
+        }
+    }
+
+    // The archive ends with two zeroed blocks, though the first is sufficient to mark its end.
+    if block.iter().all(|b| *b == 0) {
+        return Ok(None);
+    }
+
+    TarHeader::parse(&block).map(Some)
+}
+
+/// Reads the data of a GNU long name or pax extended header, along with its padding.
+async fn read_extension<R: AsyncRead + Unpin>(reader: &mut R, header: &TarHeader) -> Result<Vec<u8>> {
+    if header.size > MAX_EXTENSION_LENGTH {
+        return Err(ZipError::InvalidTarHeader("extended header exceeded 1MiB"));
+    }
+
+    let mut data = vec![0; header.size as usize];
+    reader.read_exact(&mut data).await?;
+    skip(reader, header::padding(header.size)).await?;
+
+    Ok(data)
+}
+
+async fn skip<R: AsyncRead + Unpin>(reader: &mut R, length: u64) -> Result<()> {
+    let skipped = tokio::io::copy(&mut reader.take(length), &mut tokio::io::sink()).await?;
+    if skipped != length {
+        return Err(ZipError::UpstreamReadError(ErrorKind::UnexpectedEof.into()));
+    }
+
+    Ok(())
+}
+
+fn trim_nul(data: Vec<u8>) -> String {
+    let end = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).into_owned()
+}
+
+/// Returns an entry's modification time in seconds since the Unix epoch, preferring its precise time where known.
+fn unix_seconds(precise: Option<i64>, time: SystemTime) -> u64 {
+    match precise {
+        Some(seconds) => u64::try_from(seconds).unwrap_or_default(),
+        None => time.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default(),
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::blocking::{ZipFileReader, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::{Cursor, Read};
+
+#[test]
+fn blocking_round_trip_test() {
+    let mut writer = ZipFileWriter::new(Cursor::new(Vec::new())).expect("failed to create writer");
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored);
+    writer.write_entry_from_reader(builder, &b"bar"[..]).expect("failed to write entry");
+
+    let data = writer.close().expect("failed to close writer").into_inner();
+    let mut reader = ZipFileReader::new(Cursor::new(data)).expect("failed to open reader");
+    assert_eq!(reader.file().entries().len(), 2);
+
+    let mut buffer = String::new();
+    reader.entry(1).unwrap().read_to_string(&mut buffer).expect("failed to read entry");
+    assert_eq!(buffer, "bar");
+
+    let entry = reader.file().entries()[0].entry().clone();
+    let mut output = Vec::new();
+    reader.entry(0).unwrap().copy_to_end_checked(&mut output, &entry).expect("failed to copy entry");
+    assert_eq!(output, b"foo");
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Compile-time checks that the readers, writers, and their futures can be moved across threads (eg. into
+//! `tokio::spawn()` or an axum handler). The futures are only constructed and dropped, never polled.
+
+use crate::error::Result;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntry, ZipEntryBuilder};
+
+use std::future::Future;
+use std::io::Cursor;
+
+use tokio::io::AsyncReadExt;
+
+fn assert_send<F: Future + Send>(_: F) {}
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn reader_send_sync_test() {
+    assert_send_sync::<crate::read::seek::ZipFileReader<Cursor<Vec<u8>>>>();
+    assert_send_sync::<crate::read::mem::ZipFileReader>();
+    assert_send_sync::<crate::read::shared::ZipFileReader<Cursor<Vec<u8>>>>();
+    assert_send_sync::<crate::read::stream::ZipFileReader<crate::read::stream::Ready<Cursor<Vec<u8>>>>>();
+    assert_send_sync::<crate::read::seek::ZipEntryReader<'static, Cursor<Vec<u8>>>>();
+    assert_send_sync::<crate::read::shared::LockedReader<Cursor<Vec<u8>>>>();
+    assert_send_sync::<ZipEntry>();
+    assert_send_sync::<crate::error::ZipError>();
+
+    assert_send(async {
+        let mut reader = crate::read::seek::ZipFileReader::new(Cursor::new(Vec::new())).await?;
+        let entry = reader.file().entries()[0].entry().clone();
+        reader.entry(0).await?.read_to_end_checked(&mut Vec::new(), &entry).await?;
+        Result::Ok(())
+    });
+
+    assert_send(async {
+        let reader = crate::read::mem::ZipFileReader::new(Vec::new()).await?;
+        reader.entry(0).await?.read_to_end(&mut Vec::new()).await?;
+        Result::Ok(())
+    });
+
+    assert_send(async {
+        let reader = crate::read::shared::ZipFileReader::new(Cursor::new(Vec::new())).await?;
+        reader.entry(0).await?.read_to_end(&mut Vec::new()).await?;
+        Result::Ok(())
+    });
+
+    assert_send(async {
+        let mut reader = crate::read::stream::ZipFileReader::new(Cursor::new(Vec::new()));
+        while let Some(mut reading) = reader.next_entry().await? {
+            reading.reader().read_to_end(&mut Vec::new()).await?;
+            reader = reading.done().await?;
+        }
+        Result::Ok(())
+    });
+}
+
+#[test]
+fn writer_send_sync_test() {
+    assert_send_sync::<ZipFileWriter<Vec<u8>>>();
+    assert_send_sync::<crate::write::EntryStreamWriter<'static, Vec<u8>>>();
+
+    assert_send(async {
+        let mut writer = ZipFileWriter::new(Vec::new());
+        let builder = || ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+
+        writer.write_entry_whole(builder(), b"foo").await?;
+        writer.write_entry_from_reader(builder(), &b"foo"[..]).await?;
+        writer.write_entry_stream(builder()).await?.close().await?;
+        writer.close().await
+    });
+}
+
+#[cfg(feature = "fs")]
+#[test]
+fn fs_send_test() {
+    use crate::fs::{ArchiveOptions, ExtractOptions};
+
+    assert_send_sync::<crate::read::fs::ZipFileReader>();
+
+    assert_send(async {
+        let reader = crate::read::fs::ZipFileReader::new("foo.zip").await?;
+        reader.entry(0).await?.read_to_end(&mut Vec::new()).await?;
+        Result::Ok(())
+    });
+
+    assert_send(async {
+        let reader = tokio::fs::File::open("foo.zip").await?;
+        crate::fs::extract_to_dir(reader, "foo", ExtractOptions::new()).await
+    });
+
+    assert_send(async {
+        let open = || tokio::fs::File::open("foo.zip");
+        crate::fs::extract_to_dir_concurrent(open, "foo", ExtractOptions::new()).await
+    });
+
+    assert_send(async {
+        let mut writer = ZipFileWriter::new(tokio::fs::File::create("foo.zip").await?);
+        crate::fs::write_dir_to_archive("foo", &mut writer, ArchiveOptions::new()).await?;
+        writer.close().await
+    });
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::digest::ContentDigest;
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+struct Length(usize);
+
+impl ContentDigest for Length {
+    fn update(&mut self, data: &[u8]) {
+        self.0 += data.len();
+    }
+}
+
+#[tokio::test]
+async fn digested_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+
+    let mut entry_writer = writer.write_entry_stream(builder).await.unwrap().digested(Length(0));
+    entry_writer.write_all(b"foo ").await.unwrap();
+    entry_writer.write_all(b"bar").await.unwrap();
+    assert_eq!(entry_writer.close().await.expect("failed to close entry").0, 7);
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.expect("failed to open ZIP file");
+
+    let (mut read, mut length) = (Vec::new(), 0);
+    let digest = (|data: &[u8]| read.extend_from_slice(data), |data: &[u8]| length += data.len());
+    let mut entry_reader = reader.entry(0).await.unwrap().digested(digest);
+
+    entry_reader.read_to_end(&mut Vec::new()).await.expect("failed to read entry");
+    drop(entry_reader);
+    assert_eq!((read, length), (b"foo bar".to_vec(), 7));
+}
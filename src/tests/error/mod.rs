@@ -0,0 +1,41 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::seek::ZipFileReader;
+use crate::tests::read::stream::stored_test_zip;
+
+use std::error::Error;
+use std::io::{Cursor, ErrorKind};
+
+#[tokio::test]
+async fn io_error_source_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    let file = ZipFileReader::new(Cursor::new(&data)).await.expect("failed to open ZIP file").file().clone();
+    let offset = file.entries()[1].header_offset() as usize;
+
+    // Truncate the archive partway through the second entry's local file header.
+    let mut reader = ZipFileReader::from_parts(Cursor::new(&data[..offset + 8]), file);
+    let error = reader.entry(1).await.err().expect("truncated header wasn't detected");
+    assert_eq!(error.io_error_kind(), Some(ErrorKind::UnexpectedEof));
+
+    // The IO error should be reachable by walking the chain of sources.
+    let mut source = error.source();
+    while let Some(inner) = source.filter(|inner| !inner.is::<std::io::Error>()) {
+        source = inner.source();
+    }
+    assert_eq!(
+        source.and_then(|inner| inner.downcast_ref::<std::io::Error>()).map(|io| io.kind()),
+        Some(ErrorKind::UnexpectedEof)
+    );
+
+    // Converting into an IO error (and back again) should neither lose the kind nor nest the ZIP error.
+    let io_error = std::io::Error::from(error);
+    assert_eq!(io_error.kind(), ErrorKind::UnexpectedEof);
+    let error = ZipError::from(io_error);
+    assert_eq!(error.entry_index(), Some(1));
+
+    let io_error = std::io::Error::from(ZipError::CRC32CheckError);
+    assert_eq!(io_error.kind(), ErrorKind::InvalidData);
+    assert!(matches!(ZipError::from(io_error), ZipError::CRC32CheckError));
+}
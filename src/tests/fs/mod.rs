@@ -0,0 +1,499 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::path::PathBuf;
+
+/// Returns a fresh directory under the system's temporary directory for a single test to use.
+pub(crate) fn test_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("async_zip_test_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).expect("failed to create test directory");
+    path
+}
+
+/// Writes a small ZIP file containing a directory, a nested file, and a traversal attempt to the provided path.
+pub(crate) async fn write_test_zip(path: &std::path::Path) {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entries: [(&str, &[u8]); 4] =
+        [("foo/", b""), ("foo/bar.txt", b"foo bar"), ("baz.txt", b"baz"), ("../escape.txt", b"escape")];
+
+    for (filename, data) in entries {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+
+    let data = writer.close().await.expect("failed to close writer");
+    tokio::fs::write(path, data).await.expect("failed to write ZIP file");
+}
+
+#[tokio::test]
+async fn extract_all_concurrent_test() {
+    let dir = test_dir("extract_all_concurrent");
+    let zip_path = dir.join("test.zip");
+    write_test_zip(&zip_path).await;
+
+    let dest = dir.join("out");
+    let result = crate::fs::extract_all_concurrent(|| tokio::fs::File::open(&zip_path), &dest, 2).await;
+    assert!(matches!(result, Err(crate::error::ZipError::UnsafeEntryPath(path)) if path == "../escape.txt"));
+    assert!(!dest.exists());
+    assert!(!dir.join("escape.txt").exists());
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entries: [(&str, &[u8]); 4] = [("./", b""), ("foo/", b""), ("foo/bar.txt", b"foo bar"), ("baz.txt", b"baz")];
+    for (filename, data) in entries {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+    tokio::fs::write(&zip_path, writer.close().await.unwrap()).await.unwrap();
+
+    crate::fs::extract_all_concurrent(|| tokio::fs::File::open(&zip_path), &dest, 2).await.expect("extraction failed");
+    assert_eq!(std::fs::read(dest.join("foo/bar.txt")).unwrap(), b"foo bar");
+    assert_eq!(std::fs::read(dest.join("baz.txt")).unwrap(), b"baz");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn extract_to_dir_test() {
+    let dir = test_dir("extract_to_dir");
+    let zip_path = dir.join("test.zip");
+    write_test_zip(&zip_path).await;
+
+    let dest = dir.join("out");
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let result = crate::fs::extract_to_dir(file, &dest, crate::fs::ExtractOptions::new()).await;
+    assert!(matches!(result, Err(crate::error::ZipError::UnsafeEntryPath(path)) if path == "../escape.txt"));
+    assert!(!dest.exists());
+
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let options = crate::fs::ExtractOptions::new().skip_unsafe_paths(true);
+    crate::fs::extract_to_dir(file, &dest, options).await.expect("extraction failed");
+
+    assert_eq!(std::fs::read(dest.join("foo/bar.txt")).unwrap(), b"foo bar");
+    assert_eq!(std::fs::read(dest.join("baz.txt")).unwrap(), b"baz");
+    assert!(!dest.join("escape.txt").exists());
+    assert!(!dir.join("escape.txt").exists());
+
+    for filename in ["/etc/passwd", "C:\\Windows\\foo", "c:foo", "foo/../../bar"] {
+        assert!(crate::utils::safe_path(filename).is_err(), "{filename} should be rejected");
+    }
+    assert_eq!(crate::utils::safe_path("foo/./bar/../baz.txt").unwrap(), PathBuf::from("foo/baz.txt"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn extract_to_dir_metadata_test() {
+    use crate::AttributeCompatibility;
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let dir = test_dir("extract_to_dir_metadata");
+    let modified = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("bin/run.sh".to_string(), Compression::Stored)
+        .attribute_compatibility(AttributeCompatibility::Unix)
+        .unix_permissions(0o100750)
+        .last_modification_time(modified)
+        .extended_timestamp(true);
+    writer.write_entry_whole(builder, b"#!/bin/sh").await.unwrap();
+    writer
+        .write_symlink_entry(ZipEntryBuilder::new("run".to_string(), Compression::Stored), "bin/run.sh")
+        .await
+        .unwrap();
+    writer.write_symlink_entry(ZipEntryBuilder::new("bin/up".to_string(), Compression::Stored), "../..").await.unwrap();
+
+    let zip_path = dir.join("test.zip");
+    tokio::fs::write(&zip_path, writer.close().await.unwrap()).await.unwrap();
+
+    let dest = dir.join("out");
+    let options = crate::fs::ExtractOptions::new().preserve_permissions(true).preserve_modification_times(true);
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let result = crate::fs::extract_to_dir(file, &dest, options.clone().symlinks(true)).await;
+    assert!(matches!(result, Err(crate::error::ZipError::UnsafeEntryPath(target)) if target == "../.."));
+
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let options = options.symlinks(true).skip_unsafe_paths(true);
+    crate::fs::extract_to_dir(file, &dest, options).await.expect("extraction failed");
+
+    let metadata = std::fs::metadata(dest.join("bin/run.sh")).unwrap();
+    assert_eq!(metadata.permissions().mode() & 0o7777, 0o750);
+    assert_eq!(metadata.modified().unwrap(), modified);
+    assert_eq!(std::fs::read_link(dest.join("run")).unwrap(), PathBuf::from("bin/run.sh"));
+    assert!(!dest.join("bin/up").exists());
+
+    let date = crate::ZipDateTime::from_unix_timestamp(1_600_000_000);
+    assert_eq!(date.as_unix_timestamp(), 1_600_000_000);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn chained_symlink_test() {
+    let dir = test_dir("chained_symlink");
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    writer.write_symlink_entry(ZipEntryBuilder::new("a/b".to_string(), Compression::Stored), "..").await.unwrap();
+    writer.write_symlink_entry(ZipEntryBuilder::new("a/b/c".to_string(), Compression::Stored), "..").await.unwrap();
+    let builder = ZipEntryBuilder::new("a/b/c/evil.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"evil").await.unwrap();
+
+    let zip_path = dir.join("test.zip");
+    tokio::fs::write(&zip_path, writer.close().await.unwrap()).await.unwrap();
+
+    let dest = dir.join("out");
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let result = crate::fs::extract_to_dir(file, &dest, crate::fs::ExtractOptions::new().symlinks(true)).await;
+    assert!(matches!(result, Err(crate::error::ZipError::UnsafeEntryPath(path)) if path == "a/b/c"));
+    assert!(!dir.join("evil.txt").exists());
+
+    // Symlinks which already exist within the destination mustn't be followed either.
+    std::fs::create_dir_all(dest.join("a")).unwrap();
+    std::os::unix::fs::symlink("..", dest.join("a/b")).unwrap();
+
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let options = crate::fs::ExtractOptions::new().symlinks(true).skip_unsafe_paths(true);
+    crate::fs::extract_to_dir(file, &dest, options).await.expect("extraction failed");
+    assert!(!dir.join("evil.txt").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn write_dir_to_archive_test() {
+    let dir = test_dir("write_dir_to_archive");
+    let src = dir.join("src");
+    std::fs::create_dir_all(src.join("foo/empty")).unwrap();
+    std::fs::write(src.join("foo/bar.txt"), b"foo bar").unwrap();
+    std::fs::write(src.join("baz.txt"), b"baz").unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    crate::fs::write_dir_to_archive(&src, &mut writer, crate::fs::ArchiveOptions::new()).await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = crate::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let names: Vec<_> = reader.file().entries().iter().map(|entry| entry.entry().filename()).collect();
+    assert_eq!(names, ["baz.txt", "foo/", "foo/bar.txt", "foo/empty/"]);
+
+    let dest = dir.join("out");
+    let options = crate::fs::ExtractOptions::new();
+    crate::fs::extract_to_dir(std::io::Cursor::new(data), &dest, options).await.expect("extraction failed");
+
+    assert_eq!(std::fs::read(dest.join("foo/bar.txt")).unwrap(), b"foo bar");
+    assert_eq!(std::fs::read(dest.join("baz.txt")).unwrap(), b"baz");
+    assert!(dest.join("foo/empty").is_dir());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn extract_to_dir_progress_test() {
+    use crate::fs::ProgressEvent;
+
+    let dir = test_dir("extract_to_dir_progress");
+    let zip_path = dir.join("test.zip");
+    write_test_zip(&zip_path).await;
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let options = crate::fs::ExtractOptions::new().skip_unsafe_paths(true).progress(sender);
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    crate::fs::extract_to_dir(file, dir.join("out"), options).await.expect("extraction failed");
+
+    let mut events = Vec::new();
+    while let Some(event) = receiver.recv().await {
+        events.push(event);
+    }
+
+    assert_eq!(events.first(), Some(&ProgressEvent::Started { entries: 3, bytes: 10 }));
+    assert_eq!(events.last(), Some(&ProgressEvent::Finished { entries: 3, bytes: 10 }));
+
+    let started = events.iter().filter(|event| matches!(event, ProgressEvent::EntryStarted { .. })).count();
+    let finished = events.iter().filter(|event| matches!(event, ProgressEvent::EntryFinished { .. })).count();
+    assert_eq!((started, finished), (3, 3));
+
+    let processed: u64 = events
+        .iter()
+        .filter_map(|event| match event {
+            ProgressEvent::BytesProcessed { bytes } => Some(bytes),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(processed, 10);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn cancellation_test() {
+    let dir = test_dir("cancellation");
+    let zip_path = dir.join("test.zip");
+    write_test_zip(&zip_path).await;
+
+    let token = tokio_util::sync::CancellationToken::new();
+    token.cancel();
+
+    let options = crate::fs::ExtractOptions::new().skip_unsafe_paths(true).cancellation_token(token.clone());
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let result = crate::fs::extract_to_dir(file, dir.join("out"), options).await;
+    assert!(matches!(result, Err(crate::error::ZipError::Cancelled)));
+    assert!(!dir.join("out").exists());
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let options = crate::fs::ArchiveOptions::new().cancellation_token(token);
+    let result = crate::fs::write_dir_to_archive(&dir, &mut writer, options).await;
+    assert!(matches!(result, Err(crate::error::ZipError::Cancelled)));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn extract_to_dir_concurrent_test() {
+    let dir = test_dir("extract_to_dir_concurrent");
+    let zip_path = dir.join("test.zip");
+    write_test_zip(&zip_path).await;
+
+    // A budget smaller than any single buffer still allows entries to be extracted one at a time.
+    let dest = dir.join("out");
+    let options = crate::fs::ExtractOptions::new().skip_unsafe_paths(true).concurrency(4).memory_budget(1);
+    crate::fs::extract_to_dir_concurrent(|| tokio::fs::File::open(&zip_path), &dest, options)
+        .await
+        .expect("extraction failed");
+
+    assert_eq!(std::fs::read(dest.join("foo/bar.txt")).unwrap(), b"foo bar");
+    assert_eq!(std::fs::read(dest.join("baz.txt")).unwrap(), b"baz");
+    assert!(!dest.join("escape.txt").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn extract_to_dir_concurrent_duplicates_test() {
+    use crate::fs::{ExtractOptions, OverwritePolicy};
+
+    let dir = test_dir("extract_to_dir_concurrent_duplicates");
+    let zip_path = dir.join("test.zip");
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for data in [&b"first"[..], &[b'a'; 256 * 1024], b"last"] {
+        let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+
+    tokio::fs::write(&zip_path, writer.close().await.expect("failed to close writer")).await.unwrap();
+
+    let extract = |dest: PathBuf, policy: OverwritePolicy| {
+        let options = ExtractOptions::new().concurrency(4).overwrite_policy(policy);
+        crate::fs::extract_to_dir_concurrent(|| tokio::fs::File::open(&zip_path), dest, options)
+    };
+
+    let dest = dir.join("overwrite");
+    extract(dest.clone(), OverwritePolicy::Overwrite).await.expect("extraction failed");
+    assert_eq!(std::fs::read(dest.join("foo.txt")).unwrap(), b"last");
+
+    let dest = dir.join("rename");
+    extract(dest.clone(), OverwritePolicy::RenameWithSuffix).await.expect("extraction failed");
+    assert_eq!(std::fs::read(dest.join("foo.txt")).unwrap(), b"first");
+    assert_eq!(std::fs::read(dest.join("foo (1).txt")).unwrap(), [b'a'; 256 * 1024]);
+    assert_eq!(std::fs::read(dest.join("foo (2).txt")).unwrap(), b"last");
+
+    let result = extract(dir.join("error"), OverwritePolicy::Error).await;
+    assert!(matches!(result, Err(crate::error::ZipError::EntryPathExists(filename)) if filename == "foo.txt"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn prefetch_test() {
+    let dir = test_dir("prefetch");
+    let zip_path = dir.join("test.zip");
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    // The middle file exceeds half of the memory budget, so it's read directly rather than being prefetched.
+    let entries: [(&str, &[u8]); 4] =
+        [("foo/", b""), ("foo/bar.txt", b"foo bar"), ("large.txt", &[b'a'; 64]), ("baz.txt", b"baz")];
+
+    for (filename, data) in entries {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+
+    tokio::fs::write(&zip_path, writer.close().await.expect("failed to close writer")).await.unwrap();
+
+    let dest = dir.join("out");
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let options = crate::fs::ExtractOptions::new().prefetch(true).memory_budget(32);
+    crate::fs::extract_to_dir(file, &dest, options).await.expect("extraction failed");
+
+    assert_eq!(std::fs::read(dest.join("foo/bar.txt")).unwrap(), b"foo bar");
+    assert_eq!(std::fs::read(dest.join("large.txt")).unwrap(), [b'a'; 64]);
+    assert_eq!(std::fs::read(dest.join("baz.txt")).unwrap(), b"baz");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn filter_test() {
+    let dir = test_dir("filter");
+    let src = dir.join("src");
+    std::fs::create_dir_all(src.join("node_modules/pkg")).unwrap();
+    std::fs::create_dir_all(src.join("data/nested")).unwrap();
+    std::fs::write(src.join("node_modules/pkg/index.json"), b"{}").unwrap();
+    std::fs::write(src.join("data/nested/a.json"), b"[]").unwrap();
+    std::fs::write(src.join("data/b.txt"), b"b").unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let options = crate::fs::ArchiveOptions::new().exclude("node_modules/".to_string());
+    crate::fs::write_dir_to_archive(&src, &mut writer, options).await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = crate::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let names: Vec<_> = reader.file().entries().iter().map(|entry| entry.entry().filename()).collect();
+    assert_eq!(names, ["data/", "data/b.txt", "data/nested/", "data/nested/a.json"]);
+
+    let dest = dir.join("out");
+    let options = crate::fs::ExtractOptions::new().include("data/**/*.json".to_string());
+    crate::fs::extract_to_dir(std::io::Cursor::new(data), &dest, options).await.unwrap();
+
+    assert!(dest.join("data/nested/a.json").exists());
+    assert!(!dest.join("data/b.txt").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn macos_metadata_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for filename in ["foo.txt", "__MACOSX/", "__MACOSX/._foo.txt", "bar/.DS_Store", "bar/._baz.txt"] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+    }
+
+    let data = writer.close().await.expect("failed to close writer");
+    let reader = crate::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let metadata: Vec<_> = reader.file().entries().iter().map(|entry| entry.entry().macos_metadata()).collect();
+    assert_eq!(metadata, [false, true, true, true, true]);
+
+    let dir = test_dir("macos_metadata");
+    let options = crate::fs::ExtractOptions::new().skip_macos_metadata(true);
+    crate::fs::extract_to_dir(std::io::Cursor::new(data), &dir, options).await.unwrap();
+
+    let mut extracted: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    extracted.sort();
+    assert_eq!(extracted, ["foo.txt"]);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn overwrite_policy_test() {
+    use crate::fs::{ExtractOptions, OverwritePolicy};
+
+    let dir = test_dir("overwrite_policy");
+    let zip_path = dir.join("test.zip");
+    write_test_zip(&zip_path).await;
+
+    let dest = dir.join("out");
+    std::fs::create_dir_all(&dest).unwrap();
+
+    for (policy, expected) in [
+        (OverwritePolicy::Skip, &b"old"[..]),
+        (OverwritePolicy::OverwriteIfNewer, b"old"),
+        (OverwritePolicy::Overwrite, b"baz"),
+    ] {
+        std::fs::write(dest.join("baz.txt"), b"old").unwrap();
+
+        let options = ExtractOptions::new().skip_unsafe_paths(true).overwrite_policy(policy);
+        let file = tokio::fs::File::open(&zip_path).await.unwrap();
+        crate::fs::extract_to_dir(file, &dest, options).await.expect("extraction failed");
+        assert_eq!(std::fs::read(dest.join("baz.txt")).unwrap(), expected, "{policy:?}");
+    }
+
+    let options = ExtractOptions::new().skip_unsafe_paths(true).overwrite_policy(OverwritePolicy::RenameWithSuffix);
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    crate::fs::extract_to_dir(file, &dest, options).await.expect("extraction failed");
+    assert_eq!(std::fs::read(dest.join("baz (1).txt")).unwrap(), b"baz");
+    assert_eq!(std::fs::read(dest.join("foo/bar (1).txt")).unwrap(), b"foo bar");
+
+    let options = ExtractOptions::new().skip_unsafe_paths(true).overwrite_policy(OverwritePolicy::Error);
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let result = crate::fs::extract_to_dir(file, &dest, options).await;
+    assert!(matches!(result, Err(crate::error::ZipError::EntryPathExists(path)) if path == "foo/bar.txt"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn plan_extraction_test() {
+    use crate::fs::{ExtractOptions, OverwritePolicy, PlannedAction};
+
+    let dir = test_dir("plan_extraction");
+    let zip_path = dir.join("test.zip");
+    write_test_zip(&zip_path).await;
+
+    let dest = dir.join("out");
+    std::fs::create_dir_all(dest.join("foo")).unwrap();
+    std::fs::write(dest.join("baz.txt"), b"old").unwrap();
+
+    let options = ExtractOptions::new().overwrite_policy(OverwritePolicy::RenameWithSuffix);
+    let file = tokio::fs::File::open(&zip_path).await.unwrap();
+    let plan = crate::fs::plan_extraction(file, &dest, options).await.expect("planning failed");
+
+    let actions: Vec<_> = plan.entries().iter().map(|entry| (entry.filename(), entry.action().clone())).collect();
+    assert_eq!(
+        actions,
+        [
+            ("foo/", PlannedAction::Create),
+            ("foo/bar.txt", PlannedAction::Create),
+            ("baz.txt", PlannedAction::Rename(dest.join("baz (1).txt"))),
+        ]
+    );
+
+    let conflicts: Vec<_> = plan.conflicts().map(|entry| entry.path().to_path_buf()).collect();
+    assert_eq!(conflicts, [dest.join("baz.txt")]);
+    assert_eq!(plan.unsafe_entries(), ["../escape.txt"]);
+    assert_eq!(plan.total_size(), 10);
+
+    // Nothing should have been written.
+    assert_eq!(std::fs::read(dest.join("baz.txt")).unwrap(), b"old");
+    assert!(!dest.join("foo/bar.txt").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(all(feature = "uring", target_os = "linux"))]
+#[test]
+fn uring_extract_to_dir_test() {
+    use crate::fs::uring::{UringFile, ZipFileReader};
+    use tokio::io::AsyncReadExt;
+
+    tokio_uring::start(async {
+        let dir = test_dir("uring_extract_to_dir");
+        let zip_path = dir.join("test.zip");
+
+        let mut writer = ZipFileWriter::new(UringFile::create(&zip_path).await.unwrap());
+        for (filename, data) in [("foo/", &b""[..]), ("foo/bar.txt", b"foo bar"), ("baz.txt", b"baz")] {
+            let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+            writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+        }
+        writer.close().await.expect("failed to close writer");
+
+        let reader = ZipFileReader::new(&zip_path).await.expect("failed to open reader");
+        let mut buffer = String::new();
+        reader.entry(2).await.unwrap().read_to_string(&mut buffer).await.expect("failed to read entry");
+        assert_eq!(buffer, "baz");
+
+        let dest = dir.join("out");
+        crate::fs::uring::extract_to_dir(&zip_path, &dest).await.expect("extraction failed");
+        assert_eq!(std::fs::read(dest.join("foo/bar.txt")).unwrap(), b"foo bar");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    });
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::futures_fs::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures::io::AsyncReadExt;
+
+// Driven by a plain executor rather than tokio, to check the helpers don't depend on a tokio runtime.
+#[test]
+fn futures_fs_extract_to_dir_test() {
+    futures::executor::block_on(async {
+        let dir = std::env::temp_dir().join(format!("async_zip_futures_fs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut writer = ZipFileWriter::new(Vec::new());
+        for (filename, data) in [("foo/", &b""[..]), ("foo/bar.txt", b"foo bar"), ("baz.txt", b"baz")] {
+            let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+            writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+        }
+
+        let zip_path = dir.join("test.zip");
+        std::fs::write(&zip_path, writer.close().await.expect("failed to close writer")).unwrap();
+
+        let reader = ZipFileReader::new(&zip_path).await.expect("failed to open reader");
+        let mut buffer = String::new();
+        reader.entry(2).await.unwrap().read_to_string(&mut buffer).await.expect("failed to read entry");
+        assert_eq!(buffer, "baz");
+
+        let dest = dir.join("out");
+        crate::futures_fs::extract_to_dir(&zip_path, &dest).await.expect("extraction failed");
+        assert_eq!(std::fs::read(dest.join("foo/bar.txt")).unwrap(), b"foo bar");
+        assert_eq!(std::fs::read(dest.join("baz.txt")).unwrap(), b"baz");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    });
+}
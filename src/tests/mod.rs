@@ -1,7 +1,20 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "blocking")]
+pub(crate) mod blocking;
+pub(crate) mod bounds;
 pub(crate) mod combined;
+pub(crate) mod digest;
+pub(crate) mod error;
+#[cfg(feature = "fs")]
+pub(crate) mod fs;
+#[cfg(feature = "futures-fs")]
+pub(crate) mod futures_fs;
 pub(crate) mod read;
 pub(crate) mod spec;
+#[cfg(feature = "tar")]
+pub(crate) mod tar;
+#[cfg(feature = "time")]
+pub(crate) mod throttle;
 pub(crate) mod write;
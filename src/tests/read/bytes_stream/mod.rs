@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::seek::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures::StreamExt;
+use std::io::Cursor;
+
+#[tokio::test]
+async fn entry_bytes_stream_test() {
+    let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new("foo.bin".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, &data).await.expect("failed to write entry");
+    let archive = writer.close().await.expect("failed to close writer");
+
+    let mut reader = ZipFileReader::new(Cursor::new(archive)).await.expect("failed to open reader");
+    let mut stream = reader.entry(0).await.expect("failed to open entry").into_bytes_stream().chunk_size(64);
+
+    let mut output = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.expect("failed to read chunk");
+        assert!(!chunk.is_empty() && chunk.len() <= 64);
+        output.extend_from_slice(&chunk);
+    }
+
+    assert_eq!(output, data);
+}
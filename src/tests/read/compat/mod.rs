@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::compat::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures::io::{AsyncReadExt, Cursor};
+
+#[tokio::test]
+async fn futures_io_reader_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", b"foo"), ("bar.txt", b"bar")] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+
+    let data = writer.close().await.expect("failed to close writer");
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.expect("failed to open reader");
+
+    // Read the entries out of order to exercise seeking over the futures-io source.
+    let mut buffer = Vec::new();
+    reader.entry(1).await.unwrap().read_to_end(&mut buffer).await.expect("failed to read entry");
+    assert_eq!(buffer, b"bar");
+
+    let entry = reader.file().entries()[0].entry().clone();
+    let mut output = Cursor::new(Vec::new());
+    reader.entry(0).await.unwrap().copy_to_end_checked(&mut output, &entry).await.expect("failed to copy entry");
+    assert_eq!(output.into_inner(), b"foo");
+}
@@ -0,0 +1,20 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::stream::ZipFileReader;
+use crate::tests::read::stream::stored_test_zip;
+
+use futures_util::TryStreamExt;
+use tokio_util::codec::{BytesCodec, FramedRead};
+
+#[tokio::test]
+async fn framed_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo bar"), ("bar.txt", b"bar")]).await;
+    let zip = ZipFileReader::from_byte_stream(FramedRead::with_capacity(&data[..], BytesCodec::new(), 4));
+
+    let mut entry = zip.next_entry().await.expect("failed to read entry").expect("expected an entry");
+    assert_eq!(entry.entry().filename(), "foo.txt");
+
+    let chunks: Vec<_> = entry.reader().reader_stream().try_collect().await.unwrap();
+    assert_eq!(chunks.concat(), b"foo bar");
+}
@@ -0,0 +1,224 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn stored_entry_data_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    let data = writer.close().await.expect("failed to close writer");
+
+    let reader = ZipFileReader::new(data).await.expect("failed to open ZIP file");
+    let entry_data = reader.stored_entry_data(0).await.expect("failed to read entry data");
+
+    assert_eq!(entry_data, b"foo bar");
+    assert!(reader.data().as_ptr_range().contains(&entry_data.as_ptr()));
+    assert!(reader.stored_entry_data(1).await.is_err());
+}
+
+#[tokio::test]
+async fn copy_to_end_checked_test() {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::AsyncWrite;
+
+    /// A writer which fails on every write, as a full disk or closed socket might.
+    struct Failing;
+
+    impl AsyncWrite for Failing {
+        fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, _: &[u8]) -> Poll<std::io::Result<usize>> {
+            Poll::Ready(Err(std::io::ErrorKind::BrokenPipe.into()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entry = reader.file().entries()[0].entry().clone();
+
+    let mut output = Vec::new();
+    let copied = reader.entry(0).await.unwrap().copy_to_end_checked(&mut output, &entry).await;
+    assert_eq!(copied.expect("copy failed"), 7);
+    assert_eq!(output, b"foo bar");
+
+    let copied = reader.entry(0).await.unwrap().copy_to_end_checked(&mut Failing, &entry).await;
+    assert!(matches!(copied, Err(crate::error::ZipError::UpstreamReadError(_))));
+}
+
+#[tokio::test]
+async fn allocation_limit_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entry = reader.file().entries()[0].entry().clone();
+
+    let mut buffer = Vec::new();
+    let mut entry_reader = reader.entry(0).await.expect("failed to open entry");
+    entry_reader.allocation_limit(4);
+
+    match entry_reader.read_to_end_checked(&mut buffer, &entry).await {
+        Err(crate::error::ZipError::AllocationLimitExceeded(4)) => {}
+        result => panic!("expected the allocation limit to be exceeded, got {result:?}"),
+    }
+
+    let mut buffer = Vec::new();
+    let mut entry_reader = reader.entry(0).await.expect("failed to open entry");
+    entry_reader.allocation_limit(7);
+
+    entry_reader.read_to_end_checked(&mut buffer, &entry).await.expect("failed to read entry");
+    assert_eq!(buffer, b"foo bar");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn counters_test() {
+    let data = vec![b'a'; 64 * 1024];
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Deflate);
+    writer.write_entry_whole(builder, &data).await.expect("failed to write entry");
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entry = reader.file().entries()[0].entry().clone();
+
+    let mut entry_reader = reader.entry(0).await.expect("failed to open entry");
+    assert_eq!((entry_reader.crc32(), entry_reader.uncompressed_bytes_read()), (0, 0));
+
+    tokio::io::copy(&mut entry_reader, &mut tokio::io::sink()).await.expect("failed to read entry");
+    assert_eq!(entry_reader.crc32(), entry.crc32());
+    assert_eq!(entry_reader.uncompressed_bytes_read(), data.len() as u64);
+    assert_eq!(entry_reader.compressed_bytes_read(), entry.compressed_size());
+}
+
+#[tokio::test]
+async fn disk_number_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    let (mut data, summary) = writer.close_with_summary().await.expect("failed to close writer");
+
+    // Mimic writers which store sentinel or nonzero disk numbers within single-file archives.
+    let eocdr = data.len() - 22;
+    let cdh = summary.central_directory_offset() as usize;
+    data[eocdr + 4..eocdr + 6].copy_from_slice(&0xFFFFu16.to_le_bytes());
+    data[eocdr + 6..eocdr + 8].copy_from_slice(&1u16.to_le_bytes());
+    data[cdh + 34..cdh + 36].copy_from_slice(&1u16.to_le_bytes());
+
+    let reader = ZipFileReader::new(data.clone()).await.expect("failed to open ZIP file");
+    assert_eq!(reader.file().disk_number(), 0xFFFF);
+    assert_eq!(reader.file().cent_dir_disk_number(), 1);
+    assert_eq!(reader.file().entries()[0].disk_start(), 1);
+    assert_eq!(reader.stored_entry_data(0).await.expect("failed to read entry data"), b"foo bar");
+
+    // Entries spread across multiple disks are still rejected.
+    data[eocdr + 8..eocdr + 10].copy_from_slice(&0u16.to_le_bytes());
+    assert!(ZipFileReader::new(data).await.is_err());
+}
+
+#[cfg(feature = "bytes")]
+#[tokio::test]
+async fn read_entry_bytes_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    let data = writer.close().await.expect("failed to close writer");
+
+    let reader = ZipFileReader::new(data).await.expect("failed to open ZIP file");
+    let bytes = reader.read_entry_bytes(0).await.expect("failed to read entry bytes");
+    let source = reader.data().as_ptr_range();
+    drop(reader);
+
+    // The bytes keep the source alive after the reader has been dropped.
+    assert_eq!(bytes, &b"foo bar"[..]);
+    assert!(source.contains(&bytes.as_ptr()));
+}
+
+#[tokio::test]
+async fn stored_entry_data_oversized_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).force_zip64(true);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    let mut data = writer.close().await.expect("failed to close writer");
+
+    // Overwrite the compressed size held within the central directory's ZIP64 extra field (which follows the
+    // uncompressed size), so that the entry's data would extend far past the end of the source.
+    let cdh = data.windows(4).position(|window| window == [0x50, 0x4b, 0x01, 0x02]).expect("no central directory");
+    assert_eq!(data[cdh + 20..cdh + 28], [0xff; 8]);
+    let filename_length = u16::from_le_bytes([data[cdh + 28], data[cdh + 29]]) as usize;
+    let extra_field = cdh + 46 + filename_length;
+    assert_eq!(data[extra_field..extra_field + 2], [0x01, 0x00]);
+    data[extra_field + 12..extra_field + 20].copy_from_slice(&(u64::MAX - 2).to_le_bytes());
+
+    let reader = ZipFileReader::new(data).await.expect("failed to open ZIP file");
+    let result = reader.stored_entry_data(0).await;
+    assert!(matches!(result, Err(crate::error::ZipError::UpstreamReadError(_))));
+}
+
+#[tokio::test]
+async fn copy_to_end_checked_vectored_test() {
+    use std::io::IoSlice;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::AsyncWrite;
+
+    /// A writer which only accepts vectored writes, and only a limited amount of data at a time.
+    #[derive(Default)]
+    struct Vectored(Vec<u8>);
+
+    impl AsyncWrite for Vectored {
+        fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, _: &[u8]) -> Poll<std::io::Result<usize>> {
+            panic!("vectored writes weren't used")
+        }
+
+        fn poll_write_vectored(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            bufs: &[IoSlice<'_>],
+        ) -> Poll<std::io::Result<usize>> {
+            let mut written = 0;
+            for buf in bufs {
+                let length = buf.len().min(10_000 - written);
+                self.0.extend_from_slice(&buf[..length]);
+                written += length;
+            }
+
+            Poll::Ready(Ok(written))
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            true
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let data: Vec<u8> = (0..200_000u32).map(|index| index as u8).collect();
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(String::from("foo.bin"), Compression::Stored);
+    writer.write_entry_whole(builder, &data).await.expect("failed to write entry");
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entry = reader.file().entries()[0].entry().clone();
+
+    let mut output = Vectored::default();
+    let copied = reader.entry(0).await.unwrap().copy_to_end_checked(&mut output, &entry).await;
+    assert_eq!(copied.expect("copy failed"), data.len() as u64);
+    assert_eq!(output.0, data);
+}
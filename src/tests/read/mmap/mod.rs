@@ -0,0 +1,24 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mmap::ZipFileReader;
+use crate::tests::read::stream::stored_test_zip;
+
+#[tokio::test]
+async fn map_test() {
+    let path = std::env::temp_dir().join(format!("async_zip_test_mmap_{}.zip", std::process::id()));
+    tokio::fs::write(&path, stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"foo bar")]).await).await.unwrap();
+
+    // SAFETY: The file is only written above, before it's mapped, and removed once the reader has been dropped.
+    let reader = unsafe { ZipFileReader::map(&path) }.await.expect("failed to map ZIP file");
+    assert_eq!(reader.file().entries().len(), 2);
+    assert_eq!(reader.stored_entry_data(1).await.unwrap(), b"foo bar");
+
+    let mut buffer = String::new();
+    let entry = reader.file().entries()[0].entry().clone();
+    reader.entry(0).await.unwrap().read_to_string_checked(&mut buffer, &entry).await.unwrap();
+    assert_eq!(buffer, "foo");
+
+    drop(reader);
+    std::fs::remove_file(path).unwrap();
+}
@@ -1,5 +1,19 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "futures")]
+pub(crate) mod bytes_stream;
+#[cfg(feature = "futures")]
+pub(crate) mod compat;
 pub(crate) mod compression;
+#[cfg(feature = "tokio-util")]
+pub(crate) mod framed;
 pub(crate) mod locator;
+pub(crate) mod mem;
+#[cfg(feature = "mmap")]
+pub(crate) mod mmap;
+pub(crate) mod options;
+pub(crate) mod remote;
+pub(crate) mod seek;
+pub(crate) mod shared;
+pub(crate) mod stream;
@@ -0,0 +1,205 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+#[cfg(feature = "time")]
+#[tokio::test]
+async fn open_timeout_test() {
+    use crate::error::ZipError;
+    use crate::read::{seek::ZipFileReader, ReaderOptions};
+
+    use std::io::SeekFrom;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+    /// A source which never makes progress, as a stalled network source might.
+    struct Stalled;
+
+    impl AsyncRead for Stalled {
+        fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, _: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Pending
+        }
+    }
+
+    impl AsyncSeek for Stalled {
+        fn start_seek(self: Pin<&mut Self>, _: SeekFrom) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+            Poll::Pending
+        }
+    }
+
+    let options = ReaderOptions::new().open_timeout(Duration::from_millis(10));
+    let result = ZipFileReader::with_options(Stalled, options).await;
+
+    assert!(matches!(result, Err(ZipError::OpenTimedOut(_))));
+}
+
+#[cfg(feature = "time")]
+#[tokio::test]
+async fn open_timeout_in_memory_test() {
+    use crate::error::ZipError;
+    use crate::read::mem::ZipFileReader;
+    use crate::read::ReaderOptions;
+    use crate::tests::read::stream::stored_test_zip;
+
+    use std::time::Duration;
+
+    let filenames: Vec<String> = (0..1024).map(|index| format!("{index}.txt")).collect();
+    let entries: Vec<(&str, &[u8])> = filenames.iter().map(|filename| (filename.as_str(), &b""[..])).collect();
+    let data = stored_test_zip(&entries).await;
+
+    let options = ReaderOptions::new().open_timeout(Duration::ZERO);
+    let result = ZipFileReader::with_options(data, options).await;
+
+    assert!(matches!(result, Err(ZipError::OpenTimedOut(_))));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn open_timeout_without_runtime_test() {
+    use crate::error::ZipError;
+    use crate::read::{mem::ZipFileReader, ReaderOptions};
+    use crate::tests::read::stream::stored_test_zip;
+
+    use std::time::Duration;
+
+    let data = tokio::runtime::Runtime::new().unwrap().block_on(stored_test_zip(&[("foo.txt", b"foo")]));
+    let options = ReaderOptions::new().open_timeout(Duration::from_secs(5));
+    let result = futures::executor::block_on(ZipFileReader::with_options(data, options));
+
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+}
+
+#[tokio::test]
+async fn lenient_test() {
+    use crate::read::{seek::ZipFileReader, ReaderOptions, ZipWarning};
+    use crate::tests::read::stream::stored_test_zip;
+
+    use std::io::Cursor;
+
+    let mut data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+
+    // Corrupt the CRC32 value within the first entry's local file header.
+    data[14] ^= 0xff;
+
+    // Declare one more entry than the central directory holds (within both the disk and total entry counts).
+    let eocdr = data.windows(4).rposition(|window| window == [0x50, 0x4b, 0x05, 0x06]).expect("no EOCDR");
+    data[eocdr + 8] = 3;
+    data[eocdr + 10] = 3;
+
+    assert!(ZipFileReader::new(Cursor::new(&data)).await.is_err());
+
+    let options = ReaderOptions::new().lenient(true);
+    let reader = ZipFileReader::with_options(Cursor::new(&data), options).await.expect("failed to open ZIP file");
+
+    assert_eq!(reader.file().entries().len(), 2);
+    assert_eq!(
+        reader.file().warnings(),
+        [
+            ZipWarning::TruncatedCentralDirectory { expected: 3, found: 2 },
+            ZipWarning::LocalFileHeaderMismatch { index: 0 }
+        ]
+    );
+
+    // Point the second entry at a local file header signature which is cut short by the end of the file.
+    let mut data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    let truncated = data.len() as u32;
+    data.extend_from_slice(&[0x50, 0x4b, 0x03, 0x04, 0x14, 0x00]);
+    let cdh = data.windows(4).rposition(|window| window == [0x50, 0x4b, 0x01, 0x02]).expect("no CDH");
+    data[cdh + 42..cdh + 46].copy_from_slice(&truncated.to_le_bytes());
+
+    let options = ReaderOptions::new().lenient(true);
+    let reader = ZipFileReader::with_options(Cursor::new(&data), options).await.expect("failed to open ZIP file");
+
+    assert_eq!(reader.file().entries().len(), 2);
+    assert_eq!(
+        reader.file().warnings(),
+        [ZipWarning::DataOverlapsCentralDirectory { index: 1 }, ZipWarning::UnreadableLocalFileHeader { index: 1 }]
+    );
+}
+
+#[tokio::test]
+async fn limits_test() {
+    use crate::error::ZipError;
+    use crate::read::{seek, stream, ReaderOptions};
+    use crate::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for filename in ["foo.txt", "bar.txt"] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    }
+    let data = writer.close().await.expect("failed to close writer");
+
+    let options = ReaderOptions::new().max_entries(1);
+    let result = seek::ZipFileReader::with_options(std::io::Cursor::new(&data), options).await;
+    assert!(matches!(result, Err(ZipError::EntryLimitExceeded(1))));
+
+    // The smallest buffer forces every read to go back to the source.
+    let options = ReaderOptions::new().max_entries(2).buffer_size(1);
+    let mut reader = seek::ZipFileReader::with_options(std::io::Cursor::new(&data), options.clone()).await.unwrap();
+    assert_eq!(reader.entries(&[0, 1]).await.expect("failed to read entries"), [b"foo bar", b"foo bar"]);
+
+    let reader = stream::ZipFileReader::with_options(data.as_slice(), options);
+    let mut reading = reader.next_entry().await.unwrap().expect("expected an entry");
+    let mut buffer = String::new();
+    let entry = reading.entry().clone();
+    reading.reader().read_to_string_checked(&mut buffer, &entry).await.expect("failed to read entry");
+    assert_eq!(buffer, "foo bar");
+}
+
+#[tokio::test]
+async fn trailing_data_test() {
+    use crate::error::ZipError;
+    use crate::read::{seek::ZipFileReader, ReaderOptions};
+    use crate::tests::read::stream::stored_test_zip;
+
+    use std::io::Cursor;
+
+    let mut data = stored_test_zip(&[("foo.txt", b"foo")]).await;
+    data.extend_from_slice(&[0; 16]);
+
+    // Small amounts of trailing data are tolerated by default, but rejected in strict mode.
+    assert!(ZipFileReader::new(Cursor::new(&data)).await.is_ok());
+    let result = ZipFileReader::with_options(Cursor::new(&data), ReaderOptions::new().strict(true)).await;
+    assert!(matches!(result.map(|_| ()).unwrap_err().kind(), ZipError::TrailingData(16)));
+
+    // Trailing data beyond the default search window requires a larger one.
+    data.extend_from_slice(&[0; 128 * 1024]);
+    let result = ZipFileReader::new(Cursor::new(&data)).await;
+    assert!(matches!(result, Err(ZipError::UnableToLocateEOCDR)));
+
+    let options = ReaderOptions::new().eocdr_search_window(256 * 1024);
+    let reader = ZipFileReader::with_options(Cursor::new(&data), options).await.expect("failed to open ZIP file");
+    assert_eq!(reader.file().entries().len(), 1);
+}
+
+#[tokio::test]
+async fn trust_central_directory_test() {
+    use crate::read::{mem::ZipFileReader, ReaderOptions};
+    use crate::tests::read::stream::stored_test_zip;
+
+    use tokio::io::AsyncReadExt;
+
+    // Corrupting the first local file header's signature means it can only be opened if the header isn't read.
+    let mut data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    data[0] = 0;
+
+    let reader = ZipFileReader::new(data.clone()).await.expect("failed to open ZIP file");
+    assert!(reader.entry(0).await.is_err());
+
+    let options = ReaderOptions::new().trust_central_directory(true);
+    let reader = ZipFileReader::with_options(data, options).await.expect("failed to open ZIP file");
+
+    for (index, expected) in ["foo", "bar"].into_iter().enumerate() {
+        let mut buffer = String::new();
+        reader.entry(index).await.expect("failed to open entry").read_to_string(&mut buffer).await.unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::remote::{RangeReader, ZipFileReader};
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// An in-memory source which counts the number of ranged reads made against it.
+pub(crate) struct CountingSource {
+    pub(crate) data: Vec<u8>,
+    pub(crate) reads: AtomicUsize,
+}
+
+impl RangeReader for CountingSource {
+    async fn size(&self) -> std::io::Result<u64> {
+        Ok(self.data.len() as u64)
+    }
+
+    async fn read_range(&self, offset: u64, length: u64) -> std::io::Result<Vec<u8>> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        Ok(self.data[offset as usize..(offset + length) as usize].to_vec())
+    }
+}
+
+/// Writes a ZIP file with a large stored entry followed by a small one, so that the small entry's data lies outside
+/// of the prefetched tail.
+pub(crate) async fn large_test_zip() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new(String::from("large.bin"), Compression::Stored);
+    writer.write_entry_whole(builder, &vec![0xAB; 256 * 1024]).await.expect("failed to write entry");
+    let builder = ZipEntryBuilder::new(String::from("small.txt"), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+
+    writer.close().await.expect("failed to close writer")
+}
+
+#[tokio::test]
+async fn remote_read_test() {
+    let source = CountingSource { data: large_test_zip().await, reads: AtomicUsize::new(0) };
+    let reader = ZipFileReader::new(source).await.expect("failed to open ZIP file");
+
+    assert_eq!(reader.file().entries().len(), 2);
+    assert_eq!(reader.range_reader().reads.load(Ordering::SeqCst), 1);
+
+    let mut data = Vec::new();
+    let entry = reader.file().entries()[0].entry().clone();
+    reader.entry(0).await.unwrap().read_to_end_checked(&mut data, &entry).await.expect("failed to read entry");
+    assert_eq!(data, vec![0xAB; 256 * 1024]);
+
+    let mut data = String::new();
+    let entry = reader.file().entries()[1].entry().clone();
+    reader.entry(1).await.unwrap().read_to_string_checked(&mut data, &entry).await.expect("failed to read entry");
+    assert_eq!(data, "foo bar");
+}
+
+#[tokio::test]
+async fn cached_remote_read_test() {
+    use crate::read::remote::CachedRangeReader;
+
+    let source = CountingSource { data: large_test_zip().await, reads: AtomicUsize::new(0) };
+    let cached = CachedRangeReader::new(source).chunk_size(4096).capacity(128);
+    let reader = ZipFileReader::new(cached).await.expect("failed to open ZIP file");
+
+    let mut data = String::new();
+    let entry = reader.file().entries()[1].entry().clone();
+    reader.entry(1).await.unwrap().read_to_string_checked(&mut data, &entry).await.expect("failed to read entry");
+    assert_eq!(data, "foo bar");
+
+    let reads = reader.range_reader().inner().reads.load(Ordering::SeqCst);
+
+    let mut data = String::new();
+    reader.entry(1).await.unwrap().read_to_string_checked(&mut data, &entry).await.expect("failed to read entry");
+    assert_eq!(data, "foo bar");
+    assert_eq!(reader.range_reader().inner().reads.load(Ordering::SeqCst), reads);
+}
+
+#[tokio::test]
+async fn cached_range_eviction_test() {
+    use crate::read::remote::CachedRangeReader;
+
+    let source = CountingSource { data: (0..12).collect(), reads: AtomicUsize::new(0) };
+    let cached = CachedRangeReader::new(source).chunk_size(4).capacity(2);
+    let reads = || cached.inner().reads.load(Ordering::SeqCst);
+
+    // Empty and past-EOF ranges don't reach the source, and ranges overrunning EOF are clamped.
+    assert!(cached.read_range(4, 0).await.unwrap().is_empty());
+    assert!(cached.read_range(12, 4).await.unwrap().is_empty());
+    assert!(cached.read_range(100, 4).await.unwrap().is_empty());
+    assert_eq!(reads(), 0);
+    assert_eq!(cached.read_range(10, 8).await.unwrap(), vec![10, 11]);
+    assert_eq!(reads(), 1);
+
+    // Chunk 2 was used least recently, so it's the one evicted when chunk 1 is fetched.
+    assert_eq!(cached.read_range(0, 4).await.unwrap(), vec![0, 1, 2, 3]);
+    assert_eq!(reads(), 2);
+    assert_eq!(cached.read_range(0, 1).await.unwrap(), vec![0]);
+    assert_eq!(reads(), 2);
+    assert_eq!(cached.read_range(4, 4).await.unwrap(), vec![4, 5, 6, 7]);
+    assert_eq!(reads(), 3);
+    assert_eq!(cached.read_range(0, 8).await.unwrap(), (0..8).collect::<Vec<u8>>());
+    assert_eq!(reads(), 3);
+    assert_eq!(cached.read_range(8, 4).await.unwrap(), vec![8, 9, 10, 11]);
+    assert_eq!(reads(), 4);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn cached_index_test() {
+    let data = large_test_zip().await;
+    let size = data.len() as u64;
+    let reader = ZipFileReader::new(CountingSource { data: data.clone(), reads: AtomicUsize::new(0) }).await.unwrap();
+
+    let index = serde_json::to_string(reader.file()).expect("failed to serialise index");
+    let file = serde_json::from_str(&index).expect("failed to deserialise index");
+
+    // Reconstructing the reader from the cached index doesn't fetch anything.
+    let reader = ZipFileReader::from_parts(CountingSource { data, reads: AtomicUsize::new(0) }, size, file);
+    assert_eq!(reader.range_reader().reads.load(Ordering::SeqCst), 0);
+
+    let mut data = String::new();
+    let entry = reader.file().entries()[1].entry().clone();
+    assert_eq!(entry.filename(), "small.txt");
+    reader.entry(1).await.unwrap().read_to_string_checked(&mut data, &entry).await.expect("failed to read entry");
+    assert_eq!(data, "foo bar");
+}
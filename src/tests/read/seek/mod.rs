@@ -0,0 +1,217 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::seek::ZipFileReader;
+use crate::tests::read::stream::stored_test_zip;
+
+use std::io::Cursor;
+
+use tokio::io::AsyncReadExt;
+
+#[tokio::test]
+async fn entries_by_offset_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar"), ("baz.txt", b"baz")]).await;
+
+    // Reverse the central directory so that its order differs from the order of the entries' data.
+    let mut file = ZipFileReader::new(Cursor::new(&data)).await.expect("failed to open ZIP file").file().clone();
+    file.entries.reverse();
+
+    let mut reader = ZipFileReader::from_parts(Cursor::new(&data), file);
+    let mut entries = reader.entries_by_offset();
+    let mut contents = Vec::new();
+
+    while let Some((index, mut entry)) = entries.next_entry().await.expect("failed to open entry") {
+        let mut buffer = String::new();
+        entry.read_to_string(&mut buffer).await.expect("failed to read entry");
+        contents.push((index, buffer));
+    }
+
+    assert_eq!(contents, [(2, "foo".to_string()), (1, "bar".to_string()), (0, "baz".to_string())]);
+}
+
+#[tokio::test]
+async fn entries_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar"), ("baz.txt", b"baz")]).await;
+    let mut reader = ZipFileReader::new(Cursor::new(&data)).await.expect("failed to open ZIP file");
+
+    let entries = reader.entries(&[2, 0, 2]).await.expect("failed to read entries");
+    assert_eq!(entries, [b"baz".to_vec(), b"foo".to_vec(), b"baz".to_vec()]);
+
+    assert!(matches!(reader.entries(&[0, 3]).await, Err(crate::error::ZipError::EntryIndexOutOfBounds)));
+}
+
+#[tokio::test]
+async fn error_context_test() {
+    let mut data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    let file = ZipFileReader::new(Cursor::new(&data)).await.expect("failed to open ZIP file").file().clone();
+    let offset = file.entries()[1].header_offset();
+
+    // Corrupt the second entry's local file header signature.
+    data[offset as usize] ^= 0xff;
+    let mut reader = ZipFileReader::from_parts(Cursor::new(&data), file);
+
+    let error = reader.entry(1).await.err().expect("corrupt header wasn't detected");
+    assert!(matches!(error.kind(), crate::error::ZipError::UnexpectedHeaderError(..)));
+    assert_eq!(error.offset(), Some(offset));
+    assert_eq!(error.entry_index(), Some(1));
+    assert_eq!(error.entry_filename(), Some("bar.txt"));
+    assert!(error.to_string().ends_with(&format!("(at byte offset {offset}) in entry 1 'bar.txt'")));
+}
+
+#[tokio::test]
+async fn duplicates_test() {
+    let data =
+        stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar"), ("foo.txt", b"baz"), ("bar.txt", b"")]).await;
+    let reader = ZipFileReader::new(Cursor::new(&data)).await.expect("failed to open ZIP file");
+
+    let index_map = reader.file().index_map();
+    assert_eq!(index_map.len(), 2);
+    assert_eq!(index_map["foo.txt"], [0, 2]);
+
+    assert_eq!(reader.file().duplicates(), [("foo.txt", vec![0, 2]), ("bar.txt", vec![1, 3])]);
+}
+
+#[tokio::test]
+async fn verify_test() {
+    let mut data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"qux"), ("baz.txt", b"baz")]).await;
+    let file = ZipFileReader::new(Cursor::new(&data)).await.expect("failed to open ZIP file").file().clone();
+
+    // Corrupt the second entry's data and the third entry's local file header signature.
+    let data_offset = data.windows(3).position(|window| window == b"qux").unwrap();
+    data[data_offset] ^= 0xff;
+    data[file.entries()[2].header_offset() as usize] ^= 0xff;
+
+    let report = ZipFileReader::from_parts(Cursor::new(&data), file).verify().await;
+    assert!(!report.is_ok());
+    assert_eq!(report.verified(), 1);
+
+    let failures = report.failures().iter().map(|failure| (failure.index(), failure.filename())).collect::<Vec<_>>();
+    assert_eq!(failures, [(1, "bar.txt"), (2, "baz.txt")]);
+    assert!(matches!(report.failures()[0].error().kind(), crate::error::ZipError::CRC32CheckError));
+}
+
+#[tokio::test]
+async fn diff_test() {
+    let old = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar"), ("baz.txt", b"baz")]).await;
+    let new = stored_test_zip(&[("foo.txt", b"foo"), ("baz.txt", b"qux"), ("new.txt", b"new")]).await;
+
+    let mut old = ZipFileReader::new(Cursor::new(&old)).await.expect("failed to open ZIP file");
+    let mut new = ZipFileReader::new(Cursor::new(&new)).await.expect("failed to open ZIP file");
+
+    let diff = old.file().diff(new.file());
+    assert_eq!(
+        (diff.added(), diff.removed(), diff.changed()),
+        (&["new.txt".into()][..], &["bar.txt".into()][..], &["baz.txt".into()][..])
+    );
+    assert_eq!(old.diff(&mut new).await.expect("failed to diff ZIP files"), diff);
+    assert!(old.file().diff(old.file()).is_empty());
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn nested_entry_test() {
+    use crate::read::nested::NestedZipReader;
+    use crate::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+
+    let inner = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, compression) in [("stored.zip", Compression::Stored), ("deflate.zip", Compression::Deflate)] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), compression);
+        writer.write_entry_whole(builder, &inner).await.expect("failed to write entry");
+    }
+
+    let data = writer.close().await.expect("failed to close writer");
+    let mut reader = ZipFileReader::new(Cursor::new(&data)).await.expect("failed to open ZIP file");
+
+    let Ok(NestedZipReader::Seek(mut nested)) = reader.nested_entry(0).await else {
+        panic!("expected a seekable nested reader");
+    };
+    assert_eq!(nested.entries(&[1, 0]).await.expect("failed to read entries"), [b"bar".to_vec(), b"foo".to_vec()]);
+
+    let Ok(NestedZipReader::Stream(nested)) = reader.nested_entry(1).await else {
+        panic!("expected a streaming nested reader");
+    };
+    let mut entry = (*nested).next_entry().await.expect("failed to read entry").expect("expected an entry");
+    assert_eq!(entry.entry().filename(), "foo.txt");
+
+    let mut buffer = String::new();
+    entry.reader().read_to_string(&mut buffer).await.expect("failed to read entry");
+    assert_eq!(buffer, "foo");
+}
+
+#[tokio::test]
+async fn entry_with_capacity_test() {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+    /// A source which records the largest read requested of it.
+    struct Recording(Cursor<Vec<u8>>, usize);
+
+    impl AsyncRead for Recording {
+        fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+            self.1 = self.1.max(b.remaining());
+            Pin::new(&mut self.0).poll_read(c, b)
+        }
+    }
+
+    impl AsyncSeek for Recording {
+        fn start_seek(mut self: Pin<&mut Self>, position: std::io::SeekFrom) -> std::io::Result<()> {
+            Pin::new(&mut self.0).start_seek(position)
+        }
+
+        fn poll_complete(mut self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+            Pin::new(&mut self.0).poll_complete(c)
+        }
+    }
+
+    let data = stored_test_zip(&[("foo.txt", b"foo")]).await;
+    let mut reader = ZipFileReader::new(Recording(Cursor::new(data), 0)).await.expect("failed to open ZIP file");
+
+    for capacity in [64, 16] {
+        reader.inner_mut().1 = 0;
+        let mut byte = [0; 1];
+        reader.entry_with_capacity(0, capacity).await.unwrap().read_exact(&mut byte).await.unwrap();
+        assert_eq!(reader.inner_mut().1, capacity);
+    }
+
+    reader.set_buffer_size(32);
+    reader.inner_mut().1 = 0;
+    let mut buffer = String::new();
+    reader.entry(0).await.unwrap().read_to_string(&mut buffer).await.unwrap();
+    assert_eq!((buffer.as_str(), reader.inner_mut().1), ("foo", 32));
+}
+
+#[cfg(all(feature = "parallel", feature = "deflate"))]
+#[tokio::test]
+async fn offload_threshold_test() {
+    use crate::read::ReaderOptions;
+    use crate::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+
+    // Larger than a few chunks, so that data flows through both channels more than once.
+    let data: Vec<u8> = (0..1024 * 1024).map(|index: u32| (index.wrapping_mul(2654435761) >> 24) as u8).collect();
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("small.txt", &b"foo bar"[..]), ("large.bin", &data)] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Deflate);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+
+    let archive = writer.close().await.expect("failed to close writer");
+    let options = ReaderOptions::new().offload_threshold(1024);
+    let mut reader =
+        ZipFileReader::with_options(Cursor::new(&archive), options).await.expect("failed to open ZIP file");
+
+    for (index, expected) in [&b"foo bar"[..], &data].into_iter().enumerate() {
+        let entry = reader.file().entries()[index].entry().clone();
+        let mut buffer = Vec::new();
+        let mut entry_reader = reader.entry(index).await.expect("failed to open entry");
+
+        entry_reader.read_to_end_checked(&mut buffer, &entry).await.expect("failed to read entry");
+        assert_eq!(entry_reader.compressed_bytes_read(), entry.compressed_size());
+        assert_eq!(buffer, expected);
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::shared::ZipFileReader;
+use crate::tests::read::stream::stored_test_zip;
+
+use std::io::Cursor;
+
+use tokio::io::AsyncReadExt;
+
+#[tokio::test]
+async fn shared_entry_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    let reader = ZipFileReader::new(Cursor::new(data)).await.expect("failed to open ZIP file");
+
+    let read = |reader: ZipFileReader<Cursor<Vec<u8>>>, index| async move {
+        let mut buffer = String::new();
+        let mut entry = reader.entry(index).await.expect("failed to open entry");
+        entry.read_to_string(&mut buffer).await.expect("failed to read entry");
+        buffer
+    };
+
+    let handles = [tokio::spawn(read(reader.clone(), 1)), tokio::spawn(read(reader.clone(), 0))];
+    let mut contents = Vec::new();
+
+    for handle in handles {
+        contents.push(handle.await.expect("task panicked"));
+    }
+
+    assert_eq!(contents, ["bar", "foo"]);
+}
@@ -0,0 +1,273 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::stream::{UnconsumedEntryBehavior, ZipFileReader};
+use crate::read::ReaderOptions;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use tokio::io::AsyncReadExt;
+
+/// Writes a ZIP file containing the provided stored entries.
+pub(crate) async fn stored_test_zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in entries {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+
+    writer.close().await.expect("failed to close writer")
+}
+
+#[tokio::test]
+async fn offset_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"foo bar")]).await;
+    let mut zip = ZipFileReader::new(&data[..]);
+    assert_eq!(zip.offset(), 0);
+
+    let mut contents = Vec::new();
+
+    while let Some(mut entry) = zip.next_entry().await.expect("failed to read local file header") {
+        let data_offset = entry.offset();
+        assert_eq!(data_offset, zip_offset_after_header(&contents, entry.entry().filename()));
+
+        let mut buffer = String::new();
+        let metadata = entry.entry().clone();
+        entry.reader().read_to_string_checked(&mut buffer, &metadata).await.expect("failed to read entry");
+        assert_eq!(entry.compressed_bytes_read(), buffer.len() as u64);
+        assert_eq!(entry.offset(), data_offset + buffer.len() as u64);
+
+        contents.push((entry.entry().filename().to_string(), buffer));
+        zip = entry.done().await.expect("failed to finish entry");
+    }
+
+    assert_eq!(contents, [("foo.txt".to_string(), "foo".to_string()), ("bar.txt".to_string(), "foo bar".to_string())]);
+}
+
+/// Computes the expected data offset of the next entry given the entries which preceded it.
+fn zip_offset_after_header(previous: &[(String, String)], filename: &str) -> u64 {
+    let previous: usize = previous.iter().map(|(name, data)| 30 + name.len() + data.len()).sum();
+    (previous + 30 + filename.len()) as u64
+}
+
+#[tokio::test]
+async fn close_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo bar"), ("bar.txt", b"bar")]).await;
+    let zip = ZipFileReader::new(&data[..]);
+
+    let mut entry = zip.next_entry().await.expect("failed to read local file header").expect("no entry");
+    entry.reader().read_exact(&mut [0; 3]).await.expect("failed to read entry");
+    let zip = entry.close().await.expect("failed to close entry");
+
+    let entry = zip.next_entry().await.expect("failed to read local file header").expect("no entry");
+    assert_eq!(entry.entry().filename(), "bar.txt");
+    entry.close().await.expect("failed to close entry");
+}
+
+#[tokio::test]
+#[should_panic(expected = "before all of its data was consumed")]
+async fn unconsumed_entry_panic_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo bar")]).await;
+    let options = ReaderOptions::new().unconsumed_entry_behavior(UnconsumedEntryBehavior::Panic);
+    let zip = ZipFileReader::with_options(&data[..], options);
+
+    let entry = zip.next_entry().await.expect("failed to read local file header");
+    drop(entry);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn unconsumed_entry_panic_data_descriptor_test() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Deflate);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"foo bar").await.expect("failed to write entry");
+    entry_writer.close().await.expect("failed to close entry");
+    let data = writer.close().await.expect("failed to close writer");
+
+    let options = ReaderOptions::new().unconsumed_entry_behavior(UnconsumedEntryBehavior::Panic);
+
+    // An entry which has been read until EOF is consumed, even though its data descriptor remains unread.
+    let zip = ZipFileReader::with_options(&data[..], options.clone());
+    let mut entry = zip.next_entry().await.expect("failed to read local file header").expect("no entry");
+    entry.reader().read_to_end(&mut Vec::new()).await.expect("failed to read entry");
+    drop(entry);
+
+    // An error returned from done() shouldn't then also panic as the entry is dropped.
+    let zip = ZipFileReader::with_options(&data[..], options);
+    let mut entry = zip.next_entry().await.expect("failed to read local file header").expect("no entry");
+    entry.reader().read_exact(&mut [0; 3]).await.expect("failed to read entry");
+    let error = entry.done().await.err().expect("partial read wasn't detected");
+    assert!(matches!(error.kind(), crate::error::ZipError::EntryNotFullyRead));
+}
+
+#[tokio::test]
+async fn skip_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo bar"), ("bar.txt", b"bar"), ("baz.txt", b"baz")]).await;
+    let zip = ZipFileReader::new(std::io::Cursor::new(&data));
+
+    let mut entry = zip.next_entry().await.expect("failed to read local file header").expect("no entry");
+    entry.reader().read_exact(&mut [0; 3]).await.expect("failed to read entry");
+    let zip = entry.skip().await.expect("failed to skip entry");
+    assert_eq!(zip.offset(), zip_offset_after_header(&[], "foo.txt") + 7);
+
+    let entry = zip.next_entry().await.expect("failed to read local file header").expect("no entry");
+    let zip = entry.skip_by_seeking().await.expect("failed to skip entry");
+
+    let mut entry = zip.next_entry().await.expect("failed to read local file header").expect("no entry");
+    assert_eq!(entry.entry().filename(), "baz.txt");
+
+    let mut buffer = String::new();
+    entry.reader().read_to_string(&mut buffer).await.expect("failed to read entry");
+    assert_eq!(buffer, "baz");
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn into_stream_test() {
+    use futures_util::StreamExt;
+
+    let data = stored_test_zip(&[("foo.txt", b"foo bar"), ("bar.txt", b"bar")]).await;
+    let stream = ZipFileReader::new(std::io::Cursor::new(data)).into_stream();
+    futures_util::pin_mut!(stream);
+
+    // Partially read the first entry to ensure its remaining data is skipped.
+    let (entry, mut reader) = stream.next().await.expect("no entry").expect("failed to read entry");
+    assert_eq!(entry.filename(), "foo.txt");
+    reader.read_exact(&mut [0; 3]).await.expect("failed to read entry");
+    drop(reader);
+
+    let (entry, mut reader) = stream.next().await.expect("no entry").expect("failed to read entry");
+    assert_eq!(entry.filename(), "bar.txt");
+
+    let mut buffer = String::new();
+    reader.read_to_string(&mut buffer).await.expect("failed to read entry");
+    assert_eq!(buffer, "bar");
+    drop(reader);
+
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn peek_entry_test() {
+    let data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    let mut zip = ZipFileReader::new(&data[..]);
+
+    let peeked = zip.peek_entry().await.expect("failed to peek entry").expect("no entry");
+    assert_eq!(peeked.filename(), "foo.txt");
+    assert_eq!(zip.peek_entry().await.expect("failed to peek entry").map(|entry| entry.filename()), Some("foo.txt"));
+    assert_eq!(zip.offset(), 0);
+
+    let entry = zip.next_entry().await.expect("failed to read local file header").expect("no entry");
+    assert_eq!(entry.entry().filename(), "foo.txt");
+    let mut zip = entry.skip().await.expect("failed to skip entry");
+
+    assert_eq!(zip.peek_entry().await.expect("failed to peek entry").map(|entry| entry.filename()), Some("bar.txt"));
+    zip = zip.next_entry().await.expect("failed to read local file header").expect("no entry").skip().await.unwrap();
+
+    assert!(zip.peek_entry().await.expect("failed to peek entry").is_none());
+    assert!(zip.next_entry().await.expect("failed to read local file header").is_none());
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn data_descriptor_test() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", "foo bar"), ("bar.txt", "bar")] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Deflate);
+        let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+        entry_writer.write_all(data.as_bytes()).await.expect("failed to write entry");
+        entry_writer.close().await.expect("failed to close entry");
+    }
+
+    let mut data = writer.close().await.expect("failed to close writer");
+    let mut zip = ZipFileReader::new(&data[..]);
+    let mut contents = Vec::new();
+
+    while let Some(mut entry) = zip.next_entry().await.expect("failed to read local file header") {
+        let mut buffer = String::new();
+        entry.reader().read_to_string(&mut buffer).await.expect("failed to read entry");
+        contents.push(buffer);
+        zip = entry.done().await.expect("failed to finish entry");
+    }
+
+    assert_eq!(contents, ["foo bar", "bar"]);
+
+    // Corrupt the CRC32 value within the first entry's data descriptor (which directly follows its signature).
+    let position = data.windows(4).position(|window| window == [0x50, 0x4b, 0x07, 0x08]).expect("no data descriptor");
+    data[position + 4] ^= 0xff;
+
+    let mut entry = ZipFileReader::new(&data[..]).next_entry().await.unwrap().expect("no entry");
+    entry.reader().read_to_end(&mut Vec::new()).await.expect("failed to read entry");
+    let error = entry.done().await.err().expect("CRC32 mismatch wasn't detected");
+    assert!(matches!(error.kind(), crate::error::ZipError::CRC32CheckError));
+    assert_eq!(error.offset(), Some(position as u64));
+    assert_eq!(error.entry_filename(), Some("foo.txt"));
+}
+
+#[tokio::test]
+async fn next_archive_test() {
+    let mut data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    data.extend(stored_test_zip(&[]).await);
+    data.extend(stored_test_zip(&[("baz.txt", b"baz")]).await);
+
+    let mut zip = ZipFileReader::new(&data[..]);
+    let mut archives = Vec::new();
+
+    loop {
+        let mut filenames = Vec::new();
+
+        // Only read the first entry of each archive to ensure any remaining entries are skipped.
+        if let Some(entry) = zip.peek_entry().await.expect("failed to peek entry") {
+            filenames.push(entry.filename().to_string());
+        }
+
+        archives.push(filenames);
+
+        zip = match zip.next_archive().await.expect("failed to move onto the next archive") {
+            Some(zip) => zip,
+            None => break,
+        };
+    }
+
+    assert_eq!(archives, [vec!["foo.txt".to_string()], vec![], vec!["baz.txt".to_string()]]);
+}
+
+#[tokio::test]
+async fn next_archive_zip64_test() {
+    use crate::spec::consts::{EOCDR_LENGTH, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDL_SIGNATURE};
+    use crate::spec::consts::{ZIP64_EOCDR_LENGTH, ZIP64_EOCDR_SIGNATURE};
+
+    // ZIP64 end records are only written for very large archives, so they're instead inserted ahead of the EOCDR of a
+    // smaller archive (their contents don't matter as they're only skipped over by their lengths).
+    let mut data = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    let eocdr_offset = data.len() - (SIGNATURE_LENGTH + EOCDR_LENGTH);
+
+    let mut records = ZIP64_EOCDR_SIGNATURE.to_le_bytes().to_vec();
+    records.extend(((ZIP64_EOCDR_LENGTH - 8) as u64).to_le_bytes());
+    records.extend([0; ZIP64_EOCDR_LENGTH - 8]);
+    records.extend(ZIP64_EOCDL_SIGNATURE.to_le_bytes());
+    records.extend([0; ZIP64_EOCDL_LENGTH]);
+    data.splice(eocdr_offset..eocdr_offset, records);
+    data.extend(stored_test_zip(&[("baz.txt", b"baz")]).await);
+
+    let mut zip = ZipFileReader::new(&data[..]);
+    let mut archives = Vec::new();
+
+    loop {
+        archives.push(zip.peek_entry().await.expect("failed to peek entry").map(|entry| entry.filename().to_string()));
+
+        zip = match zip.next_archive().await.expect("failed to move onto the next archive") {
+            Some(zip) => zip,
+            None => break,
+        };
+    }
+
+    assert_eq!(archives, [Some("foo.txt".to_string()), Some("baz.txt".to_string())]);
+}
@@ -1,6 +1,7 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "chrono")]
 use crate::spec::date::ZipDateTime;
 #[cfg(feature = "chrono")]
 use chrono::{TimeZone, Utc};
@@ -8,8 +9,24 @@ use chrono::{TimeZone, Utc};
 #[test]
 #[cfg(feature = "chrono")]
 fn date_conversion_test() {
-    let original_dt = Utc.timestamp(1666544102, 0);
+    let original_dt = Utc.timestamp_opt(1666544102, 0).unwrap();
     let zip_dt = ZipDateTime::from_chrono(&original_dt);
     let result_dt = zip_dt.as_chrono().single().expect("expected single unique result");
     assert_eq!(result_dt, original_dt);
 }
+
+#[test]
+fn system_time_conversion_test() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let original = UNIX_EPOCH + Duration::from_secs(1666544102);
+    let zip_dt = crate::ZipDateTime::from(original);
+    assert_eq!((zip_dt.year(), zip_dt.month(), zip_dt.day()), (2022, 10, 23));
+    assert_eq!((zip_dt.hour(), zip_dt.minute(), zip_dt.second()), (16, 55, 2));
+    assert_eq!(SystemTime::from(zip_dt), original);
+
+    // Times outside of the MS-DOS range are clamped to its bounds.
+    assert_eq!(crate::ZipDateTime::from_system_time(UNIX_EPOCH).year(), 1980);
+    let far_future = UNIX_EPOCH + Duration::from_secs(1 << 40);
+    assert_eq!(crate::ZipDateTime::from_system_time(far_future).year(), 2107);
+}
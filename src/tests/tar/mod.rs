@@ -0,0 +1,87 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::seek::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{AttributeCompatibility, Compression, ZipEntryBuilder};
+
+use std::io::Cursor;
+
+#[tokio::test]
+async fn tar_round_trip_test() {
+    let long_name = format!("{}.txt", "a".repeat(150));
+    let files = [("foo.txt", b"foo".to_vec()), (long_name.as_str(), vec![b'b'; 1000])];
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_dir_entry(ZipEntryBuilder::new("dir".to_string(), Compression::Stored)).await.unwrap();
+
+    for (filename, data) in &files {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored)
+            .attribute_compatibility(AttributeCompatibility::Unix)
+            .unix_permissions(0o600);
+        writer.write_entry_whole(builder, data).await.unwrap();
+    }
+
+    let builder = ZipEntryBuilder::new("link".to_string(), Compression::Stored);
+    writer.write_symlink_entry(builder, "foo.txt").await.unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    let tar = crate::tar::zip_to_tar(&mut reader, Vec::new()).await.unwrap();
+    assert_eq!(tar.len() % 512, 0);
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    assert_eq!(crate::tar::tar_to_zip(&tar[..], &mut writer).await.unwrap(), 4);
+
+    let mut reader = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    let entries: Vec<_> = reader.file().entries().iter().map(|stored_entry| stored_entry.entry().clone()).collect();
+
+    assert!(entries[0].dir() && entries[0].filename() == "dir/");
+    assert_eq!(entries[0].unix_permissions(), Some(0o040755));
+    assert!(entries[3].symlink() && entries[3].filename() == "link");
+
+    for (index, (filename, data)) in files.iter().enumerate() {
+        let entry = &entries[index + 1];
+        assert_eq!(entry.filename(), *filename);
+        assert_eq!(entry.unix_permissions(), Some(0o100600));
+
+        let mut buffer = Vec::new();
+        reader.entry(index + 1).await.unwrap().read_to_end_checked(&mut buffer, entry).await.unwrap();
+        assert_eq!(&buffer, data);
+    }
+
+    let mut target = String::new();
+    reader.entry(3).await.unwrap().read_to_string_checked(&mut target, &entries[3]).await.unwrap();
+    assert_eq!(target, "foo.txt");
+}
+
+#[test]
+fn parse_number_test() {
+    use crate::error::ZipError;
+    use crate::tar::header::parse_number;
+
+    assert_eq!(parse_number(b"0000644\0").unwrap(), 0o644);
+    assert_eq!(parse_number(&[0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x00]).unwrap(), 256);
+
+    // A base-256 value within a 12-byte field may hold more than 64 bits.
+    let result = parse_number(&[0x80, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert!(matches!(result, Err(ZipError::InvalidTarHeader(_))));
+}
+
+#[tokio::test]
+async fn tar_mtime_overflow_test() {
+    use crate::error::ZipError;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored), b"foo").await.unwrap();
+    let mut reader = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    let mut tar = crate::tar::zip_to_tar(&mut reader, Vec::new()).await.unwrap();
+
+    // Set the modification time to u64::MAX seconds via a base-256 field, and then recompute the header's checksum.
+    tar[136..148].copy_from_slice(&[0x80, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    tar[148..156].fill(b' ');
+    let checksum: u32 = tar[..512].iter().map(|b| *b as u32).sum();
+    tar[148..156].copy_from_slice(format!("{checksum:06o}\0 ").as_bytes());
+
+    let result = crate::tar::tar_to_zip(&tar[..], &mut ZipFileWriter::new(Vec::new())).await;
+    assert!(matches!(result, Err(ZipError::InvalidTarHeader(_))));
+}
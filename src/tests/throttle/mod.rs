@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::time::Duration;
+
+use tokio::io::AsyncReadExt;
+use tokio::time::Instant;
+
+#[tokio::test(start_paused = true)]
+async fn throttled_test() {
+    let data = vec![0; 4096];
+
+    let start = Instant::now();
+    let mut writer = ZipFileWriter::throttled(Vec::new(), 1024);
+    let builder = ZipEntryBuilder::new(String::from("foo.bin"), Compression::Stored);
+    writer.write_entry_whole(builder, &data).await.expect("failed to write entry");
+    let zip = writer.close().await.expect("failed to close writer").into_inner();
+    assert!(start.elapsed() >= Duration::from_secs(4));
+
+    let reader = ZipFileReader::new(zip).await.expect("failed to open ZIP file");
+
+    let start = Instant::now();
+    let mut buffer = Vec::new();
+    reader.entry(0).await.unwrap().throttled(2048).read_to_end(&mut buffer).await.expect("failed to read entry");
+    assert_eq!(buffer, data);
+    assert!(start.elapsed() >= Duration::from_millis(1500) && start.elapsed() < Duration::from_secs(3));
+}
@@ -0,0 +1,84 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn abort_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("partial.txt".to_string(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"partial data").await.expect("failed to write entry");
+    entry_writer.abort();
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"bar").await.expect("failed to write entry");
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let filenames: Vec<_> =
+        reader.file().entries().iter().map(|stored_entry| stored_entry.entry().filename()).collect();
+    assert_eq!(filenames, ["foo.txt", "bar.txt"]);
+
+    let entry = reader.file().entries()[1].entry().clone();
+    let mut buffer = Vec::new();
+    reader.entry(1).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+    assert_eq!(buffer, b"bar");
+}
+
+#[tokio::test]
+async fn abort_and_rewind_test() {
+    let mut writer = ZipFileWriter::new(std::io::Cursor::new(Vec::new()));
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+    let offset = writer.offset();
+
+    let builder = ZipEntryBuilder::new("partial.txt".to_string(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"partial data").await.expect("failed to write entry");
+    entry_writer.abort_and_rewind().await.expect("failed to abort entry");
+    assert_eq!(writer.offset(), offset);
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"bar").await.expect("failed to write entry");
+
+    let data = writer.close().await.expect("failed to close writer").into_inner();
+    let signature = crate::spec::consts::LFH_SIGNATURE.to_le_bytes();
+    assert_eq!(data.windows(4).filter(|window| *window == signature).count(), 2);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[1].header_offset(), offset);
+    assert_eq!(reader.file().entries()[1].entry().filename(), "bar.txt");
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn abort_and_truncate_test() {
+    let dir = crate::tests::fs::test_dir("abort_and_truncate");
+    let path = dir.join("test.zip");
+    let mut writer = ZipFileWriter::new(tokio::fs::File::create(&path).await.unwrap());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("partial.txt".to_string(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(&[0; 4096]).await.expect("failed to write entry");
+    entry_writer.abort_and_truncate().await.expect("failed to abort entry");
+
+    let (_, length) = writer.close_and_shutdown().await.expect("failed to close writer");
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), length);
+
+    let reader = ZipFileReader::new(std::fs::read(&path).unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 1);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
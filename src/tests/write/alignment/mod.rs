@@ -0,0 +1,33 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn alignment_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, alignment) in [("a", 4), ("bb.txt", 4), ("ccc/ddd.bin", 4096), ("e", 4096)] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored).alignment(alignment);
+        writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    }
+
+    let builder = ZipEntryBuilder::new("f".to_string(), Compression::Stored).alignment(4096);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"foo bar").await.expect("failed to write entry");
+    entry_writer.close().await.expect("failed to close entry");
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+
+    for (index, alignment) in [4, 4, 4096, 4096, 4096].into_iter().enumerate() {
+        let data = reader.stored_entry_data(index).await.expect("failed to read entry");
+        let offset = data.as_ptr() as usize - reader.data().as_ptr() as usize;
+
+        assert_eq!(data, b"foo bar");
+        assert_eq!(offset % alignment, 0, "entry {index} is misaligned");
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::write::ZipFileWriter;
+use crate::{AttributeCompatibility, Attributes, Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn unix_permissions_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.sh".to_string(), Compression::Stored).unix_permissions(0o100755);
+    writer.write_entry_whole(builder, b"#!/bin/sh").await.expect("failed to write entry");
+    let data = writer.close().await.expect("failed to close writer");
+
+    // The upper byte of "version made by" must declare a Unix host for unzippers to honour the mode.
+    let position = data.windows(4).position(|window| window == CDH_SIGNATURE.to_le_bytes()).expect("no CDH");
+    assert_eq!(data[position + 5], 3);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].entry().unix_permissions(), Some(0o100755));
+}
+
+#[tokio::test]
+async fn attributes_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let mut attributes = Attributes::from_unix_mode(0o100644);
+    attributes.set_readonly(true);
+    attributes.set_hidden(true);
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored).attributes(attributes);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let mut attributes = Attributes::new();
+    attributes.set_system(true);
+    attributes.set_unix_mode(0o100755);
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored)
+        .attribute_compatibility(AttributeCompatibility::Dos)
+        .attributes(attributes);
+    writer.write_entry_whole(builder, b"bar").await.expect("failed to write entry");
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entries = reader.file().entries();
+
+    let foo = entries[0].entry();
+    assert_eq!(foo.external_file_attribute(), 0o100444 << 16 | 0x03);
+    assert_eq!(foo.attributes().unix_mode(), Some(0o100444));
+    assert!(foo.attributes().readonly() && foo.attributes().hidden() && !foo.attributes().system());
+
+    // The Unix mode isn't representable with DOS compatibility, so only the flags are kept.
+    let bar = entries[1].entry();
+    assert_eq!(bar.attribute_compatibility(), AttributeCompatibility::Dos);
+    assert_eq!((bar.external_file_attribute(), bar.unix_permissions()), (0x04, None));
+    assert!(bar.attributes().system() && !bar.attributes().readonly());
+}
@@ -0,0 +1,37 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::write::body::ZipBodyStream;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::StreamExt;
+
+#[tokio::test]
+async fn body_stream_test() {
+    let data = vec![7; 200 * 1024];
+    let expected = data.clone();
+
+    let mut body = ZipBodyStream::with_capacity(1, |mut writer| async move {
+        let entry = ZipEntryBuilder::new(String::from("foo.bin"), Compression::Stored);
+        writer.write_entry_whole(entry, &data).await?;
+        Ok(writer)
+    });
+
+    let mut chunks = 0;
+    let mut zip = Vec::new();
+
+    while let Some(chunk) = body.next().await {
+        zip.extend_from_slice(&chunk.expect("failed to write ZIP file"));
+        chunks += 1;
+    }
+
+    assert!(chunks > 1);
+    let reader = ZipFileReader::new(zip).await.expect("failed to open ZIP file");
+    assert_eq!(reader.stored_entry_data(0).await.expect("failed to read entry"), expected);
+
+    let mut body = ZipBodyStream::new(|_| async { Err(ZipError::EntryIndexOutOfBounds) });
+    assert!(matches!(body.next().await, Some(Err(ZipError::EntryIndexOutOfBounds))));
+    assert!(body.next().await.is_none());
+}
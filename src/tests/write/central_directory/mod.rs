@@ -0,0 +1,44 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::{CentralDirectoryWriter, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn central_directory_writer_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", &b"foo"[..]), ("bar.txt", b"bar bar")] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+
+    let (mut data, summary) = writer.close_with_summary().await.expect("failed to close writer");
+    data.truncate(summary.central_directory_offset() as usize);
+
+    // Lay out the existing entries again, in reverse order and with one renamed.
+    let mut writer = CentralDirectoryWriter::new(Vec::new(), data.len() as u64);
+    let entries = summary.file().entries();
+
+    let renamed = ZipEntryBuilder::from(entries[1].entry().clone()).filename("baz.txt".to_string()).build();
+    writer.push_entry(renamed, entries[1].header_offset()).expect("failed to push entry");
+    writer.push_entry(entries[0].entry().clone(), entries[0].header_offset()).expect("failed to push entry");
+    writer.comment("rebuilt".to_string());
+
+    let (central_directory, summary) = writer.close().await.expect("failed to close writer");
+    data.extend(central_directory);
+    assert_eq!(summary.total_bytes(), data.len() as u64);
+
+    let reader = ZipFileReader::new(data).await.expect("failed to open ZIP file");
+    assert_eq!(reader.file().comment(), "rebuilt");
+
+    for (index, (filename, expected)) in [("baz.txt", &b"bar bar"[..]), ("foo.txt", b"foo")].into_iter().enumerate() {
+        let entry = reader.file().entries()[index].entry().clone();
+        assert_eq!(entry.filename(), filename);
+
+        let mut buffer = Vec::new();
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
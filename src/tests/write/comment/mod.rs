@@ -0,0 +1,80 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::{Zip64Policy, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn entry_comment_copy_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.zip64_policy(Zip64Policy::Always);
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored).comment("foo comment".to_string());
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored).comment("bär".to_string());
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"bar").await.expect("failed to write entry");
+    entry_writer.close().await.expect("failed to close entry");
+
+    let source = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+
+    // Copy every entry (along with its metadata) into a new archive.
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for index in 0..source.file().entries().len() {
+        let entry = source.file().entries()[index].entry().clone();
+        let data = source.stored_entry_data(index).await.expect("failed to read entry").to_vec();
+        writer.write_entry_whole(entry, &data).await.expect("failed to write entry");
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert_eq!(entries[0].entry().comment(), "foo comment");
+    assert_eq!(entries[1].entry().comment(), "bär");
+
+    // The source's ZIP64 extra fields shouldn't be carried over into the copies.
+    assert!(entries.iter().all(|entry| entry.entry().extra_field().is_empty()));
+}
+
+#[tokio::test]
+async fn update_comment_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment("foo".to_string());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let mut file = std::io::Cursor::new(writer.close().await.expect("failed to close writer"));
+
+    for comment in ["a much longer comment", "bar"] {
+        let length = crate::write::update_comment(&mut file, comment).await.expect("failed to update comment");
+        file.get_mut().truncate(length as usize);
+
+        let reader = ZipFileReader::new(file.get_ref().clone()).await.unwrap();
+        assert_eq!(reader.file().comment(), comment);
+        assert_eq!(reader.stored_entry_data(0).await.unwrap(), b"foo");
+    }
+}
+
+#[tokio::test]
+async fn raw_comment_test() {
+    // "Größe" encoded as CP437, which isn't valid UTF-8.
+    let comment = vec![0x47, 0x72, 0xF6, 0xE1, 0x65];
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    assert!(writer.comment_raw(vec![0; u16::MAX as usize + 1]).is_err());
+    writer.comment_raw(comment.clone()).expect("failed to set comment");
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let data = writer.close().await.expect("failed to close writer");
+    let (eocdr, written) = data.split_at(data.len() - comment.len());
+
+    assert_eq!(written, comment);
+    assert_eq!(&eocdr[eocdr.len() - 2..], (comment.len() as u16).to_le_bytes());
+}
@@ -0,0 +1,35 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::compat::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures::io::{AsyncWriteExt, Cursor};
+
+#[tokio::test]
+async fn futures_io_writer_test() {
+    let mut writer = ZipFileWriter::new(Cursor::new(Vec::new()));
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"bar").await.expect("failed to write entry");
+    entry_writer.close().await.expect("failed to close entry");
+
+    let builder = ZipEntryBuilder::new("baz.txt".to_string(), Compression::Stored);
+    writer.write_entry_from_reader(builder, Cursor::new(b"baz")).await.expect("failed to write entry");
+
+    let data = writer.close().await.expect("failed to close writer").into_inner();
+    let reader = ZipFileReader::new(data).await.expect("failed to open reader");
+
+    for (index, expected) in [b"foo", b"bar", b"baz"].into_iter().enumerate() {
+        let entry = reader.file().entries()[index].entry().clone();
+        let mut buffer = Vec::new();
+        let mut entry_reader = reader.entry(index).await.unwrap();
+        entry_reader.read_to_end_checked(&mut buffer, &entry).await.expect("failed to read entry");
+        assert_eq!(buffer, expected);
+    }
+}
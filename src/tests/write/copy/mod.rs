@@ -0,0 +1,102 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::Cursor;
+
+#[tokio::test]
+async fn copy_entry_raw_test() {
+    #[cfg(feature = "deflate")]
+    let compression = Compression::Deflate;
+    #[cfg(not(feature = "deflate"))]
+    let compression = Compression::Stored;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (filename, data) in [("foo.txt", "foo foo foo foo"), ("bar.txt", "bar")] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), compression).comment(format!("{filename} comment"));
+        writer.write_entry_whole(builder, data.as_bytes()).await.expect("failed to write entry");
+    }
+
+    let source = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (stored_entry, filename) in source.file().entries().iter().zip([None, Some("baz.txt".to_string())]) {
+        let reader = Cursor::new(source.data());
+        writer.copy_entry_raw(stored_entry, filename, reader).await.expect("failed to copy entry");
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let mut contents = Vec::new();
+
+    for (index, stored_entry) in reader.file().entries().iter().enumerate() {
+        let entry = stored_entry.entry();
+        assert_eq!(entry.compression(), compression);
+
+        let mut buffer = Vec::new();
+        let mut entry_reader = reader.entry(index).await.unwrap();
+        entry_reader.read_to_end_checked(&mut buffer, entry).await.expect("failed to read entry");
+        contents.push((entry.filename().to_string(), entry.comment().to_string(), String::from_utf8(buffer).unwrap()));
+    }
+
+    assert_eq!(
+        contents,
+        [
+            ("foo.txt".to_string(), "foo.txt comment".to_string(), "foo foo foo foo".to_string()),
+            ("baz.txt".to_string(), "bar.txt comment".to_string(), "bar".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn copy_encrypted_entry_raw_test() {
+    use crate::write::crypto::zip_crypto::{ZipCryptoKeys, ZIP_CRYPTO_HEADER_LENGTH};
+    use crate::Encryption;
+    use tokio::io::AsyncWriteExt;
+
+    let encryption = Encryption::ZipCrypto(b"password".to_vec());
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored).encryption(encryption.clone());
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+
+    // Streamed entries are followed by a data descriptor, and so have a differing password check byte.
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored).encryption(encryption);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"bar baz").await.expect("failed to write entry");
+    entry_writer.close().await.expect("failed to close entry");
+
+    let source = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for stored_entry in source.file().entries() {
+        writer.copy_entry_raw(stored_entry, None, Cursor::new(source.data())).await.expect("failed to copy entry");
+    }
+
+    let data = writer.close().await.expect("failed to close writer");
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    for (index, expected) in [b"foo bar", b"bar baz"].into_iter().enumerate() {
+        let (stored_entry, source_entry) = (&reader.file().entries()[index], &source.file().entries()[index]);
+        assert_eq!(stored_entry.general_purpose_flag.as_slice(), source_entry.general_purpose_flag.as_slice());
+        assert!(stored_entry.general_purpose_flag.encrypted);
+
+        // The local file header's flags must match so that the password check byte is still interpreted correctly.
+        let offset = stored_entry.header_offset() as usize;
+        assert_eq!(reader.data()[offset + 6..offset + 8], source.data()[offset + 6..offset + 8]);
+
+        let encrypted = reader.stored_entry_data(index).await.expect("failed to read entry");
+        let mut keys = ZipCryptoKeys::new(b"password");
+        let decrypted: Vec<u8> = encrypted.iter().map(|byte| keys.decrypt(*byte)).collect();
+        assert_eq!(&decrypted[ZIP_CRYPTO_HEADER_LENGTH..], expected);
+    }
+
+    // The streamed entry's data descriptor is carried over along with its flag.
+    let stored_entry = &reader.file().entries()[1];
+    let entry = stored_entry.entry();
+    let end = stored_entry.header_offset() as usize + 30 + entry.filename().len() + entry.extra_field().len();
+    let end = end + entry.compressed_size() as usize;
+    assert_eq!(reader.data()[end..end + 4], crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes());
+}
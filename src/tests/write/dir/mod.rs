@@ -0,0 +1,40 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn write_dir_entry_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo".to_string(), Compression::Stored);
+    writer.write_dir_entry(builder).await.expect("failed to write directory entry");
+
+    let builder = ZipEntryBuilder::new("foo/bar/".to_string(), Compression::Stored).unix_permissions(0o40700);
+    writer.write_dir_entry(builder).await.expect("failed to write directory entry");
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert_eq!(entries[0].entry().filename(), "foo/");
+    assert!(entries.iter().all(|entry| entry.entry().dir() && entry.entry().uncompressed_size() == 0));
+    assert_eq!(entries[0].entry().unix_permissions(), Some(0o40755));
+    assert_eq!(entries[1].entry().unix_permissions(), Some(0o40700));
+}
+
+#[tokio::test]
+async fn write_symlink_entry_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo/link".to_string(), Compression::Stored);
+    writer.write_symlink_entry(builder, "../bar.txt").await.expect("failed to write symlink entry");
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entry = reader.file().entries()[0].entry();
+
+    assert!(entry.symlink());
+    assert_eq!(entry.unix_permissions(), Some(0o120777));
+    assert_eq!(reader.stored_entry_data(0).await.unwrap(), b"../bar.txt");
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::{ZipEdits, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::Cursor;
+
+#[tokio::test]
+async fn edit_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment("foo bar".to_string());
+
+    for (filename, data) in [("foo.txt", "foo"), ("bar.txt", "bar"), ("baz.txt", "baz"), ("qux.txt", "qux")] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, data.as_bytes()).await.expect("failed to write entry");
+    }
+
+    let source = writer.close().await.expect("failed to close writer");
+    let edits = ZipEdits::new()
+        .remove("foo.txt")
+        .rename("qux.txt", "quux.txt")
+        .replace(ZipEntryBuilder::new("baz.txt".to_string(), Compression::Stored), b"baz2".to_vec())
+        .replace(ZipEntryBuilder::new("new.txt".to_string(), Compression::Stored), b"new".to_vec());
+
+    let data = edits.apply(Cursor::new(source), Vec::new()).await.expect("failed to apply edits");
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let mut contents = Vec::new();
+
+    for (index, stored_entry) in reader.file().entries().iter().enumerate() {
+        let data = reader.stored_entry_data(index).await.unwrap();
+        contents.push(format!("{}={}", stored_entry.entry().filename(), String::from_utf8(data.to_vec()).unwrap()));
+    }
+
+    assert_eq!(contents, ["bar.txt=bar", "baz.txt=baz2", "quux.txt=qux", "new.txt=new"]);
+    assert_eq!(reader.file().comment(), "foo bar");
+}
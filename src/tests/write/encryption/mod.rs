@@ -0,0 +1,154 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::write::crypto::zip_crypto::{ZipCryptoKeys, ZIP_CRYPTO_HEADER_LENGTH};
+use crate::write::ZipFileWriter;
+use crate::{Compression, Encryption, ZipEntryBuilder};
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn zip_crypto_test() {
+    let encryption = Encryption::ZipCrypto(b"password".to_vec());
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored).encryption(encryption.clone());
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored).encryption(encryption);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"bar baz").await.expect("failed to write entry");
+    entry_writer.close().await.expect("failed to close entry");
+
+    let data = writer.close().await.expect("failed to close writer");
+    let reader = ZipFileReader::new(data.clone()).await.expect("failed to open reader");
+
+    // The general purpose flag's encryption bit is held at bit 0 of the local file header's flags.
+    assert_eq!(data[6] & 1, 1);
+
+    for (index, expected) in [b"foo bar", b"bar baz"].into_iter().enumerate() {
+        let entry = reader.file().entries()[index].entry();
+        let encrypted = reader.stored_entry_data(index).await.expect("failed to read entry");
+        assert_eq!(encrypted.len(), expected.len() + ZIP_CRYPTO_HEADER_LENGTH);
+
+        let mut keys = ZipCryptoKeys::new(b"password");
+        let decrypted: Vec<u8> = encrypted.iter().map(|byte| keys.decrypt(*byte)).collect();
+        let (header, decrypted) = decrypted.split_at(ZIP_CRYPTO_HEADER_LENGTH);
+
+        let check_byte = match index {
+            0 => (entry.crc32() >> 24) as u8,
+            _ => (entry.last_modification_date().time >> 8) as u8,
+        };
+
+        assert_eq!(header[ZIP_CRYPTO_HEADER_LENGTH - 1], check_byte);
+        assert_eq!(decrypted, expected);
+    }
+
+    // Decryption isn't supported when reading, so opening the entry should be refused upfront.
+    let err = reader.entry(0).await.err().expect("opened encrypted entry");
+    assert!(matches!(err.kind(), ZipError::FeatureNotSupported("Reading encrypted entries")));
+
+    let stream = crate::read::stream::ZipFileReader::new(data.as_slice());
+    let err = stream.next_entry().await.err().expect("opened encrypted entry");
+    assert!(matches!(err.kind(), ZipError::FeatureNotSupported("Reading encrypted entries")));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_test() {
+    use crate::spec::consts::AES_EXTRA_FIELD_HEADER_ID;
+    use crate::write::crypto::aes::{AES_AUTH_CODE_LENGTH, AES_SALT_LENGTH, AES_VERIFIER_LENGTH, PBKDF2_ITERATIONS};
+
+    use aes::cipher::generic_array::GenericArray;
+    use aes::cipher::{BlockEncrypt, KeyInit};
+    use hmac::Mac;
+
+    let encryption = Encryption::Aes256(b"password".to_vec());
+    let data = b"foo bar ".repeat(8);
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored).encryption(encryption.clone());
+    writer.write_entry_whole(builder, &data).await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored).encryption(encryption);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(&data).await.expect("failed to write entry");
+    entry_writer.close().await.expect("failed to close entry");
+
+    let (archive, summary) = writer.close_with_summary().await.expect("failed to close writer");
+
+    for stored_entry in summary.file().entries() {
+        let entry = stored_entry.entry();
+        let field = crate::spec::extra_field::find_field(entry.extra_field(), AES_EXTRA_FIELD_HEADER_ID).unwrap();
+        assert_eq!(field, [2, 0, b'A', b'E', 3, 0, 0]);
+        assert_eq!(entry.crc32(), 0);
+
+        let start = stored_entry.header_offset() as usize + 30 + entry.filename().len() + entry.extra_field().len();
+        let encrypted = &archive[start..start + entry.compressed_size() as usize];
+
+        let (salt, encrypted) = encrypted.split_at(AES_SALT_LENGTH);
+        let (verifier, encrypted) = encrypted.split_at(AES_VERIFIER_LENGTH);
+        let (encrypted, auth_code) = encrypted.split_at(encrypted.len() - AES_AUTH_CODE_LENGTH);
+
+        let mut derived = [0; 66];
+        pbkdf2::pbkdf2_hmac::<sha1::Sha1>(b"password", salt, PBKDF2_ITERATIONS, &mut derived);
+        assert_eq!(verifier, &derived[64..]);
+
+        let mut hmac = <hmac::Hmac<sha1::Sha1> as Mac>::new_from_slice(&derived[32..64]).unwrap();
+        hmac.update(encrypted);
+        assert_eq!(auth_code, &hmac.finalize().into_bytes()[..AES_AUTH_CODE_LENGTH]);
+
+        let cipher = aes::Aes256::new(GenericArray::from_slice(&derived[..32]));
+        let mut decrypted = encrypted.to_vec();
+
+        for (counter, chunk) in (1u128..).zip(decrypted.chunks_mut(16)) {
+            let mut block = GenericArray::from(counter.to_le_bytes());
+            cipher.encrypt_block(&mut block);
+            chunk.iter_mut().zip(block).for_each(|(byte, key)| *byte ^= key);
+        }
+
+        assert_eq!(decrypted, data);
+    }
+
+    // The archive should also be readable by this crate, even though the entries' data can't be decrypted.
+    let reader = ZipFileReader::new(archive).await.expect("failed to open reader");
+    let filenames: Vec<_> =
+        reader.file().entries().iter().map(|stored_entry| stored_entry.entry().filename()).collect();
+    assert_eq!(filenames, ["foo.txt", "bar.txt"]);
+
+    for (index, stored_entry) in reader.file().entries().iter().enumerate() {
+        assert_eq!(stored_entry.entry().uncompressed_size(), data.len() as u64);
+        let err = reader.entry(index).await.err().expect("opened encrypted entry");
+        assert!(matches!(err.kind(), ZipError::FeatureNotSupported("Reading encrypted entries")));
+    }
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_open_test() {
+    let encryption = Encryption::Aes256(b"password".to_vec());
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored).encryption(encryption);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"bar baz").await.expect("failed to write entry");
+
+    let data = writer.close().await.expect("failed to close writer");
+    let reader = ZipFileReader::new(data).await.expect("failed to open reader");
+
+    // The AES entry's placeholder method is kept, whilst its actual method is taken from the AES extra field.
+    let stored_entry = &reader.file().entries()[0];
+    assert_eq!(stored_entry.compression_method(), crate::spec::consts::AES_COMPRESSION_METHOD);
+    assert_eq!(stored_entry.entry().compression(), Compression::Stored);
+
+    let err = reader.entry(0).await.err().expect("opened encrypted entry");
+    assert!(matches!(err.kind(), ZipError::FeatureNotSupported("Reading encrypted entries")));
+
+    let mut buffer = Vec::new();
+    let mut entry_reader = reader.entry(1).await.expect("failed to open entry");
+    entry_reader.read_to_end_checked(&mut buffer, reader.file().entries()[1].entry()).await.unwrap();
+    assert_eq!(buffer, b"bar baz");
+}
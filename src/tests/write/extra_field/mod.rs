@@ -0,0 +1,68 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ExtraField, ZipEntryBuilder};
+
+#[tokio::test]
+async fn typed_extra_field_test() {
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored)
+        .add_extra_field(ExtraField::ExtendedTimestamp { modified: Some(0x01020304), accessed: None, created: None })
+        .add_extra_field(ExtraField::UnixOwner { uid: 1000, gid: 100 })
+        .add_extra_field(ExtraField::Custom { id: 0xcafe, data: vec![1, 2, 3] });
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+    let data = writer.close().await.expect("failed to close writer");
+
+    #[rustfmt::skip]
+    let expected: &[u8] = &[
+        0x55, 0x54, 5, 0, 0b1, 4, 3, 2, 1,
+        0x75, 0x78, 11, 0, 1, 4, 0xe8, 3, 0, 0, 4, 100, 0, 0, 0,
+        0xfe, 0xca, 3, 0, 1, 2, 3,
+    ];
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].entry().extra_field(), expected);
+
+    // The local file header holds the same fields.
+    let (entry, _) = crate::read::lfh(&reader.data()[4..]).await.expect("failed to read local file header");
+    assert_eq!(entry.extra_field(), expected);
+}
+
+#[tokio::test]
+async fn extended_timestamp_test() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    // 2023-05-17T12:34:57Z
+    let time = UNIX_EPOCH + Duration::from_secs(1684326897);
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored)
+        .last_modification_time(time)
+        .extended_timestamp(true);
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entry = reader.file().entries()[0].entry();
+
+    let date = entry.last_modification_date();
+    assert_eq!((date.year(), date.month(), date.day()), (2023, 5, 17));
+    assert_eq!((date.hour(), date.minute(), date.second()), (12, 34, 56));
+
+    let mut expected = vec![0x55, 0x54, 5, 0, 0b1];
+    expected.extend_from_slice(&1684326897i32.to_le_bytes());
+    assert_eq!(entry.extra_field(), expected);
+}
+
+#[test]
+fn modification_date_from_metadata_test() {
+    let metadata = std::fs::metadata("Cargo.toml").expect("failed to read metadata");
+    let modified = metadata.modified().expect("modification time unsupported");
+
+    let entry =
+        ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored).last_modification_date(&metadata).build();
+    assert_eq!(*entry.last_modification_date(), crate::ZipDateTime::from_system_time(modified));
+    assert!(entry.unix_modification_time.is_some());
+}
@@ -0,0 +1,58 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, FilenamePolicy, Utf8Flag, ZipEntryBuilder};
+
+#[tokio::test]
+async fn filename_policy_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new(String::from("foo\\bar.txt"), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new(String::from("foo\0.txt"), Compression::Stored);
+    let result = writer.write_entry_whole(builder, b"foo bar").await;
+    assert!(matches!(result, Err(ZipError::InvalidFilename(..))));
+
+    // Unsafe paths are only rejected by the strict policy.
+    for filename in ["/etc/passwd", "C:\\Windows\\foo", "foo/../../bar"] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        assert!(
+            builder.filename_policy(FilenamePolicy::strict()).try_build().is_err(),
+            "{filename} should be rejected"
+        );
+    }
+
+    let builder = ZipEntryBuilder::new(String::from("foo/../../bar"), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.expect("failed to open ZIP file");
+    let filenames = reader.file().entries().iter().map(|entry| entry.entry().filename()).collect::<Vec<_>>();
+    assert_eq!(filenames, ["foo/bar.txt", "foo/../../bar"]);
+}
+
+#[tokio::test]
+async fn utf8_flag_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, flag) in [("foo.txt", Utf8Flag::Auto), ("bar.txt", Utf8Flag::Always), ("bäz.txt", Utf8Flag::Auto)] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored).utf8_flag(flag);
+        writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    }
+
+    let builder = ZipEntryBuilder::new(String::from("bäz.txt"), Compression::Stored).utf8_flag(Utf8Flag::Never);
+    let result = writer.write_entry_whole(builder, b"foo bar").await;
+    assert!(matches!(result, Err(ZipError::InvalidFilename(..))));
+
+    let data = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(data.clone()).await.expect("failed to open ZIP file");
+
+    let flags = reader.file().entries().iter().map(|entry| {
+        let offset = entry.header_offset() as usize;
+        u16::from_le_bytes([data[offset + 6], data[offset + 7]]) & 0x800 != 0
+    });
+    assert_eq!(flags.collect::<Vec<_>>(), [false, true, true]);
+}
@@ -0,0 +1,33 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn auto_compression_test() {
+    // A simple LCG is enough to produce bytes which appear incompressible.
+    let mut state = 1u32;
+    let noise: Vec<u8> = (0..16 * 1024)
+        .map(|_| {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 24) as u8
+        })
+        .collect();
+
+    let text = b"foo bar baz ".repeat(1024);
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", &text), ("foo.PNG", &text), ("noise.bin", &noise)] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Deflate).auto_compression(true);
+        writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+    }
+
+    let builder = ZipEntryBuilder::new("bar.zip".to_string(), Compression::Deflate).auto_compression(true);
+    writer.write_entry_from_reader(builder, &noise[..]).await.expect("failed to write entry");
+
+    let (_, summary) = writer.close_with_summary().await.expect("failed to close writer");
+    let methods: Vec<Compression> = summary.file().entries().iter().map(|entry| entry.entry().compression()).collect();
+
+    assert_eq!(methods, [Compression::Deflate, Compression::Stored, Compression::Stored, Compression::Stored]);
+}
@@ -0,0 +1,40 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, CompressionLevel, ZipEntryBuilder};
+
+/// Writes the provided data as a single Deflate entry at the provided level, via both the whole and stream writers.
+async fn write_at_level(data: &[u8], level: CompressionLevel) -> ZipFileReader {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("whole.txt".to_string(), Compression::Deflate).compression_level(level);
+    writer.write_entry_whole(builder, data).await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("stream.txt".to_string(), Compression::Deflate).compression_level(level);
+    writer.write_entry_from_reader(builder, data).await.expect("failed to write entry");
+
+    ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.expect("failed to open ZIP file")
+}
+
+#[tokio::test]
+async fn compression_level_test() {
+    let data: Vec<u8> = (0..100_000u64).map(|value| ((value * value) % 13 + value % 7) as u8).collect();
+
+    let fastest = write_at_level(&data, CompressionLevel::Fastest).await;
+    let best = write_at_level(&data, CompressionLevel::Precise(9)).await;
+
+    for index in 0..2 {
+        let fastest_size = fastest.file().entries()[index].entry().compressed_size();
+        let best_size = best.file().entries()[index].entry().compressed_size();
+        assert!(best_size < fastest_size, "{best_size} isn't smaller than {fastest_size}");
+
+        for reader in [&fastest, &best] {
+            let entry = reader.file().entries()[index].entry().clone();
+            let mut buffer = Vec::new();
+            reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+            assert_eq!(buffer, data);
+        }
+    }
+}
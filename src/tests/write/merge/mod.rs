@@ -0,0 +1,39 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::tests::read::stream::stored_test_zip;
+use crate::write::{merge, ConflictPolicy};
+
+use std::io::Cursor;
+
+#[tokio::test]
+async fn merge_test() {
+    let first = stored_test_zip(&[("foo.txt", b"foo"), ("bar.txt", b"bar")]).await;
+    let second = stored_test_zip(&[("bar.txt", b"qux"), ("baz.txt", b"baz")]).await;
+    let sources = || [Cursor::new(first.clone()), Cursor::new(second.clone())];
+
+    for (policy, expected) in [
+        (ConflictPolicy::KeepFirst, vec![("foo.txt", "foo"), ("bar.txt", "bar"), ("baz.txt", "baz")]),
+        (ConflictPolicy::KeepLast, vec![("foo.txt", "foo"), ("bar.txt", "qux"), ("baz.txt", "baz")]),
+        (ConflictPolicy::KeepAll, vec![("foo.txt", "foo"), ("bar.txt", "bar"), ("bar.txt", "qux"), ("baz.txt", "baz")]),
+    ] {
+        let data = merge(sources(), Vec::new(), policy).await.expect("failed to merge ZIP files");
+        let reader = ZipFileReader::new(data).await.expect("failed to open ZIP file");
+
+        let mut entries = Vec::new();
+        for (index, stored_entry) in reader.file().entries().iter().enumerate() {
+            let mut data = String::new();
+            let mut entry_reader = reader.entry(index).await.expect("failed to open entry");
+            entry_reader.read_to_string_checked(&mut data, stored_entry.entry()).await.expect("failed to read entry");
+            entries.push((stored_entry.entry().filename().to_string(), data));
+        }
+
+        let expected: Vec<_> = expected.into_iter().map(|(name, data)| (name.to_string(), data.to_string())).collect();
+        assert_eq!(entries, expected, "{policy:?}");
+    }
+
+    let result = merge(sources(), Vec::new(), ConflictPolicy::Error).await;
+    assert!(matches!(result, Err(ZipError::DuplicateFilename(filename)) if filename == "bar.txt"));
+}
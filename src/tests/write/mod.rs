@@ -1,4 +1,34 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod abort;
+pub(crate) mod alignment;
+pub(crate) mod attribute;
+#[cfg(feature = "body")]
+pub(crate) mod body;
+pub(crate) mod central_directory;
+pub(crate) mod comment;
+#[cfg(feature = "futures")]
+pub(crate) mod compat;
+pub(crate) mod copy;
+pub(crate) mod dir;
+pub(crate) mod edit;
+pub(crate) mod encryption;
+pub(crate) mod extra_field;
+pub(crate) mod filename;
+#[cfg(feature = "deflate")]
+pub(crate) mod heuristic;
+#[cfg(feature = "deflate")]
+pub(crate) mod level;
+pub(crate) mod merge;
 pub(crate) mod offset;
+#[cfg(feature = "deflate")]
+pub(crate) mod options;
+#[cfg(feature = "parallel")]
+pub(crate) mod parallel;
+pub(crate) mod reader;
+#[cfg(feature = "deflate")]
+pub(crate) mod recompress;
+pub(crate) mod split;
+pub(crate) mod summary;
+pub(crate) mod zip64;
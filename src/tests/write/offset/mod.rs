@@ -20,3 +20,27 @@ async fn basic() {
     writer.write_all(b"Bar. Bar.").await.expect("failed to write data");
     assert_eq!(writer.offset(), 37);
 }
+
+#[tokio::test]
+async fn zip_file_writer_offset_test() {
+    use crate::read::mem::ZipFileReader;
+    use crate::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let mut offsets = Vec::new();
+
+    for filename in ["foo.txt", "bar.txt"] {
+        offsets.push(writer.offset());
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, b"foo bar").await.expect("failed to write entry");
+    }
+
+    let cd_offset = writer.offset();
+    let data = writer.close().await.expect("failed to close writer");
+    let reader = ZipFileReader::new(data).await.expect("failed to open reader");
+
+    let header_offsets: Vec<u64> = reader.file().entries().iter().map(|entry| entry.header_offset()).collect();
+    assert_eq!(header_offsets, offsets);
+    assert_eq!(&reader.data()[cd_offset as usize..cd_offset as usize + 4], b"PK\x01\x02");
+}
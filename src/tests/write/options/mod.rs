@@ -0,0 +1,63 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::{EntryOptions, WriterOptions, ZipFileWriter};
+use crate::{Compression, Utf8Flag, ZipEntryBuilder};
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn writer_options_test() {
+    let data = b"The quick brown fox jumps over the lazy dog. The quick brown fox jumps over the lazy dog.";
+    let options = WriterOptions::new().default_compression(Compression::Deflate).utf8_flag(Utf8Flag::Never);
+    let mut writer = ZipFileWriter::with_options(Vec::new(), options.buffer_size(16));
+
+    let builder = writer.entry_builder("inherited.txt".to_string());
+    writer.write_entry_from_reader(builder, &data[..]).await.unwrap();
+
+    // Options set on the entry itself take precedence over the archive-wide defaults.
+    let builder = ZipEntryBuilder::new("ü.txt".to_string(), Compression::Stored).utf8_flag(Utf8Flag::Always);
+    writer.write_entry_whole(builder, data).await.unwrap();
+
+    let builder = writer.entry_builder("ü-inherited.txt".to_string());
+    assert!(writer.write_entry_whole(builder, data).await.is_err());
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entries = reader.file().entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].entry().compression(), Compression::Deflate);
+    assert_eq!(entries[1].entry().compression(), Compression::Stored);
+
+    for (index, stored_entry) in entries.iter().enumerate() {
+        let entry = stored_entry.entry().clone();
+        let mut buffer = Vec::new();
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+        assert_eq!(buffer, data);
+    }
+}
+
+#[tokio::test]
+async fn entry_options_test() {
+    let options = EntryOptions::new().compression(Compression::Stored).alignment(4096).comment("foo".to_string());
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("whole.txt".to_string(), Compression::Deflate);
+    writer.write_entry_whole_with_options(builder, b"foo bar", options.clone()).await.unwrap();
+
+    let builder = ZipEntryBuilder::new("stream.txt".to_string(), Compression::Deflate);
+    let mut entry_writer = writer.write_entry_stream_with_options(builder, options.force_zip64(true)).await.unwrap();
+    entry_writer.write_all(b"foo bar").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    for (index, stored_entry) in reader.file().entries().iter().enumerate() {
+        assert_eq!(stored_entry.entry().compression(), Compression::Stored);
+        assert_eq!(stored_entry.entry().comment(), "foo");
+
+        let data = reader.stored_entry_data(index).await.unwrap();
+        assert_eq!(data, b"foo bar");
+        assert_eq!((data.as_ptr() as usize - reader.data().as_ptr() as usize) % 4096, 0);
+    }
+}
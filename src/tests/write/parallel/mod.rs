@@ -0,0 +1,100 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipDateTime, ZipEntry, ZipEntryBuilder};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn write_entries_parallel_test() {
+    #[cfg(feature = "deflate")]
+    let compression = Compression::Deflate;
+    #[cfg(not(feature = "deflate"))]
+    let compression = Compression::Stored;
+
+    let entries: Vec<(ZipEntry, Vec<u8>)> = (0..32u32)
+        .map(|index| {
+            let data: Vec<u8> = (0..index * 1024).map(|value| (value % 13) as u8).collect();
+            let builder = ZipEntryBuilder::new(format!("{index}.bin"), compression)
+                .last_modification_date(ZipDateTime::default());
+            (builder.build(), data)
+        })
+        .collect();
+
+    let mut serial = ZipFileWriter::new(Vec::new());
+    for (entry, data) in entries.clone() {
+        serial.write_entry_whole(entry, &data).await.expect("failed to write entry");
+    }
+
+    let mut parallel = ZipFileWriter::new(Vec::new());
+    parallel.write_entries_parallel(entries, 4).await.expect("failed to write entries");
+
+    let serial = serial.close().await.expect("failed to close writer");
+    let parallel = parallel.close().await.expect("failed to close writer");
+    assert_eq!(serial, parallel);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn write_entries_deferred_test() {
+    let entries: Vec<(ZipEntry, Vec<u8>)> = (0..16u32)
+        .map(|index| {
+            let data: Vec<u8> = (0..index * 1024).map(|value| (value % 7) as u8).collect();
+            let builder = ZipEntryBuilder::new(format!("{index}.bin"), Compression::Stored)
+                .last_modification_date(ZipDateTime::default());
+            (builder.build(), data)
+        })
+        .collect();
+
+    let mut serial = ZipFileWriter::new(Vec::new());
+    for (entry, data) in entries.clone() {
+        serial.write_entry_whole(entry, &data).await.expect("failed to write entry");
+    }
+
+    let producers =
+        entries.into_iter().map(|(entry, data)| (entry, move || async move { Ok(std::io::Cursor::new(data)) }));
+
+    let mut deferred = ZipFileWriter::new(Vec::new());
+    deferred.write_entries_deferred(producers, 4).await.expect("failed to write entries");
+
+    let serial = serial.close().await.expect("failed to close writer");
+    let deferred = deferred.close().await.expect("failed to close writer");
+    assert_eq!(serial, deferred);
+
+    let builder = ZipEntryBuilder::new("missing.bin".to_string(), Compression::Stored);
+    let producer = || async { Err::<std::io::Cursor<Vec<u8>>, _>(std::io::ErrorKind::NotFound.into()) };
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let result = writer.write_entries_deferred([(builder, producer)], 4).await;
+    assert!(matches!(result, Err(crate::error::ZipError::UpstreamReadError(_))));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn write_entries_deferred_abort_test() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let finished = Arc::new(AtomicBool::new(false));
+    let producers = (0..2).map(|index| {
+        let builder = ZipEntryBuilder::new(format!("{index}.bin"), Compression::Stored);
+        let finished = finished.clone();
+
+        let producer = move || async move {
+            if index == 0 {
+                return Err(std::io::ErrorKind::NotFound.into());
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            finished.store(true, Ordering::SeqCst);
+            Ok(std::io::Cursor::new(Vec::new()))
+        };
+
+        (builder, producer)
+    });
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    assert!(writer.write_entries_deferred(producers, 2).await.is_err());
+
+    // The second entry's producer is aborted rather than left running once the first entry has failed.
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(!finished.load(Ordering::SeqCst));
+}
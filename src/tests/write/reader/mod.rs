@@ -0,0 +1,109 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn write_entry_from_reader_test() {
+    let data: Vec<u8> = (0..200_000u32).map(|value| (value % 251) as u8).collect();
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.bin".to_string(), Compression::Stored);
+    let written = writer.write_entry_from_reader(builder, &data[..]).await.expect("failed to write entry");
+    assert_eq!(written, data.len() as u64);
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entry = reader.file().entries()[0].entry().clone();
+    assert_eq!(entry.uncompressed_size() as usize, data.len());
+
+    let mut buffer = Vec::new();
+    reader.entry(0).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.expect("failed to read entry");
+    assert_eq!(buffer, data);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn non_seekable_sink_test() {
+    use tokio::io::AsyncReadExt;
+
+    // A pipe can't be seeked, so the output is consumed concurrently by the stream reader as it's written.
+    let (sink, source) = tokio::io::duplex(1024);
+
+    let write = async move {
+        let mut writer = ZipFileWriter::new(sink);
+
+        for (filename, data) in [("foo.txt", "foo bar"), ("bar.txt", "bar")] {
+            let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Deflate);
+            writer.write_entry_from_reader(builder, data.as_bytes()).await.expect("failed to write entry");
+        }
+
+        writer.close().await.expect("failed to close writer");
+    };
+
+    let read = async move {
+        let mut zip = crate::read::stream::ZipFileReader::new(source);
+        let mut contents = Vec::new();
+
+        while let Some(mut entry) = zip.next_entry().await.expect("failed to read local file header") {
+            let mut buffer = String::new();
+            entry.reader().read_to_string(&mut buffer).await.expect("failed to read entry");
+            contents.push(buffer);
+            zip = entry.done().await.expect("failed to finish entry");
+        }
+
+        contents
+    };
+
+    let ((), contents) = tokio::join!(write, read);
+    assert_eq!(contents, ["foo bar", "bar"]);
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn entry_sink_test() {
+    use bytes::Bytes;
+    use futures::{stream, StreamExt};
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    let mut sink = writer.write_entry_stream(builder).await.expect("failed to open entry").into_sink();
+
+    let chunks = ["foo ", "bar ", "baz"].map(|chunk| Bytes::from_static(chunk.as_bytes()));
+    stream::iter(chunks).map(Ok).forward(&mut sink).await.expect("failed to forward stream");
+    sink.close().await.expect("failed to close entry");
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let entry = reader.file().entries()[0].entry().clone();
+
+    let mut buffer = Vec::new();
+    reader.entry(0).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.expect("failed to read entry");
+    assert_eq!(buffer, b"foo bar baz");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn entry_stream_flush_test() {
+    use futures::FutureExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (sink, mut source) = tokio::io::duplex(64 * 1024);
+    let mut buffer = vec![0; 64 * 1024];
+
+    let mut writer = ZipFileWriter::new(sink);
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Deflate);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+
+    // Drain the local file header so that only compressed data remains to be read.
+    source.read(&mut buffer).now_or_never().expect("header not written").unwrap();
+
+    entry_writer.write_all(b"foo bar").await.expect("failed to write data");
+    assert!(source.read(&mut buffer).now_or_never().is_none());
+
+    entry_writer.flush().await.expect("failed to flush entry");
+    let read = source.read(&mut buffer).now_or_never().expect("data not flushed").unwrap();
+    assert!(read > 0);
+
+    entry_writer.close().await.expect("failed to close entry");
+}
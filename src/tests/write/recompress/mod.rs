@@ -0,0 +1,41 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, CompressionLevel, ZipEntryBuilder};
+
+use std::io::Cursor;
+
+#[tokio::test]
+async fn recompress_test() {
+    let data = b"foo bar foo bar foo bar foo bar foo bar foo bar foo bar foo bar".repeat(64);
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    writer.write_dir_entry(ZipEntryBuilder::new("dir".to_string(), Compression::Stored)).await.unwrap();
+    for (filename, compression) in [("stored.txt", Compression::Stored), ("deflate.txt", Compression::Deflate)] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), compression).comment(filename.to_string());
+        writer.write_entry_whole(builder, &data).await.unwrap();
+    }
+
+    writer.comment("foo".to_string());
+    let input = Cursor::new(writer.close().await.unwrap());
+
+    let output = crate::write::recompress(input, Vec::new(), Compression::Deflate, CompressionLevel::Best).await;
+    let reader = ZipFileReader::new(output.unwrap()).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert_eq!(reader.file().comment(), "foo");
+    assert_eq!(entries[0].entry().compression(), Compression::Stored);
+    assert!(entries[1].entry().compressed_size() < data.len() as u64);
+
+    for (index, stored_entry) in entries.iter().enumerate().skip(1) {
+        let entry = stored_entry.entry().clone();
+        assert_eq!(entry.compression(), Compression::Deflate);
+        assert_eq!(entry.comment(), entry.filename());
+
+        let mut buffer = Vec::new();
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+        assert_eq!(buffer, data);
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::consts::{CDH_LENGTH, CDH_SIGNATURE, EOCDR_LENGTH, EOCDR_SIGNATURE, LFH_SIGNATURE};
+use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader};
+use crate::write::split::{SplitZipFileWriter, MIN_VOLUME_SIZE};
+use crate::{Compression, ZipEntryBuilder};
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+/// A volume which appends its data to a shared list of volumes.
+struct Volume(Arc<Mutex<Vec<Vec<u8>>>>, usize);
+
+impl AsyncWrite for Volume {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, b: &[u8]) -> Poll<std::io::Result<usize>> {
+        self.0.lock().unwrap()[self.1].extend_from_slice(b);
+        Poll::Ready(Ok(b.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn read_u32(data: &[u8]) -> u32 {
+    u32::from_le_bytes(data[..4].try_into().unwrap())
+}
+
+#[tokio::test]
+async fn split_test() {
+    let volumes = Arc::new(Mutex::new(Vec::new()));
+    let factory = {
+        let volumes = volumes.clone();
+        move |index: u32| {
+            volumes.lock().unwrap().push(Vec::new());
+            Ok(Volume(volumes.clone(), index as usize))
+        }
+    };
+
+    let mut writer = SplitZipFileWriter::new(MIN_VOLUME_SIZE, factory).await.expect("failed to create writer");
+    for (filename, length) in [("foo.bin", 100 * 1024), ("bar.bin", 10), ("baz.bin", 50 * 1024)] {
+        let builder = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+        writer.write_entry_whole(builder, &vec![0xAB; length]).await.expect("failed to write entry");
+    }
+    assert_eq!(writer.close().await.expect("failed to close writer"), 3);
+
+    let volumes = volumes.lock().unwrap();
+    assert_eq!(volumes.len(), 3);
+    assert!(volumes.iter().all(|volume| volume.len() as u64 <= MIN_VOLUME_SIZE));
+    assert_eq!(read_u32(&volumes[0]), crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE);
+
+    // The end of central directory record is held within the final volume, which it identifies.
+    let last = volumes.last().unwrap();
+    let eocdr_offset = last.len() - EOCDR_LENGTH - 4;
+    assert_eq!(read_u32(&last[eocdr_offset..]), EOCDR_SIGNATURE);
+    let eocdr = EndOfCentralDirectoryHeader::from(<[u8; EOCDR_LENGTH]>::try_from(&last[eocdr_offset + 4..]).unwrap());
+    assert_eq!(eocdr.disk_num, 2);
+    assert_eq!(eocdr.num_of_entries, 3);
+
+    // Every central directory record points to a local file header within the volume it names.
+    let cd_volume = &volumes[eocdr.start_cent_dir_disk as usize];
+    let mut offset = eocdr.cent_dir_offset as usize;
+    let mut disks = Vec::new();
+
+    for _ in 0..3 {
+        assert_eq!(read_u32(&cd_volume[offset..]), CDH_SIGNATURE);
+        let record = &cd_volume[offset + 4..offset + 4 + CDH_LENGTH];
+        let record = CentralDirectoryRecord::from(<[u8; CDH_LENGTH]>::try_from(record).unwrap());
+
+        let lfh_volume = &volumes[record.disk_start as usize];
+        assert_eq!(read_u32(&lfh_volume[record.lh_offset as usize..]), LFH_SIGNATURE);
+
+        disks.push(record.disk_start);
+        offset += 4 + CDH_LENGTH + record.file_name_length as usize + record.extra_field_length as usize;
+    }
+
+    assert_eq!(disks, [0, 1, 1]);
+}
+
+#[tokio::test]
+async fn split_volume_error_test() {
+    let volumes = Arc::new(Mutex::new(Vec::new()));
+    let factory = {
+        let volumes = volumes.clone();
+        move |index: u32| match index {
+            0 => {
+                volumes.lock().unwrap().push(Vec::new());
+                Ok(Volume(volumes.clone(), 0))
+            }
+            _ => Err(std::io::ErrorKind::StorageFull.into()),
+        }
+    };
+
+    let mut writer = SplitZipFileWriter::new(MIN_VOLUME_SIZE, factory).await.expect("failed to create writer");
+    let builder = ZipEntryBuilder::new("foo.bin".to_string(), Compression::Stored);
+    assert!(writer.write_entry_whole(builder, &vec![0xAB; 100 * 1024]).await.is_err());
+
+    // Once a volume couldn't be opened, every further operation fails rather than spinning or panicking.
+    let builder = ZipEntryBuilder::new("bar.bin".to_string(), Compression::Stored);
+    assert!(writer.write_entry_whole(builder, b"bar").await.is_err());
+    assert!(writer.close().await.is_err());
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+#[tokio::test]
+async fn close_with_summary_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored);
+    writer.write_entry_from_reader(builder, &b"bar bar"[..]).await.expect("failed to write entry");
+
+    let (data, summary) = writer.close_with_summary().await.expect("failed to close writer");
+    assert_eq!(summary.total_bytes(), data.len() as u64);
+
+    // The summary should match what's then read back from the ZIP file.
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let written = summary.file().entries().iter().zip(reader.file().entries());
+    assert_eq!(summary.file().entries().len(), 2);
+
+    for (written, read) in written {
+        assert_eq!(written.header_offset(), read.header_offset());
+        assert_eq!(written.entry().crc32(), read.entry().crc32());
+        assert_eq!(written.entry().compressed_size(), read.entry().compressed_size());
+        assert_eq!(written.entry().uncompressed_size(), read.entry().uncompressed_size());
+    }
+}
+
+#[tokio::test]
+async fn stream_finish_test() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+
+    for chunk in [&b"bar "[..], b"bar ", b"bar"] {
+        entry_writer.write_all(chunk).await.expect("failed to write chunk");
+    }
+
+    let written = entry_writer.finish().await.expect("failed to finish entry");
+    assert_eq!(written.uncompressed_size(), 11);
+    assert_eq!(written.compressed_size(), 11);
+    assert_eq!(written.crc32(), crc32fast::hash(b"bar bar bar"));
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    let stored_entry = &reader.file().entries()[1];
+    assert_eq!(stored_entry.header_offset(), written.header_offset());
+    assert_eq!(stored_entry.entry().crc32(), written.crc32());
+
+    let data = reader.stored_entry_data(1).await.expect("failed to read entry");
+    assert_eq!(data.as_ptr() as usize - reader.data().as_ptr() as usize, written.data_offset() as usize);
+}
+
+/// A writer which records whether it has been flushed & shut down.
+#[derive(Default)]
+struct TrackingWriter {
+    data: Vec<u8>,
+    flushed: bool,
+    shutdown: bool,
+}
+
+impl AsyncWrite for TrackingWriter {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        this.data.extend_from_slice(buf);
+        this.flushed = false;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().flushed = true;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().shutdown = true;
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn close_and_shutdown_test() {
+    let mut writer = ZipFileWriter::new(TrackingWriter::default());
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"foo").await.expect("failed to write entry");
+
+    let inner = writer.close().await.expect("failed to close writer");
+    assert!(inner.flushed && !inner.shutdown);
+
+    let writer = ZipFileWriter::new(TrackingWriter::default());
+    let (inner, total_bytes) = writer.close_and_shutdown().await.expect("failed to close writer");
+    assert!(inner.shutdown);
+    assert_eq!(total_bytes, inner.data.len() as u64);
+}
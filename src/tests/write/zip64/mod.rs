@@ -0,0 +1,175 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::consts::NON_ZIP64_MAX_SIZE;
+use crate::spec::header::{CentralDirectoryRecord, GeneralPurposeFlag};
+use crate::write::{CentralDirectoryEntry, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn zip64_entry_count_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for index in 0..70_000 {
+        let builder = ZipEntryBuilder::new(format!("{index}.txt"), Compression::Stored);
+        writer.write_entry_whole(builder, b"a").await.expect("failed to write entry");
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.expect("failed to close writer")).await.unwrap();
+    assert!(reader.file().zip64());
+    assert_eq!(reader.file().entries().len(), 70_000);
+    assert_eq!(reader.file().entries()[69_999].entry().filename(), "69999.txt");
+}
+
+#[tokio::test]
+async fn zip64_cd_record_test() {
+    let header = CentralDirectoryRecord {
+        v_made_by: 0,
+        v_needed: 10,
+        flags: GeneralPurposeFlag {
+            data_descriptor: false,
+            encrypted: false,
+            filename_unicode: false,
+            patched: false,
+            strong_encryption: false,
+            masked_header: false,
+        },
+        compression: 0,
+        mod_time: 0,
+        mod_date: 0,
+        crc: 0,
+        compressed_size: 0,
+        uncompressed_size: 0,
+        file_name_length: 7,
+        extra_field_length: 0,
+        file_comment_length: 0,
+        disk_start: 0,
+        inter_attr: 0,
+        exter_attr: 0,
+        lh_offset: 0,
+    };
+    let entry = ZipEntryBuilder::new("foo.bin".to_string(), Compression::Stored).build();
+    let sizes = (5 * 1024 * 1024 * 1024, 1024);
+    let cd_entry = CentralDirectoryEntry::new(header, entry, sizes, NON_ZIP64_MAX_SIZE.into());
+
+    // Only the out-of-range uncompressed size & the sentinel-valued offset should be promoted.
+    assert_eq!(cd_entry.header.compressed_size, 1024);
+    assert_eq!(cd_entry.extra_field.len(), 4 + 16);
+
+    let mut buffer = crate::spec::consts::CDH_SIGNATURE.to_le_bytes().to_vec();
+    buffer.extend(cd_entry.header.as_slice());
+    buffer.extend(b"foo.bin");
+    buffer.extend(&cd_entry.extra_field);
+
+    let stored_entry = crate::read::cd_record(&buffer[..]).await.expect("failed to read record");
+    assert_eq!(stored_entry.entry().uncompressed_size(), sizes.0);
+    assert_eq!(stored_entry.entry().compressed_size(), sizes.1);
+    assert_eq!(stored_entry.file_offset, u64::from(NON_ZIP64_MAX_SIZE));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn force_zip64_test() {
+    use crate::write::Zip64Policy;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let builder = ZipEntryBuilder::new("foo.txt".to_string(), Compression::Deflate).force_zip64(true);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+    entry_writer.write_all(b"foo bar").await.expect("failed to write entry");
+    entry_writer.close().await.expect("failed to close entry");
+
+    writer.zip64_policy(Zip64Policy::Always);
+    let builder = ZipEntryBuilder::new("bar.txt".to_string(), Compression::Stored);
+    writer.write_entry_whole(builder, b"bar").await.expect("failed to write entry");
+
+    let data = writer.close().await.expect("failed to close writer");
+
+    // The stream reader must parse the 64-bit data descriptor in order to verify the CRC32 value & reach the next entry.
+    let mut zip = crate::read::stream::ZipFileReader::new(&data[..]);
+    let mut contents = Vec::new();
+
+    while let Some(mut entry) = zip.next_entry().await.expect("failed to read local file header") {
+        let mut buffer = String::new();
+        entry.reader().read_to_string(&mut buffer).await.expect("failed to read entry");
+        contents.push(buffer);
+        zip = entry.close().await.expect("failed to close entry");
+    }
+
+    assert_eq!(contents, ["foo bar", "bar"]);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    for (stored_entry, size) in reader.file().entries().iter().zip([7, 3]) {
+        assert_eq!(stored_entry.entry().uncompressed_size(), size);
+    }
+}
+
+/// A writer which discards all data except for the bytes written past a given offset.
+struct TailWriter {
+    offset: u64,
+    retain_from: u64,
+    tail: Vec<u8>,
+}
+
+impl tokio::io::AsyncWrite for TailWriter {
+    fn poll_write(
+        mut self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        let skip = self.retain_from.saturating_sub(self.offset).min(buf.len() as u64) as usize;
+        self.tail.extend_from_slice(&buf[skip..]);
+        self.offset += buf.len() as u64;
+        std::task::Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn zip64_stream_data_descriptor_test() {
+    use tokio::io::AsyncWriteExt;
+
+    let chunk = vec![0; 1024 * 1024];
+    let size = (NON_ZIP64_MAX_SIZE as u64 + 1) + chunk.len() as u64;
+
+    let mut writer = ZipFileWriter::new(TailWriter { offset: 0, retain_from: size, tail: Vec::new() });
+    let builder = ZipEntryBuilder::new("foo.bin".to_string(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.expect("failed to open entry");
+
+    for _ in 0..size / chunk.len() as u64 {
+        entry_writer.write_all(&chunk).await.expect("failed to write entry");
+    }
+
+    entry_writer.close().await.expect("failed to close entry");
+    let (sink, summary) = writer.close_with_summary().await.expect("failed to close writer");
+
+    let entry = summary.file().entries()[0].entry();
+    assert_eq!(entry.uncompressed_size(), size);
+
+    // The data descriptor directly precedes the central directory, and must hold 8-byte sizes.
+    let cd_offset = (summary.central_directory_offset() - sink.retain_from) as usize;
+    let descriptor = &sink.tail[cd_offset - 24..cd_offset];
+    assert_eq!(descriptor[8..16], size.to_le_bytes());
+    assert_eq!(descriptor[16..24], size.to_le_bytes());
+
+    // The central directory record must point to a ZIP64 extra field which holds the actual sizes.
+    let record = &sink.tail[cd_offset..];
+    let extra_field_offset = 46 + u16::from_le_bytes([record[28], record[29]]) as usize;
+    assert_eq!(record[extra_field_offset..extra_field_offset + 2], [1, 0]);
+    assert_eq!(u16::from_le_bytes([record[6], record[7]]), crate::spec::version::ZIP64_VERSION_NEEDED);
+}
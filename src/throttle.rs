@@ -0,0 +1,163 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Capping the rate at which data is read or written (eg. for background backup jobs).
+//!
+//! A [`Throttled`] reader or writer paces the data passed through it to a maximum number of bytes per second, sleeping
+//! between operations whenever it gets ahead. Entry readers are throttled via [`ZipEntryReader::throttled()`], and the
+//! archive writer via [`ZipFileWriter::throttled()`]. Note that this requires the `time` feature.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::{Compression, ZipEntryBuilder};
+//! # use async_zip::error::Result;
+//! # use async_zip::write::ZipFileWriter;
+//! # use tokio::fs::File;
+//! #
+//! # async fn run() -> Result<()> {
+//! // Write at most 1MiB per second.
+//! let mut writer = ZipFileWriter::throttled(File::create("./foo.zip").await?, 1024 * 1024);
+//!
+//! let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+//! writer.write_entry_whole(entry, b"foo").await?;
+//! writer.close().await?;
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::read::io::entry::ZipEntryReader;
+use crate::write::ZipFileWriter;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::time::{Instant, Sleep};
+
+/// A reader or writer which caps the rate of data read or written through it (in bytes per second).
+pub struct Throttled<T> {
+    inner: T,
+    bytes_per_second: u64,
+    start: Option<Instant>,
+    transferred: u64,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<T> Throttled<T> {
+    /// Constructs a new wrapper around the provided reader or writer and max rate.
+    ///
+    /// # Panics
+    /// Panics if the rate is zero.
+    pub fn new(inner: T, bytes_per_second: u64) -> Self {
+        assert!(bytes_per_second > 0, "throttled rate must be greater than zero");
+        Self { inner, bytes_per_second, start: None, transferred: 0, sleep: None }
+    }
+
+    /// Returns a reference to the inner reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader or writer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper and returns the inner reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Waits until the data transferred so far is within the max rate, and returns the max length of the next operation.
+    fn poll_ready(&mut self, c: &mut Context<'_>) -> Poll<usize> {
+        if let Some(sleep) = self.sleep.as_mut() {
+            ready!(sleep.as_mut().poll(c));
+            self.sleep = None;
+        }
+
+        self.start.get_or_insert_with(Instant::now);
+        // A single operation never transfers more than a second's worth of data, so pacing stays reasonably smooth.
+        Poll::Ready(usize::try_from(self.bytes_per_second).unwrap_or(usize::MAX))
+    }
+
+    /// Records a completed operation, and schedules a sleep if the data transferred has got ahead of the max rate.
+    fn record(&mut self, length: usize) {
+        self.transferred += length as u64;
+
+        let start = self.start.expect("throttle started before recording");
+        let deadline = start + Duration::from_secs_f64(self.transferred as f64 / self.bytes_per_second as f64);
+
+        if deadline > Instant::now() {
+            self.sleep = Some(Box::pin(tokio::time::sleep_until(deadline)));
+        }
+    }
+}
+
+impl<T> AsyncRead for Throttled<T>
+where
+    T: AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let limit = std::cmp::min(ready!(this.poll_ready(c)), b.remaining());
+
+        let mut limited = ReadBuf::new(&mut b.initialize_unfilled()[..limit]);
+        ready!(Pin::new(&mut this.inner).poll_read(c, &mut limited))?;
+
+        let read = limited.filled().len();
+        b.advance(read);
+        this.record(read);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> AsyncWrite for Throttled<T>
+where
+    T: AsyncWrite + Unpin,
+{
+    fn poll_write(self: Pin<&mut Self>, c: &mut Context<'_>, b: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let limit = std::cmp::min(ready!(this.poll_ready(c)), b.len());
+
+        let written = ready!(Pin::new(&mut this.inner).poll_write(c, &b[..limit]))?;
+        this.record(written);
+
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(c)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(c)
+    }
+}
+
+impl<'a, R> ZipEntryReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Caps the rate at which this entry's data may be read (in uncompressed bytes per second).
+    ///
+    /// # Panics
+    /// Panics if the rate is zero.
+    pub fn throttled(self, bytes_per_second: u64) -> Throttled<Self> {
+        Throttled::new(self, bytes_per_second)
+    }
+}
+
+impl<W: AsyncWrite + Unpin> ZipFileWriter<Throttled<W>> {
+    /// Constructs a new ZIP file writer which caps the rate at which data is written to the provided writer (in bytes
+    /// per second).
+    ///
+    /// # Panics
+    /// Panics if the rate is zero.
+    pub fn throttled(writer: W, bytes_per_second: u64) -> Self {
+        ZipFileWriter::new(Throttled::new(writer, bytes_per_second))
+    }
+}
@@ -1,4 +1,6 @@
 use crate::error::{Result, ZipError};
+#[cfg(any(feature = "fs", feature = "futures-fs"))]
+use std::path::{Component, Path, PathBuf};
 use tokio::io::{AsyncRead, AsyncReadExt};
 
 // Assert that the next four-byte signature read by a reader which impls AsyncRead matches the expected signature.
@@ -8,3 +10,51 @@ pub(crate) async fn assert_signature<R: AsyncRead + Unpin>(reader: &mut R, expec
         actual => Err(ZipError::UnexpectedHeaderError(actual, expected)),
     }
 }
+
+/// Returns whether the provided path is macOS metadata (ie. within a `__MACOSX/` directory, a `.DS_Store` file, or an
+/// AppleDouble `._*` file), which Finder's archiver adds alongside the actual content.
+pub(crate) fn is_macos_metadata(path: &str) -> bool {
+    path.split(['/', '\\'])
+        .any(|component| component == "__MACOSX" || component == ".DS_Store" || component.starts_with("._"))
+}
+
+/// Returns a relative path built from an entry's filename, or an error if the path would escape its destination.
+///
+/// Unsafe components aren't silently stripped. Absolute paths, Windows drive letters, and any ".." component which
+/// climbs above the root of the destination are all rejected. Backslashes are treated as path separators.
+#[cfg(any(feature = "fs", feature = "futures-fs"))]
+pub(crate) fn safe_path(filename: &str) -> Result<PathBuf> {
+    resolve_path(PathBuf::new(), filename).ok_or_else(|| ZipError::UnsafeEntryPath(filename.to_string()))
+}
+
+/// Returns an error if a symlink at the provided relative path, pointing to the provided target, would resolve to a
+/// location outside of its destination.
+#[cfg(feature = "fs")]
+pub(crate) fn check_symlink_target(link: &Path, target: &str) -> Result<()> {
+    let parent = link.parent().map(Path::to_path_buf).unwrap_or_default();
+    resolve_path(parent, target).map(|_| ()).ok_or_else(|| ZipError::UnsafeEntryPath(target.to_string()))
+}
+
+/// Resolves a path relative to a base path, returning None if the result wouldn't be contained within the base's root.
+#[cfg(any(feature = "fs", feature = "futures-fs"))]
+fn resolve_path(mut base: PathBuf, path: &str) -> Option<PathBuf> {
+    let normalised = path.replace('\\', "/");
+
+    // Drive letters aren't parsed as prefix components on non-Windows targets, so they're checked for explicitly.
+    if let [letter, b':', ..] = normalised.as_bytes() {
+        if letter.is_ascii_alphabetic() {
+            return None;
+        }
+    }
+
+    for component in Path::new(&normalised).components() {
+        match component {
+            Component::Normal(part) => base.push(part),
+            Component::CurDir => (),
+            Component::ParentDir if base.pop() => (),
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    Some(base)
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A [`Stream`] of [`Bytes`] which yields a ZIP file as it's written (eg. for use as a HTTP response body).
+//!
+//! The provided closure is run within a spawned task and writes the ZIP file's entries. Its output is split into
+//! chunks which are passed to the stream through a bounded channel, so the whole ZIP file is never held in memory and
+//! the writer is paused whilst the consumer (eg. a slow client) catches up.
+//!
+//! Note that this requires the `body` feature, and the stream must be created within a tokio runtime.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::{Compression, ZipEntryBuilder};
+//! # use async_zip::write::body::ZipBodyStream;
+//! #
+//! let body = ZipBodyStream::new(|mut writer| async move {
+//!     let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+//!     writer.write_entry_whole(entry, b"foo").await?;
+//!     Ok(writer)
+//! });
+//!
+//! // Hand `body` to a web framework as a streamed response body.
+//! ```
+
+use crate::error::Result;
+use crate::write::ZipFileWriter;
+
+use std::future::Future;
+use std::io::ErrorKind;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::Stream;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc::{self, error::SendError, OwnedPermit, Receiver, Sender};
+use tokio::task::JoinHandle;
+
+/// The size at which written data is passed to the stream as a chunk, equal to 64KiB.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The number of chunks which may be buffered within the channel by default.
+const DEFAULT_CHANNEL_CAPACITY: usize = 8;
+
+type Reserve = Pin<Box<dyn Future<Output = std::result::Result<OwnedPermit<Result<Bytes>>, SendError<()>>> + Send>>;
+
+/// A [`Stream`] of [`Bytes`] which yields a ZIP file as it's written by a spawned task.
+///
+/// Dropping the stream aborts the task (eg. if a client disconnects part way through a download).
+pub struct ZipBodyStream {
+    receiver: Receiver<Result<Bytes>>,
+    task: JoinHandle<()>,
+}
+
+impl ZipBodyStream {
+    /// Spawns a task which writes a ZIP file via the provided closure, and returns a stream over its output.
+    ///
+    /// The closure is given a new [`ZipFileWriter`] and should return it once all entries have been written, after
+    /// which it's closed by the task. Any error returned is yielded as the stream's final item.
+    pub fn new<F, Fut>(write: F) -> ZipBodyStream
+    where
+        F: FnOnce(ZipFileWriter<BodyWriter>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<ZipFileWriter<BodyWriter>>> + Send + 'static,
+    {
+        Self::with_capacity(DEFAULT_CHANNEL_CAPACITY, write)
+    }
+
+    /// Spawns a task which writes a ZIP file via the provided closure, buffering up to the provided number of chunks.
+    ///
+    /// See [`ZipBodyStream::new()`] for the closure's requirements.
+    ///
+    /// # Panics
+    /// Panics if the capacity is zero.
+    pub fn with_capacity<F, Fut>(capacity: usize, write: F) -> ZipBodyStream
+    where
+        F: FnOnce(ZipFileWriter<BodyWriter>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<ZipFileWriter<BodyWriter>>> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(capacity);
+        let writer = ZipFileWriter::new(BodyWriter { sender: sender.clone(), buffer: BytesMut::new(), reserve: None });
+
+        let task = tokio::spawn(async move {
+            let written = async {
+                let mut body = write(writer).await?.close().await?;
+                body.shutdown().await?;
+                Ok(())
+            };
+
+            if let Err(err) = written.await {
+                // If the stream has already been dropped, there's no one left to report the error to.
+                let _ = sender.send(Err(err)).await;
+            }
+        });
+
+        ZipBodyStream { receiver, task }
+    }
+}
+
+impl Stream for ZipBodyStream {
+    type Item = Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().receiver.poll_recv(c)
+    }
+}
+
+impl Drop for ZipBodyStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The writer given to the closure of a [`ZipBodyStream`], which passes written data to the stream in chunks.
+pub struct BodyWriter {
+    sender: Sender<Result<Bytes>>,
+    buffer: BytesMut,
+    reserve: Option<Reserve>,
+}
+
+impl BodyWriter {
+    /// Sends the buffered data as a chunk once the channel has capacity for it.
+    fn poll_send(&mut self, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        if self.buffer.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        let sender = &self.sender;
+        let reserve = self.reserve.get_or_insert_with(|| Box::pin(sender.clone().reserve_owned()));
+        let permit = ready!(reserve.as_mut().poll(c));
+        self.reserve = None;
+
+        // The receiver is only dropped alongside the stream, so there's nothing left to write to.
+        let permit = permit.map_err(|_| std::io::Error::from(ErrorKind::BrokenPipe))?;
+        permit.send(Ok(self.buffer.split().freeze()));
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for BodyWriter {
+    fn poll_write(self: Pin<&mut Self>, c: &mut Context<'_>, b: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+
+        if this.buffer.len() >= CHUNK_SIZE {
+            ready!(this.poll_send(c))?;
+        }
+
+        let length = std::cmp::min(b.len(), CHUNK_SIZE - this.buffer.len());
+        this.buffer.extend_from_slice(&b[..length]);
+        Poll::Ready(Ok(length))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().poll_send(c)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.get_mut().poll_send(c)
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::spec::header::{CentralDirectoryRecord, GeneralPurposeFlag};
+use crate::write::io::offset::AsyncOffsetWriter;
+use crate::write::{CentralDirectoryEntry, WriteSummary, ZipFileWriter};
+
+use tokio::io::AsyncWrite;
+
+/// A low-level writer which only writes a central directory (and end of central directory records) for a set of
+/// entries whose local file headers & data have already been laid out by the caller.
+///
+/// This is intended for repackaging tools which place entry data themselves (eg. to reuse chunks of data shared with
+/// another archive). No local file headers or data are written, and each entry's CRC32 value & sizes are written as-is,
+/// so they must match the data at each entry's offset (see [`ZipEntryBuilder::crc32()`] and
+/// [`ZipEntryBuilder::sizes()`]). Entries read from another archive already hold these values.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::write::CentralDirectoryWriter;
+/// # use async_zip::read::mem::ZipFileReader;
+/// # use async_zip::error::Result;
+/// #
+/// async fn run(source: ZipFileReader) -> Result<Vec<u8>> {
+///     // The source's data is reused verbatim, so only a new central directory is written after it.
+///     let mut writer = CentralDirectoryWriter::new(Vec::new(), source.data().len() as u64);
+///
+///     for stored_entry in source.file().entries() {
+///         writer.push_entry(stored_entry.entry().clone(), stored_entry.header_offset())?;
+///     }
+///
+///     let (central_directory, _) = writer.close().await?;
+///     Ok(central_directory)
+/// }
+/// ```
+///
+/// [`ZipEntryBuilder::crc32()`]: crate::ZipEntryBuilder::crc32
+/// [`ZipEntryBuilder::sizes()`]: crate::ZipEntryBuilder::sizes
+pub struct CentralDirectoryWriter<W: AsyncWrite + Unpin>(ZipFileWriter<W>);
+
+impl<W: AsyncWrite + Unpin> CentralDirectoryWriter<W> {
+    /// Constructs a new central directory writer.
+    ///
+    /// The provided offset is that at which the writer's output starts within the archive (ie. the offset of the
+    /// central directory).
+    pub fn new(writer: W, offset: u64) -> Self {
+        let mut inner = ZipFileWriter::new(writer);
+        inner.writer = AsyncOffsetWriter::with_offset(inner.writer.into_inner(), offset);
+        Self(inner)
+    }
+
+    /// Pushes an entry whose local file header is located at the provided offset within the archive.
+    ///
+    /// Returns an error if the entry's filename is rejected by its filename policy.
+    pub fn push_entry(&mut self, entry: ZipEntry, offset: u64) -> Result<()> {
+        let entry = self.0.prepare_entry(entry, None)?;
+        let mut v_needed = crate::spec::version::as_needed_to_extract(&entry);
+
+        if entry.force_zip64 {
+            v_needed = std::cmp::max(v_needed, crate::spec::version::ZIP64_VERSION_NEEDED);
+        }
+
+        let header = CentralDirectoryRecord {
+            v_made_by: crate::spec::version::as_made_by(entry.attribute_compatibility()),
+            v_needed,
+            compressed_size: 0,
+            uncompressed_size: 0,
+            compression: crate::write::crypto::compression_method(&entry),
+            crc: crate::write::crypto::stored_crc(&entry, entry.crc32()),
+            extra_field_length: 0,
+            file_name_length: entry.filename().len() as u16,
+            file_comment_length: entry.comment().len() as u16,
+            mod_time: entry.last_modification_date().time,
+            mod_date: entry.last_modification_date().date,
+            flags: GeneralPurposeFlag {
+                data_descriptor: false,
+                encrypted: entry.encryption.is_some(),
+                filename_unicode: entry.utf8_flag.enabled(entry.filename(), entry.comment()),
+                patched: false,
+                strong_encryption: false,
+                masked_header: false,
+            },
+            disk_start: 0,
+            inter_attr: entry.internal_file_attribute(),
+            exter_attr: entry.external_file_attribute(),
+            lh_offset: 0,
+        };
+
+        let sizes = (entry.uncompressed_size(), entry.compressed_size());
+        self.0.cd_entries.push(CentralDirectoryEntry::new(header, entry, sizes, offset));
+        Ok(())
+    }
+
+    /// Set the ZIP file comment.
+    pub fn comment(&mut self, comment: String) {
+        self.0.comment(comment)
+    }
+
+    /// Consumes this writer, writes the central directory & end of central directory records, and returns the inner
+    /// writer along with a summary of the archive.
+    pub async fn close(self) -> Result<(W, WriteSummary)> {
+        self.0.close_with_summary().await
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::{Result, ZipError};
+use crate::spec::consts::EOCDR_LENGTH;
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, SeekFrom};
+
+/// Replaces the comment of an existing ZIP file in place, without touching any of its entries.
+///
+/// Only the end of central directory record's comment length and the trailing comment itself are rewritten. The new
+/// length of the ZIP file is returned, which the caller should truncate the file to if the comment was shortened (eg.
+/// via [`tokio::fs::File::set_len()`]). With the `fs` feature, `fs::update_comment()` handles this for files on disk.
+pub async fn update_comment<F>(mut file: F, comment: &str) -> Result<u64>
+where
+    F: AsyncRead + AsyncWrite + AsyncSeek + Unpin,
+{
+    let length: u16 = comment.len().try_into().map_err(|_| ZipError::CommentTooLong)?;
+    let eocdr_offset = crate::read::io::locator::eocdr(&mut file).await?;
+
+    // The comment length is the last fixed-size field of the EOCDR, directly preceding the comment itself.
+    file.seek(SeekFrom::Start(eocdr_offset + EOCDR_LENGTH as u64 - 2)).await?;
+    file.write_all(&length.to_le_bytes()).await?;
+    file.write_all(comment.as_bytes()).await?;
+    file.flush().await?;
+
+    Ok(eocdr_offset + EOCDR_LENGTH as u64 + u64::from(length))
+}
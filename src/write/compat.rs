@@ -0,0 +1,198 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP writer which acts over implementers of the `futures-io` [`AsyncWrite`] trait (rather than tokio's).
+//!
+//! This mirrors the API of [`crate::write::ZipFileWriter`], and so allows ZIP files to be written from non-tokio
+//! executors (eg. smol or async-std) without any additional compatibility shim. Note that this module requires the
+//! `futures` feature.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::{Compression, ZipEntryBuilder, write::compat::ZipFileWriter};
+//! # use async_zip::error::Result;
+//! # use futures::io::{AsyncWriteExt, Cursor};
+//! #
+//! # async fn run() -> Result<()> {
+//! let mut writer = ZipFileWriter::new(Cursor::new(Vec::new()));
+//!
+//! let builder = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+//! let mut entry_writer = writer.write_entry_stream(builder).await?;
+//! entry_writer.write_all(b"foo bar").await?;
+//! entry_writer.close().await?;
+//!
+//! let data = writer.close().await?.into_inner();
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::entry::builder::ZipEntryBuilder;
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::write::io::compat::Compat;
+use crate::write::{WriteSummary, WriterOptions, WrittenEntry, Zip64Policy};
+
+use std::io::Error;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+/// A ZIP file writer which acts over `futures-io` [`AsyncWrite`] implementers.
+///
+/// # Note
+/// - [`ZipFileWriter::close()`] must be called before a stream writer goes out of scope.
+pub struct ZipFileWriter<W: AsyncWrite + Unpin>(crate::write::ZipFileWriter<Compat<W>>);
+
+impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
+    /// Construct a new ZIP file writer from a mutable reference to a writer.
+    pub fn new(writer: W) -> Self {
+        Self(crate::write::ZipFileWriter::new(Compat::new(writer)))
+    }
+
+    /// Construct a new ZIP file writer from a mutable reference to a writer and a set of archive-wide options.
+    pub fn with_options(writer: W, options: WriterOptions) -> Self {
+        Self(crate::write::ZipFileWriter::with_options(Compat::new(writer), options))
+    }
+
+    /// Returns a new entry builder which uses this writer's default compression method.
+    pub fn entry_builder(&self, filename: String) -> ZipEntryBuilder {
+        self.0.entry_builder(filename)
+    }
+
+    /// Write a new ZIP entry of known size and data.
+    pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
+        self.0.write_entry_whole(entry, data).await
+    }
+
+    /// Write a directory entry, so that the directory is recreated on extraction even if it holds no other entries.
+    ///
+    /// See [`crate::write::ZipFileWriter::write_dir_entry()`].
+    pub async fn write_dir_entry<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<()> {
+        self.0.write_dir_entry(entry).await
+    }
+
+    /// Write a symbolic link entry which points to the provided target path.
+    ///
+    /// See [`crate::write::ZipFileWriter::write_symlink_entry()`].
+    pub async fn write_symlink_entry<E: Into<ZipEntry>>(&mut self, entry: E, target: &str) -> Result<()> {
+        self.0.write_symlink_entry(entry, target).await
+    }
+
+    /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
+    pub async fn write_entry_stream<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<EntryStreamWriter<'_, W>> {
+        Ok(EntryStreamWriter(self.0.write_entry_stream(entry).await?))
+    }
+
+    /// Write an entry by streaming its data from a reader until EOF (ie. using a data descriptor).
+    ///
+    /// Returns the number of uncompressed bytes written.
+    pub async fn write_entry_from_reader<E, R>(&mut self, entry: E, reader: R) -> Result<u64>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        self.0.write_entry_from_reader(entry, Compat::new(reader)).await
+    }
+
+    /// Set the ZIP file comment.
+    pub fn comment(&mut self, comment: String) {
+        self.0.comment(comment)
+    }
+
+    /// Set the ZIP file comment from raw bytes.
+    ///
+    /// Returns an error if the comment is longer than 65535 bytes.
+    pub fn comment_raw(&mut self, comment: Vec<u8>) -> Result<()> {
+        self.0.comment_raw(comment)
+    }
+
+    /// Set the policy which determines when entries are written using ZIP64 structures.
+    pub fn zip64_policy(&mut self, policy: Zip64Policy) {
+        self.0.zip64_policy(policy)
+    }
+
+    /// Returns the number of bytes written to the inner writer so far.
+    pub fn offset(&self) -> u64 {
+        self.0.offset()
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Care should be taken when using this inner writer as doing so may invalidate internal state of this writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        self.0.inner_mut().get_mut()
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks.
+    ///
+    /// Failiure to call this function before going out of scope would result in a corrupted ZIP file.
+    pub async fn close(self) -> Result<W> {
+        Ok(self.0.close().await?.into_inner())
+    }
+
+    /// Consumes this ZIP writer, completes all closing tasks, and returns a summary of what was written.
+    pub async fn close_with_summary(self) -> Result<(W, WriteSummary)> {
+        let (writer, summary) = self.0.close_with_summary().await?;
+        Ok((writer.into_inner(), summary))
+    }
+
+    /// Consumes this ZIP writer, completes all closing tasks, and then closes the inner writer.
+    ///
+    /// Returns the inner writer along with the total number of bytes written to it.
+    pub async fn close_and_shutdown(self) -> Result<(W, u64)> {
+        let (writer, total_bytes) = self.0.close_and_shutdown().await?;
+        Ok((writer.into_inner(), total_bytes))
+    }
+}
+
+/// An entry writer which supports the streaming of data, and implements the `futures-io` [`AsyncWrite`] trait.
+///
+/// # Note
+/// - This writer cannot be manually constructed; instead, use [`ZipFileWriter::write_entry_stream()`].
+/// - [`EntryStreamWriter::close()`] must be called before a stream writer goes out of scope. Closing this writer via
+///   [`AsyncWrite`] only flushes it, as the entry can't be completed without access to the ZIP file writer.
+pub struct EntryStreamWriter<'b, W: AsyncWrite + Unpin>(crate::write::EntryStreamWriter<'b, Compat<W>>);
+
+impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
+    /// Flushes all data written so far through the compressor and into the underlying writer.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.0.flush().await
+    }
+
+    /// Consumes this entry writer and discards the partially written entry.
+    ///
+    /// See [`crate::write::EntryStreamWriter::abort()`].
+    pub fn abort(self) {
+        self.0.abort()
+    }
+
+    /// Consumes this entry writer and completes all closing tasks.
+    ///
+    /// Failiure to call this function before going out of scope would result in a corrupted ZIP file.
+    pub async fn close(self) -> Result<()> {
+        self.0.close().await
+    }
+
+    /// Consumes this entry writer, completes all closing tasks, and returns the details of the written entry.
+    ///
+    /// See [`crate::write::EntryStreamWriter::finish()`].
+    pub async fn finish(self) -> Result<WrittenEntry> {
+        self.0.finish().await
+    }
+}
+
+impl<'b, W: AsyncWrite + Unpin> AsyncWrite for EntryStreamWriter<'b, W> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
+        tokio::io::AsyncWrite::poll_write(Pin::new(&mut self.0), cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        tokio::io::AsyncWrite::poll_flush(Pin::new(&mut self.0), cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        tokio::io::AsyncWrite::poll_flush(Pin::new(&mut self.0), cx)
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::entry::ZipEntry;
 use crate::spec::compression::Compression;
+use crate::write::crypto::EntryCipher;
+use crate::write::io::cipher::CipherWriter;
 use crate::write::io::offset::AsyncOffsetWriter;
 
 use std::io::Error;
@@ -12,52 +15,72 @@ use std::task::{Context, Poll};
 use async_compression::tokio::write;
 use tokio::io::AsyncWrite;
 
+/// The writer over which an entry's (compressed) data is written, encrypting it if required.
+type EntryDataWriter<'b, W> = CipherWriter<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>;
+
 pub enum CompressedAsyncWriter<'b, W: AsyncWrite + Unpin> {
-    Stored(ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>),
+    Stored(EntryDataWriter<'b, W>),
     #[cfg(feature = "deflate")]
-    Deflate(write::DeflateEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Deflate(write::DeflateEncoder<EntryDataWriter<'b, W>>),
     #[cfg(feature = "bzip2")]
-    Bz(write::BzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Bz(write::BzEncoder<EntryDataWriter<'b, W>>),
     #[cfg(feature = "lzma")]
-    Lzma(write::LzmaEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Lzma(write::LzmaEncoder<EntryDataWriter<'b, W>>),
     #[cfg(feature = "zstd")]
-    Zstd(write::ZstdEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Zstd(write::ZstdEncoder<EntryDataWriter<'b, W>>),
     #[cfg(feature = "xz")]
-    Xz(write::XzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Xz(write::XzEncoder<EntryDataWriter<'b, W>>),
 }
 
 impl<'b, W: AsyncWrite + Unpin> CompressedAsyncWriter<'b, W> {
-    pub fn from_raw(writer: &'b mut AsyncOffsetWriter<W>, compression: Compression) -> Self {
-        match compression {
-            Compression::Stored => CompressedAsyncWriter::Stored(ShutdownIgnoredWriter(writer)),
+    pub fn from_raw(
+        writer: &'b mut AsyncOffsetWriter<W>,
+        entry: &ZipEntry,
+        cipher: Option<(EntryCipher, Vec<u8>)>,
+    ) -> Self {
+        let inner = match cipher {
+            Some((cipher, header)) => CipherWriter::with_cipher(ShutdownIgnoredWriter(writer), cipher, header),
+            None => CipherWriter::new(ShutdownIgnoredWriter(writer)),
+        };
+
+        match entry.compression() {
+            Compression::Stored => CompressedAsyncWriter::Stored(inner),
             #[cfg(feature = "deflate")]
             Compression::Deflate => {
-                CompressedAsyncWriter::Deflate(write::DeflateEncoder::new(ShutdownIgnoredWriter(writer)))
+                CompressedAsyncWriter::Deflate(write::DeflateEncoder::with_quality(inner, entry.compression_level))
             }
             #[cfg(feature = "bzip2")]
-            Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Bz => {
+                CompressedAsyncWriter::Bz(write::BzEncoder::with_quality(inner, entry.compression_level))
+            }
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Lzma => {
+                CompressedAsyncWriter::Lzma(write::LzmaEncoder::with_quality(inner, entry.compression_level))
+            }
             #[cfg(feature = "zstd")]
-            Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Zstd => {
+                CompressedAsyncWriter::Zstd(write::ZstdEncoder::with_quality(inner, entry.compression_level))
+            }
             #[cfg(feature = "xz")]
-            Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Xz => {
+                CompressedAsyncWriter::Xz(write::XzEncoder::with_quality(inner, entry.compression_level))
+            }
         }
     }
 
     pub fn into_inner(self) -> &'b mut AsyncOffsetWriter<W> {
         match self {
-            CompressedAsyncWriter::Stored(inner) => inner.into_inner(),
+            CompressedAsyncWriter::Stored(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "deflate")]
-            CompressedAsyncWriter::Deflate(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Deflate(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "bzip2")]
-            CompressedAsyncWriter::Bz(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Bz(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "lzma")]
-            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "zstd")]
-            CompressedAsyncWriter::Zstd(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Zstd(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "xz")]
-            CompressedAsyncWriter::Xz(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Xz(inner) => inner.into_inner().into_inner().into_inner(),
         }
     }
 }
@@ -0,0 +1,97 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::spec::consts::AES_EXTRA_FIELD_HEADER_ID;
+use crate::spec::extra_field::ExtraField;
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes256;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// The length of the salt which precedes the data of an AES-256 encrypted entry.
+pub(crate) const AES_SALT_LENGTH: usize = 16;
+
+/// The length of the password verification value which follows the salt.
+pub(crate) const AES_VERIFIER_LENGTH: usize = 2;
+
+/// The length of the (truncated HMAC-SHA1) authentication code which follows the encrypted data.
+pub(crate) const AES_AUTH_CODE_LENGTH: usize = 10;
+
+/// The number of PBKDF2 iterations used to derive the keys, as fixed by WinZip's specification.
+pub(crate) const PBKDF2_ITERATIONS: u32 = 1000;
+
+const KEY_LENGTH: usize = 32;
+const BLOCK_LENGTH: usize = 16;
+
+/// The AE-2 cipher state of an entry, which encrypts data using AES-256 in CTR mode and authenticates the ciphertext.
+// https://www.winzip.com/en/support/aes-encryption/
+pub(crate) struct AesCipher {
+    cipher: Aes256,
+    hmac: Hmac<Sha1>,
+    counter: u128,
+    keystream: [u8; BLOCK_LENGTH],
+    position: usize,
+}
+
+impl AesCipher {
+    /// Derives the keys from a password and a random salt, returning the cipher along with the header (the salt and
+    /// password verification value) which must precede the encrypted data.
+    pub(crate) fn new(password: &[u8]) -> Result<(Self, Vec<u8>)> {
+        let mut salt = [0; AES_SALT_LENGTH];
+        getrandom::getrandom(&mut salt).map_err(std::io::Error::from)?;
+
+        let mut derived = [0; KEY_LENGTH * 2 + AES_VERIFIER_LENGTH];
+        pbkdf2::pbkdf2_hmac::<Sha1>(password, &salt, PBKDF2_ITERATIONS, &mut derived);
+
+        let cipher = Aes256::new(GenericArray::from_slice(&derived[..KEY_LENGTH]));
+        let hmac =
+            <Hmac<Sha1> as Mac>::new_from_slice(&derived[KEY_LENGTH..KEY_LENGTH * 2]).expect("HMAC accepts any key");
+
+        let mut header = salt.to_vec();
+        header.extend_from_slice(&derived[KEY_LENGTH * 2..]);
+
+        Ok((AesCipher { cipher, hmac, counter: 0, keystream: [0; BLOCK_LENGTH], position: BLOCK_LENGTH }, header))
+    }
+
+    /// Encrypts a chunk of data in place.
+    ///
+    /// Unlike standard CTR mode, WinZip's counter is little-endian and starts at one.
+    pub(crate) fn encrypt(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            if self.position == BLOCK_LENGTH {
+                self.counter += 1;
+
+                let mut block = GenericArray::from(self.counter.to_le_bytes());
+                self.cipher.encrypt_block(&mut block);
+
+                self.keystream = block.into();
+                self.position = 0;
+            }
+
+            *byte ^= self.keystream[self.position];
+            self.position += 1;
+        }
+
+        self.hmac.update(data);
+    }
+
+    /// Consumes this cipher and returns the authentication code of all encrypted data.
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.hmac.finalize().into_bytes()[..AES_AUTH_CODE_LENGTH].to_vec()
+    }
+}
+
+/// Returns the AES extra field of an entry, which holds the AE-2 version and the entry's actual compression method.
+pub(crate) fn extra_field(entry: &ZipEntry) -> Vec<u8> {
+    let mut data = Vec::with_capacity(7);
+    data.extend_from_slice(&2u16.to_le_bytes());
+    data.extend_from_slice(b"AE");
+    data.push(3);
+    data.extend_from_slice(&u16::from(entry.compression()).to_le_bytes());
+
+    ExtraField::Custom { id: AES_EXTRA_FIELD_HEADER_ID, data }.to_bytes()
+}
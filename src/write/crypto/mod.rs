@@ -0,0 +1,84 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Implementations of the encryption methods supported when writing entries.
+
+#[cfg(feature = "aes")]
+pub(crate) mod aes;
+pub(crate) mod zip_crypto;
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::spec::encryption::Encryption;
+use zip_crypto::ZipCryptoKeys;
+
+/// The cipher used to encrypt an entry's (compressed) data.
+pub(crate) enum EntryCipher {
+    ZipCrypto(ZipCryptoKeys),
+    // Boxed as the AES key schedule is much larger than the ZipCrypto keys.
+    #[cfg(feature = "aes")]
+    Aes(Box<aes::AesCipher>),
+}
+
+impl EntryCipher {
+    /// Constructs the cipher of an encryption method, along with the header which must precede the encrypted data.
+    ///
+    /// The check byte is only used by ZipCrypto, and should be the high byte of the entry's CRC32 value (or of its last
+    /// modification time when the CRC32 value is only known after the data has been written).
+    pub(crate) fn new(encryption: &Encryption, check_byte: u8) -> Result<(Self, Vec<u8>)> {
+        match encryption {
+            Encryption::ZipCrypto(password) => {
+                let mut keys = ZipCryptoKeys::new(password);
+                let header = keys.encryption_header(check_byte).to_vec();
+                Ok((EntryCipher::ZipCrypto(keys), header))
+            }
+            #[cfg(feature = "aes")]
+            Encryption::Aes256(password) => {
+                let (cipher, header) = aes::AesCipher::new(password)?;
+                Ok((EntryCipher::Aes(Box::new(cipher)), header))
+            }
+        }
+    }
+
+    /// Encrypts a chunk of data in place.
+    pub(crate) fn encrypt(&mut self, data: &mut [u8]) {
+        match self {
+            EntryCipher::ZipCrypto(keys) => data.iter_mut().for_each(|byte| *byte = keys.encrypt(*byte)),
+            #[cfg(feature = "aes")]
+            EntryCipher::Aes(cipher) => cipher.encrypt(data),
+        }
+    }
+
+    /// Consumes this cipher and returns the trailer which must follow the encrypted data.
+    pub(crate) fn finish(self) -> Vec<u8> {
+        match self {
+            EntryCipher::ZipCrypto(_) => Vec::new(),
+            #[cfg(feature = "aes")]
+            EntryCipher::Aes(cipher) => cipher.finish(),
+        }
+    }
+}
+
+/// Returns the compression method written to an entry's headers.
+///
+/// AES-encrypted entries are written with a placeholder method, with the actual method instead held within the AES
+/// extra field.
+pub(crate) fn compression_method(entry: &ZipEntry) -> u16 {
+    match entry.encryption {
+        #[cfg(feature = "aes")]
+        Some(Encryption::Aes256(_)) => crate::spec::consts::AES_COMPRESSION_METHOD,
+        _ => entry.compression().into(),
+    }
+}
+
+/// Returns the CRC32 value written to an entry's headers.
+///
+/// AE-2 omits the CRC32 value of AES-encrypted entries (as the authentication code already verifies the data, and a
+/// CRC32 value of the plaintext would leak information about it).
+pub(crate) fn stored_crc(entry: &ZipEntry, crc: u32) -> u32 {
+    match entry.encryption {
+        #[cfg(feature = "aes")]
+        Some(Encryption::Aes256(_)) => 0,
+        _ => crc,
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The length of the encryption header which precedes the data of a ZipCrypto-encrypted entry.
+pub(crate) const ZIP_CRYPTO_HEADER_LENGTH: usize = 12;
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            value = if value & 1 == 1 { 0xEDB88320 ^ (value >> 1) } else { value >> 1 };
+            bit += 1;
+        }
+
+        table[index] = value;
+        index += 1;
+    }
+
+    table
+}
+
+fn crc32_update(crc: u32, byte: u8) -> u32 {
+    CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+}
+
+/// The keystream state of the traditional PKWARE encryption method.
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#6124
+#[derive(Clone)]
+pub(crate) struct ZipCryptoKeys(u32, u32, u32);
+
+impl ZipCryptoKeys {
+    /// Constructs the initial keystream state from a password.
+    pub(crate) fn new(password: &[u8]) -> Self {
+        let mut keys = ZipCryptoKeys(0x12345678, 0x23456789, 0x34567890);
+        password.iter().for_each(|byte| keys.update(*byte));
+        keys
+    }
+
+    fn update(&mut self, byte: u8) {
+        self.0 = crc32_update(self.0, byte);
+        self.1 = self.1.wrapping_add(self.0 & 0xFF).wrapping_mul(134775813).wrapping_add(1);
+        self.2 = crc32_update(self.2, (self.1 >> 24) as u8);
+    }
+
+    fn stream_byte(&self) -> u8 {
+        let temp = (self.2 | 2) as u16;
+        (temp.wrapping_mul(temp ^ 1) >> 8) as u8
+    }
+
+    /// Encrypts a single byte of plaintext, advancing the keystream.
+    pub(crate) fn encrypt(&mut self, byte: u8) -> u8 {
+        let encrypted = byte ^ self.stream_byte();
+        self.update(byte);
+        encrypted
+    }
+
+    /// Decrypts a single byte of ciphertext, advancing the keystream.
+    #[cfg(test)]
+    pub(crate) fn decrypt(&mut self, byte: u8) -> u8 {
+        let decrypted = byte ^ self.stream_byte();
+        self.update(decrypted);
+        decrypted
+    }
+
+    /// Generates and encrypts the header which precedes an entry's encrypted data.
+    ///
+    /// The final byte of this header is used by readers to check the password, and should be the high byte of the
+    /// entry's CRC32 value (or of its last modification time when the CRC32 value is only known after the data).
+    pub(crate) fn encryption_header(&mut self, check_byte: u8) -> [u8; ZIP_CRYPTO_HEADER_LENGTH] {
+        let mut header = random_bytes::<ZIP_CRYPTO_HEADER_LENGTH>();
+        header[ZIP_CRYPTO_HEADER_LENGTH - 1] = check_byte;
+        header.map(|byte| self.encrypt(byte))
+    }
+}
+
+/// Returns a set of unpredictable (but not cryptographically secure) bytes.
+///
+/// Each [`RandomState`] is seeded with random keys from the OS, so this avoids pulling in a dependency purely for the
+/// ZipCrypto header (which doesn't provide any meaningful security regardless).
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    let seed = seed();
+
+    for chunk in bytes.chunks_mut(8) {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(seed);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes()[..chunk.len()]);
+    }
+
+    bytes
+}
+
+/// Returns a value which differs between calls, mixed into each hasher alongside its random keys.
+///
+/// The system clock isn't available on `wasm32-unknown-unknown` (where [`SystemTime::now()`] panics and the hasher's
+/// keys are fixed), so a per-process counter is always included to avoid repeated headers across entries there.
+///
+/// [`SystemTime::now()`]: std::time::SystemTime::now
+fn seed() -> u128 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed) as u128;
+
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    let count = {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_nanos()).unwrap_or_default();
+        now ^ (count << 64)
+    };
+
+    count
+}
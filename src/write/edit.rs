@@ -0,0 +1,104 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::write::ZipFileWriter;
+
+use std::collections::HashMap;
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite};
+
+/// A set of edits which are applied to an existing ZIP file by rewriting it.
+///
+/// Entries which are neither removed nor replaced are copied into the new ZIP file without decompressing or
+/// recompressing their data (see [`ZipFileWriter::copy_entry_raw()`]), and the ZIP file comment is carried over.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::{Compression, ZipEntryBuilder, write::ZipEdits};
+/// # use async_zip::error::Result;
+/// # use tokio::fs::File;
+/// #
+/// async fn run() -> Result<()> {
+///     let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+///     let edits = ZipEdits::new().remove("foo.txt").rename("baz.txt", "qux.txt").replace(entry, b"bar".to_vec());
+///
+///     edits.apply(File::open("./foo.zip").await?, File::create("./bar.zip").await?).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Default)]
+pub struct ZipEdits {
+    removals: Vec<String>,
+    renames: HashMap<String, String>,
+    replacements: Vec<(ZipEntry, Vec<u8>)>,
+}
+
+impl ZipEdits {
+    /// Constructs a new empty set of edits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes the entry with the provided filename.
+    pub fn remove(mut self, filename: impl Into<String>) -> Self {
+        self.removals.push(filename.into());
+        self
+    }
+
+    /// Renames the entry with the provided filename (whilst copying its data verbatim).
+    pub fn rename(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.renames.insert(from.into(), to.into());
+        self
+    }
+
+    /// Replaces the entry with the same filename as the provided entry, or adds it if no such entry exists.
+    ///
+    /// Replaced entries keep their position within the ZIP file, whereas added entries are written after all others.
+    pub fn replace(mut self, entry: impl Into<ZipEntry>, data: Vec<u8>) -> Self {
+        self.replacements.push((entry.into(), data));
+        self
+    }
+
+    /// Applies these edits to the ZIP file read from the provided source, writing the result to the provided writer.
+    ///
+    /// The source and writer must not refer to the same underlying file, as the source is read whilst writing.
+    pub async fn apply<R, W>(mut self, mut reader: R, writer: W) -> Result<W>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let file = crate::read::file(&mut reader).await?;
+        let mut writer = ZipFileWriter::new(writer);
+
+        if !file.comment().is_empty() {
+            writer.comment(file.comment().to_string());
+        }
+
+        for stored_entry in file.entries() {
+            let filename = stored_entry.entry().filename();
+
+            if self.removals.iter().any(|removal| removal == filename) {
+                continue;
+            }
+
+            match self.replacements.iter().position(|(entry, _)| entry.filename() == filename) {
+                Some(index) => {
+                    let (entry, data) = self.replacements.remove(index);
+                    writer.write_entry_whole(entry, &data).await?;
+                }
+                None => {
+                    let rename = self.renames.remove(filename);
+                    writer.copy_entry_raw(stored_entry, rename, &mut reader).await?;
+                }
+            }
+        }
+
+        for (entry, data) in self.replacements {
+            writer.write_entry_whole(entry, &data).await?;
+        }
+
+        writer.close().await
+    }
+}
@@ -3,8 +3,10 @@
 
 use crate::entry::ZipEntry;
 use crate::error::Result;
+use crate::spec::consts::{LFH_LENGTH, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH};
 use crate::spec::header::{CentralDirectoryRecord, GeneralPurposeFlag, LocalFileHeader};
 use crate::write::compressed_writer::CompressedAsyncWriter;
+use crate::write::crypto::EntryCipher;
 use crate::write::io::offset::AsyncOffsetWriter;
 use crate::write::CentralDirectoryEntry;
 use crate::write::ZipFileWriter;
@@ -14,13 +16,14 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crc32fast::Hasher;
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::io::{AsyncSeek, AsyncWrite, AsyncWriteExt};
 
 /// An entry writer which supports the streaming of data (ie. the writing of unknown size or data at runtime).
 ///
 /// # Note
 /// - This writer cannot be manually constructed; instead, use [`ZipFileWriter::write_entry_stream()`].
-/// - [`EntryStreamWriter::close()`] must be called before a stream writer goes out of scope.
+/// - [`EntryStreamWriter::close()`] (or [`EntryStreamWriter::finish()`]) must be called before a stream writer goes out
+///   of scope.
 /// - Utilities for working with [`AsyncWrite`] values are provided by [`AsyncWriteExt`].
 pub struct EntryStreamWriter<'b, W: AsyncWrite + Unpin> {
     writer: AsyncOffsetWriter<CompressedAsyncWriter<'b, W>>,
@@ -28,8 +31,8 @@ pub struct EntryStreamWriter<'b, W: AsyncWrite + Unpin> {
     entry: ZipEntry,
     hasher: Hasher,
     lfh: LocalFileHeader,
-    lfh_offset: usize,
-    data_offset: usize,
+    lfh_offset: u64,
+    data_offset: u64,
 }
 
 impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
@@ -41,68 +44,135 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         let lfh = EntryStreamWriter::write_lfh(writer, &entry).await?;
         let data_offset = writer.writer.offset();
 
+        // The CRC32 value isn't known until the data has been written, so ZipCrypto's password check byte is instead
+        // derived from the last modification time (as the entry is always followed by a data descriptor).
+        let check_byte = (entry.last_modification_date().time >> 8) as u8;
+        let cipher =
+            entry.encryption.as_ref().map(|encryption| EntryCipher::new(encryption, check_byte)).transpose()?;
+
         let cd_entries = &mut writer.cd_entries;
-        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression()));
+        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, &entry, cipher));
 
         Ok(EntryStreamWriter { writer, cd_entries, entry, lfh, lfh_offset, data_offset, hasher: Hasher::new() })
     }
 
     async fn write_lfh(writer: &'b mut ZipFileWriter<W>, entry: &ZipEntry) -> Result<LocalFileHeader> {
+        // The sizes aren't yet known, so a forced ZIP64 extra field is reserved with zeroed values (as the actual values
+        // are written within the data descriptor).
+        let mut extra_field = crate::spec::extra_field::without_zip64_extra_field(entry.extra_field());
+        let mut version = crate::spec::version::as_needed_to_extract(entry);
+        let mut size = 0;
+
+        if entry.force_zip64 {
+            extra_field.extend(crate::spec::extra_field::zip64_extra_field(&[0, 0]));
+            version = std::cmp::max(version, crate::spec::version::ZIP64_VERSION_NEEDED);
+            size = NON_ZIP64_MAX_SIZE;
+        }
+
+        let data_offset = writer.writer.offset() + (SIGNATURE_LENGTH + LFH_LENGTH + entry.filename().len()) as u64;
+        extra_field.extend(crate::write::alignment_padding(entry, data_offset + extra_field.len() as u64));
+
         let lfh = LocalFileHeader {
-            compressed_size: 0,
-            uncompressed_size: 0,
-            compression: entry.compression().into(),
+            compressed_size: size,
+            uncompressed_size: size,
+            compression: crate::write::crypto::compression_method(entry),
             crc: 0,
-            extra_field_length: entry.extra_field().len() as u16,
-            file_name_length: entry.filename().as_bytes().len() as u16,
+            extra_field_length: extra_field.len() as u16,
+            file_name_length: entry.filename().len() as u16,
             mod_time: entry.last_modification_date().time,
             mod_date: entry.last_modification_date().date,
-            version: crate::spec::version::as_needed_to_extract(entry),
+            version,
             flags: GeneralPurposeFlag {
                 data_descriptor: true,
-                encrypted: false,
-                filename_unicode: !entry.filename().is_ascii(),
+                encrypted: entry.encryption.is_some(),
+                filename_unicode: entry.utf8_flag.enabled(entry.filename(), entry.comment()),
+                patched: false,
+                strong_encryption: false,
+                masked_header: false,
             },
         };
 
         writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
         writer.writer.write_all(&lfh.as_slice()).await?;
         writer.writer.write_all(entry.filename().as_bytes()).await?;
-        writer.writer.write_all(entry.extra_field()).await?;
+        writer.writer.write_all(&extra_field).await?;
 
         Ok(lfh)
     }
 
+    /// Flushes all data written so far through the compressor and into the underlying writer.
+    ///
+    /// For compression methods which support it (eg. Deflate), this emits a sync flush so that every byte written
+    /// before this call can be decompressed by a reader without waiting for the entry to be closed. The underlying
+    /// writer is then flushed itself. This allows long-lived streaming responses to push data to clients as it's
+    /// produced, at the cost of a slightly worse compression ratio for each flush point.
+    pub async fn flush(&mut self) -> Result<()> {
+        AsyncWriteExt::flush(&mut self.writer).await?;
+        Ok(())
+    }
+
+    /// Consumes this entry writer and discards the partially written entry.
+    ///
+    /// As the writer never seeks, the entry's local file header & any data already written remain within the output,
+    /// but the entry is omitted from the central directory. Readers which act over the central directory (ie. all but
+    /// the [`stream`](crate::read::stream) reader) therefore never see it, so a failure whilst producing an entry's data
+    /// needn't invalidate the rest of the archive. Further entries may be written as usual. For seekable writers, see
+    /// [`EntryStreamWriter::abort_and_rewind()`] instead.
+    pub fn abort(self) {
+        drop(self);
+    }
+
     /// Consumes this entry writer and completes all closing tasks.
     ///
+    /// This is equivalent to [`EntryStreamWriter::finish()`], but discards the details of the written entry.
+    pub async fn close(self) -> Result<()> {
+        self.finish().await.map(|_| ())
+    }
+
+    /// Consumes this entry writer, completes all closing tasks, and returns the details of the written entry.
+    ///
     /// This includes:
     /// - Finalising the CRC32 hash value for the written data.
     /// - Calculating the compressed and uncompressed byte sizes.
     /// - Constructing a central directory header.
     /// - Pushing that central directory header to the [`ZipFileWriter`]'s store.
     ///
-    /// Failiure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<()> {
+    /// Either this function or [`EntryStreamWriter::close()`] must be called before going out of scope, as failiure to
+    /// do so would result in a corrupted ZIP file.
+    pub async fn finish(mut self) -> Result<WrittenEntry> {
         self.writer.shutdown().await?;
 
-        let crc = self.hasher.finalize();
-        let uncompressed_size = self.writer.offset() as u32;
+        let crc = crate::write::crypto::stored_crc(&self.entry, self.hasher.finalize());
+        let uncompressed_size = self.writer.offset();
         let inner_writer = self.writer.into_inner().into_inner();
-        let compressed_size = (inner_writer.offset() - self.data_offset) as u32;
+        let compressed_size = inner_writer.offset() - self.data_offset;
 
         inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
         inner_writer.write_all(&crc.to_le_bytes()).await?;
-        inner_writer.write_all(&compressed_size.to_le_bytes()).await?;
-        inner_writer.write_all(&uncompressed_size.to_le_bytes()).await?;
+
+        // The data descriptor's sizes are widened to 8 bytes if a ZIP64 extra field was reserved within the local file
+        // header, or if either size has since grown past 4 GiB (as they're otherwise unrepresentable). Readers must then
+        // rely on the central directory's ZIP64 extra field or the amount of data read to detect the wider sizes.
+        let zip64 = self.entry.force_zip64
+            || compressed_size > NON_ZIP64_MAX_SIZE as u64
+            || uncompressed_size > NON_ZIP64_MAX_SIZE as u64;
+
+        if zip64 {
+            inner_writer.write_all(&compressed_size.to_le_bytes()).await?;
+            inner_writer.write_all(&uncompressed_size.to_le_bytes()).await?;
+        } else {
+            inner_writer.write_all(&(compressed_size as u32).to_le_bytes()).await?;
+            inner_writer.write_all(&(uncompressed_size as u32).to_le_bytes()).await?;
+        }
 
         let cdh = CentralDirectoryRecord {
-            compressed_size,
-            uncompressed_size,
+            compressed_size: 0,
+            uncompressed_size: 0,
             crc,
-            v_made_by: crate::spec::version::as_made_by(),
+            v_made_by: crate::spec::version::as_made_by(self.entry.attribute_compatibility()),
             v_needed: self.lfh.version,
             compression: self.lfh.compression,
-            extra_field_length: self.lfh.extra_field_length,
+            extra_field_length: 0,
             file_name_length: self.lfh.file_name_length,
             file_comment_length: self.entry.comment().len() as u16,
             mod_time: self.lfh.mod_time,
@@ -111,14 +181,95 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             disk_start: 0,
             inter_attr: self.entry.internal_file_attribute(),
             exter_attr: self.entry.external_file_attribute(),
-            lh_offset: self.lfh_offset as u32,
+            lh_offset: 0,
         };
 
-        self.cd_entries.push(CentralDirectoryEntry { header: cdh, entry: self.entry });
+        let sizes = (uncompressed_size, compressed_size);
+        self.cd_entries.push(CentralDirectoryEntry::new(cdh, self.entry, sizes, self.lfh_offset));
+
+        Ok(WrittenEntry {
+            crc32: crc,
+            compressed_size,
+            uncompressed_size,
+            header_offset: self.lfh_offset,
+            data_offset: self.data_offset,
+        })
+    }
+}
+
+impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntryStreamWriter<'b, W> {
+    /// Consumes this entry writer, discards the partially written entry, and seeks back to its local file header.
+    ///
+    /// Unlike [`EntryStreamWriter::abort()`], the entry's local file header & data are then overwritten by whatever is
+    /// written next, so no orphaned header is left within the output. Any of the discarded bytes which extend past the
+    /// end of the finished archive aren't removed though, so the output should be truncated to the length returned by
+    /// [`ZipFileWriter::close_and_shutdown()`] (see [`EntryStreamWriter::abort_and_truncate()`] for files).
+    pub async fn abort_and_rewind(self) -> Result<()> {
+        let lfh_offset = self.lfh_offset;
+        let inner_writer = self.writer.into_inner().into_inner();
+
+        inner_writer.rewind_to(lfh_offset).await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<'b> EntryStreamWriter<'b, tokio::fs::File> {
+    /// Consumes this entry writer, discards the partially written entry, and truncates the file at its local file
+    /// header.
+    ///
+    /// This is equivalent to [`EntryStreamWriter::abort_and_rewind()`], but also removes the discarded bytes from the
+    /// file, so the output never holds any trace of the entry.
+    pub async fn abort_and_truncate(self) -> Result<()> {
+        let lfh_offset = self.lfh_offset;
+        let inner_writer = self.writer.into_inner().into_inner();
+
+        inner_writer.rewind_to(lfh_offset).await?;
+        let file = inner_writer.inner_mut();
+        let position = tokio::io::AsyncSeekExt::stream_position(file).await?;
+        file.set_len(position).await?;
+
         Ok(())
     }
 }
 
+/// The details of an entry which has been written, as returned by [`EntryStreamWriter::finish()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrittenEntry {
+    crc32: u32,
+    compressed_size: u64,
+    uncompressed_size: u64,
+    header_offset: u64,
+    data_offset: u64,
+}
+
+impl WrittenEntry {
+    /// Returns the entry's CRC32 value, as stored within the archive.
+    pub fn crc32(&self) -> u32 {
+        self.crc32
+    }
+
+    /// Returns the size of the entry's data once compressed (and encrypted, if applicable).
+    pub fn compressed_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    /// Returns the size of the entry's data before compression.
+    pub fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// Returns the offset of the entry's local file header, relative to the start of the writer.
+    pub fn header_offset(&self) -> u64 {
+        self.header_offset
+    }
+
+    /// Returns the offset of the entry's data, relative to the start of the writer.
+    pub fn data_offset(&self) -> u64 {
+        self.data_offset
+    }
+}
+
 impl<'a, W: AsyncWrite + Unpin> AsyncWrite for EntryStreamWriter<'a, W> {
     fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
         let poll = Pin::new(&mut self.writer).poll_write(cx, buf);
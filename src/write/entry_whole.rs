@@ -4,9 +4,13 @@
 use crate::entry::ZipEntry;
 use crate::error::Result;
 use crate::spec::compression::Compression;
+use crate::spec::consts::{LFH_LENGTH, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH};
 use crate::spec::header::{CentralDirectoryRecord, GeneralPurposeFlag, LocalFileHeader};
+use crate::write::crypto::EntryCipher;
 use crate::write::{CentralDirectoryEntry, ZipFileWriter};
 
+use std::borrow::Cow;
+
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use std::io::Cursor;
 
@@ -27,65 +31,145 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
     }
 
     pub async fn write(self) -> Result<()> {
-        let mut _compressed_data: Option<Vec<u8>> = None;
-        let compressed_data = match self.entry.compression() {
-            Compression::Stored => self.data,
-            #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
-            _ => {
-                _compressed_data =
-                    Some(compress(self.entry.compression(), self.data, self.entry.compression_level).await);
-                _compressed_data.as_ref().unwrap()
-            }
-        };
-
-        let lf_header = LocalFileHeader {
-            compressed_size: compressed_data.len() as u32,
-            uncompressed_size: self.data.len() as u32,
-            compression: self.entry.compression().into(),
-            crc: compute_crc(self.data),
-            extra_field_length: self.entry.extra_field().len() as u16,
-            file_name_length: self.entry.filename().as_bytes().len() as u16,
-            mod_time: self.entry.last_modification_date().time,
-            mod_date: self.entry.last_modification_date().date,
-            version: crate::spec::version::as_needed_to_extract(&self.entry),
-            flags: GeneralPurposeFlag {
-                data_descriptor: false,
-                encrypted: false,
-                filename_unicode: !self.entry.filename().is_ascii(),
-            },
-        };
-
-        let header = CentralDirectoryRecord {
-            v_made_by: crate::spec::version::as_made_by(),
-            v_needed: lf_header.version,
-            compressed_size: lf_header.compressed_size,
-            uncompressed_size: lf_header.uncompressed_size,
-            compression: lf_header.compression,
-            crc: lf_header.crc,
-            extra_field_length: lf_header.extra_field_length,
-            file_name_length: lf_header.file_name_length,
-            file_comment_length: self.entry.comment().len() as u16,
-            mod_time: lf_header.mod_time,
-            mod_date: lf_header.mod_date,
-            flags: lf_header.flags,
-            disk_start: 0,
-            inter_attr: self.entry.internal_file_attribute(),
-            exter_attr: self.entry.external_file_attribute(),
-            lh_offset: self.writer.writer.offset() as u32,
-        };
-
-        self.writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
-        self.writer.writer.write_all(&lf_header.as_slice()).await?;
-        self.writer.writer.write_all(self.entry.filename().as_bytes()).await?;
-        self.writer.writer.write_all(self.entry.extra_field()).await?;
-        self.writer.writer.write_all(compressed_data).await?;
-
-        self.writer.cd_entries.push(CentralDirectoryEntry { header, entry: self.entry });
-
-        Ok(())
+        let (crc, encoded_data) = encode(&self.entry, self.data).await?;
+        write_encoded(self.writer, self.entry, crc, self.data.len() as u64, &encoded_data).await
     }
 }
 
+/// Compresses (and encrypts, if required) an entry's data, returning the CRC32 value to be written alongside it.
+pub(crate) async fn encode<'a>(entry: &ZipEntry, data: &'a [u8]) -> Result<(u32, Cow<'a, [u8]>)> {
+    let compressed_data = match entry.compression() {
+        Compression::Stored => Cow::Borrowed(data),
+        #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+        _ => Cow::Owned(compress(entry.compression(), data, entry.compression_level).await),
+    };
+
+    let crc = compute_crc(data);
+    let encoded_data = match entry.encryption.as_ref() {
+        Some(encryption) => {
+            let (mut cipher, mut encrypted_data) = EntryCipher::new(encryption, (crc >> 24) as u8)?;
+            let header_length = encrypted_data.len();
+
+            encrypted_data.extend_from_slice(&compressed_data);
+            cipher.encrypt(&mut encrypted_data[header_length..]);
+            encrypted_data.extend(cipher.finish());
+
+            Cow::Owned(encrypted_data)
+        }
+        None => compressed_data,
+    };
+
+    Ok((crate::write::crypto::stored_crc(entry, crc), encoded_data))
+}
+
+/// Writes an entry along with its already-encoded data, and pushes its central directory entry to the writer's store.
+pub(crate) async fn write_encoded<W: AsyncWrite + Unpin>(
+    writer: &mut ZipFileWriter<W>,
+    entry: ZipEntry,
+    crc: u32,
+    uncompressed_size: u64,
+    encoded_data: &[u8],
+) -> Result<()> {
+    let sizes = (uncompressed_size, encoded_data.len() as u64);
+    let cd_entry = write_lfh(writer, entry, crc, sizes).await?;
+
+    writer.writer.write_all(encoded_data).await?;
+    writer.cd_entries.push(cd_entry);
+
+    Ok(())
+}
+
+/// Writes the local file header of an entry whose CRC32 value and sizes are known upfront.
+///
+/// The entry's (already compressed) data must be written directly after, before pushing the returned central directory
+/// entry to the writer's store.
+pub(crate) async fn write_lfh<W: AsyncWrite + Unpin>(
+    writer: &mut ZipFileWriter<W>,
+    entry: ZipEntry,
+    crc: u32,
+    sizes: (u64, u64),
+) -> Result<CentralDirectoryEntry> {
+    let flags = GeneralPurposeFlag {
+        data_descriptor: false,
+        encrypted: entry.encryption.is_some(),
+        filename_unicode: entry.utf8_flag.enabled(entry.filename(), entry.comment()),
+        patched: false,
+        strong_encryption: false,
+        masked_header: false,
+    };
+
+    let compression = crate::write::crypto::compression_method(&entry);
+    let version = crate::spec::version::as_needed_to_extract(&entry);
+    write_lfh_with(writer, entry, crc, sizes, (compression, flags, version)).await
+}
+
+/// Writes an entry's local file header with the provided compression method, flags, and version needed to extract, and
+/// returns its central directory entry.
+pub(crate) async fn write_lfh_with<W: AsyncWrite + Unpin>(
+    writer: &mut ZipFileWriter<W>,
+    entry: ZipEntry,
+    crc: u32,
+    sizes: (u64, u64),
+    (compression, flags, mut version): (u16, GeneralPurposeFlag, u16),
+) -> Result<CentralDirectoryEntry> {
+    let (uncompressed_size, compressed_size) = sizes;
+    let lh_offset = writer.writer.offset();
+
+    // The local file header's ZIP64 extra field must hold both sizes if either is out of range.
+    let zip64 = entry.force_zip64
+        || crate::write::saturate(uncompressed_size) == NON_ZIP64_MAX_SIZE
+        || crate::write::saturate(compressed_size) == NON_ZIP64_MAX_SIZE;
+
+    let mut lfh_extra_field = crate::spec::extra_field::without_zip64_extra_field(entry.extra_field());
+
+    if zip64 {
+        lfh_extra_field.extend(crate::spec::extra_field::zip64_extra_field(&[uncompressed_size, compressed_size]));
+        version = std::cmp::max(version, crate::spec::version::ZIP64_VERSION_NEEDED);
+    }
+
+    let data_offset = lh_offset + (SIGNATURE_LENGTH + LFH_LENGTH + entry.filename().len()) as u64;
+    lfh_extra_field.extend(crate::write::alignment_padding(&entry, data_offset + lfh_extra_field.len() as u64));
+
+    let lf_header = LocalFileHeader {
+        compressed_size: if zip64 { NON_ZIP64_MAX_SIZE } else { crate::write::saturate(compressed_size) },
+        uncompressed_size: if zip64 { NON_ZIP64_MAX_SIZE } else { crate::write::saturate(uncompressed_size) },
+        compression,
+        crc,
+        extra_field_length: lfh_extra_field.len() as u16,
+        file_name_length: entry.filename().len() as u16,
+        mod_time: entry.last_modification_date().time,
+        mod_date: entry.last_modification_date().date,
+        version,
+        flags,
+    };
+
+    let header = CentralDirectoryRecord {
+        v_made_by: crate::spec::version::as_made_by(entry.attribute_compatibility()),
+        v_needed: lf_header.version,
+        compressed_size: 0,
+        uncompressed_size: 0,
+        compression: lf_header.compression,
+        crc: lf_header.crc,
+        extra_field_length: 0,
+        file_name_length: lf_header.file_name_length,
+        file_comment_length: entry.comment().len() as u16,
+        mod_time: lf_header.mod_time,
+        mod_date: lf_header.mod_date,
+        flags: lf_header.flags,
+        disk_start: 0,
+        inter_attr: entry.internal_file_attribute(),
+        exter_attr: entry.external_file_attribute(),
+        lh_offset: 0,
+    };
+
+    writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
+    writer.writer.write_all(&lf_header.as_slice()).await?;
+    writer.writer.write_all(entry.filename().as_bytes()).await?;
+    writer.writer.write_all(&lfh_extra_field).await?;
+
+    Ok(CentralDirectoryEntry::new(header, entry, sizes, lh_offset))
+}
+
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 async fn compress(compression: Compression, data: &[u8], level: async_compression::Level) -> Vec<u8> {
     // TODO: Reduce reallocations of Vec by making a lower-bound estimate of the length reduction and
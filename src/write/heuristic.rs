@@ -0,0 +1,44 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::path::Path;
+
+/// The extensions of formats whose data is already compressed, and so is unlikely to benefit from further compression.
+const COMPRESSED_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "heic", "heif", "avif", "jxl", "mp3", "m4a", "aac", "ogg", "opus", "flac",
+    "mp4", "m4v", "mkv", "mov", "avi", "webm", "zip", "gz", "tgz", "bz2", "xz", "txz", "zst", "lz4", "lzma", "7z",
+    "rar", "cab", "jar", "apk", "docx", "xlsx", "pptx", "odt", "ods", "odp", "epub", "woff", "woff2",
+];
+
+/// The maximum number of bytes sampled when estimating the compressibility of an entry's data.
+const SAMPLE_LENGTH: usize = 64 * 1024;
+
+/// The entropy (in bits per byte) above which sampled data is considered to be incompressible.
+const ENTROPY_THRESHOLD: f64 = 7.5;
+
+/// Returns whether an entry should be stored rather than compressed, based on its filename and a sample of its data.
+pub(crate) fn prefer_stored(filename: &str, data: Option<&[u8]>) -> bool {
+    let compressed_extension = Path::new(filename)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| COMPRESSED_EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(extension)))
+        .unwrap_or_default();
+
+    compressed_extension
+        || data.map(|data| entropy(&data[..data.len().min(SAMPLE_LENGTH)]) > ENTROPY_THRESHOLD).unwrap_or_default()
+}
+
+/// Returns the Shannon entropy of some data in bits per byte (between 0 and 8).
+fn entropy(data: &[u8]) -> f64 {
+    let mut counts = [0usize; 256];
+    data.iter().for_each(|byte| counts[*byte as usize] += 1);
+
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let probability = *count as f64 / data.len() as f64;
+            -probability * probability.log2()
+        })
+        .sum()
+}
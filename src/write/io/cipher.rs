@@ -0,0 +1,87 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::write::crypto::EntryCipher;
+
+use std::io::Error;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+/// A wrapper around an [`AsyncWrite`] implementation which encrypts all data written to it (if keyed).
+///
+/// Encrypted data is buffered until the inner writer accepts it, so the keystream only ever advances once per byte.
+/// Any trailer required by the cipher is written when this wrapper is shut down.
+pub struct CipherWriter<W: AsyncWrite + Unpin> {
+    inner: W,
+    cipher: Option<EntryCipher>,
+    pending: Vec<u8>,
+}
+
+impl<W: AsyncWrite + Unpin> CipherWriter<W> {
+    /// Constructs a new wrapper which passes all data through to the inner writer unmodified.
+    pub fn new(inner: W) -> Self {
+        Self { inner, cipher: None, pending: Vec::new() }
+    }
+
+    /// Constructs a new wrapper which encrypts all data, first writing the cipher's header.
+    pub fn with_cipher(inner: W, cipher: EntryCipher, header: Vec<u8>) -> Self {
+        Self { inner, cipher: Some(cipher), pending: header }
+    }
+
+    /// Consumes this wrapper and returns the inner [`AsyncWrite`] writer.
+    ///
+    /// Any pending data must have been written beforehand (eg. by shutting down this wrapper).
+    pub fn into_inner(self) -> W {
+        debug_assert!(self.pending.is_empty(), "cipher writer consumed with pending data");
+        self.inner
+    }
+
+    fn poll_pending(&mut self, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        while !self.pending.is_empty() {
+            let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.pending))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into()));
+            }
+
+            self.pending.drain(..written);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for CipherWriter<W> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
+        let this = &mut *self;
+        ready!(this.poll_pending(cx))?;
+
+        match this.cipher.as_mut() {
+            Some(cipher) => {
+                let start = this.pending.len();
+                this.pending.extend_from_slice(buf);
+                cipher.encrypt(&mut this.pending[start..]);
+                Poll::Ready(Ok(buf.len()))
+            }
+            None => Pin::new(&mut this.inner).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        ready!(self.poll_pending(cx))?;
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        ready!(self.poll_pending(cx))?;
+
+        if let Some(cipher) = self.cipher.take() {
+            self.pending = cipher.finish();
+            ready!(self.poll_pending(cx))?;
+        }
+
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::io::{Error, SeekFrom};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::ReadBuf;
+
+/// A wrapper which implements tokio's IO traits over implementers of the `futures-io` IO traits.
+#[derive(Clone)]
+pub struct Compat<T> {
+    inner: T,
+    // tokio splits a seek into starting and completing it, whereas `futures-io` requires the position on every poll.
+    seek: Option<SeekFrom>,
+}
+
+impl<T> Compat<T> {
+    /// Constructs a new wrapper around the provided value.
+    pub(crate) fn new(inner: T) -> Self {
+        Self { inner, seek: None }
+    }
+
+    /// Returns a mutable reference to the inner value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper and returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<W: futures_io::AsyncWrite + Unpin> tokio::io::AsyncWrite for Compat<W> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+impl<R: futures_io::AsyncRead + Unpin> tokio::io::AsyncRead for Compat<R> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let read = ready!(Pin::new(&mut self.inner).poll_read(cx, buf.initialize_unfilled()))?;
+        buf.advance(read);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: futures_io::AsyncSeek + Unpin> tokio::io::AsyncSeek for Compat<S> {
+    fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        self.seek = Some(position);
+        Ok(())
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        // tokio polls for completion without a pending seek to query the current position.
+        let position = self.seek.unwrap_or(SeekFrom::Current(0));
+        let result = ready!(Pin::new(&mut self.inner).poll_seek(cx, position));
+
+        self.seek = None;
+        Poll::Ready(result)
+    }
+}
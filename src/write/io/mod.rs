@@ -1,4 +1,7 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod cipher;
+#[cfg(feature = "futures")]
+pub(crate) mod compat;
 pub(crate) mod offset;
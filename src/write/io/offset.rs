@@ -4,12 +4,12 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-io-utilities/blob/main/LICENSE)
 
-use std::io::{Error, IoSlice};
+use std::io::{Error, IoSlice, SeekFrom};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use pin_project::pin_project;
-use tokio::io::AsyncWrite;
+use tokio::io::{AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
 
 /// A wrapper around an [`AsyncWrite`] implementation which tracks the current byte offset.
 #[pin_project(project = OffsetWriterProj)]
@@ -19,7 +19,7 @@ where
 {
     #[pin]
     inner: W,
-    offset: usize,
+    offset: u64,
 }
 
 impl<W> AsyncOffsetWriter<W>
@@ -31,8 +31,13 @@ where
         Self { inner, offset: 0 }
     }
 
+    /// Constructs a new wrapper from an inner [`AsyncWrite`] writer, starting at the provided byte offset.
+    pub fn with_offset(inner: W, offset: u64) -> Self {
+        Self { inner, offset }
+    }
+
     /// Returns the current byte offset.
-    pub fn offset(&self) -> usize {
+    pub fn offset(&self) -> u64 {
         self.offset
     }
 
@@ -46,6 +51,22 @@ where
     }
 }
 
+impl<W> AsyncOffsetWriter<W>
+where
+    W: AsyncWrite + AsyncSeek + Unpin,
+{
+    /// Seeks the inner writer back to an earlier byte offset, so that any bytes written since are overwritten.
+    ///
+    /// The seek is relative to the inner writer's current position, as it may not have started at offset zero.
+    pub(crate) async fn rewind_to(&mut self, offset: u64) -> std::io::Result<()> {
+        self.inner.flush().await?;
+        self.inner.seek(SeekFrom::Current(-((self.offset - offset) as i64))).await?;
+        self.offset = offset;
+
+        Ok(())
+    }
+}
+
 impl<W> AsyncWrite for AsyncOffsetWriter<W>
 where
     W: AsyncWrite + Unpin,
@@ -55,7 +76,7 @@ where
         let poll = this.inner.poll_write(cx, buf);
 
         if let Poll::Ready(Ok(inner)) = &poll {
-            *this.offset += inner;
+            *this.offset += *inner as u64;
         }
 
         poll
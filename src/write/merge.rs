@@ -0,0 +1,85 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::{Result, ZipError};
+use crate::write::ZipFileWriter;
+
+use std::collections::HashMap;
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite};
+
+/// The policy which determines how [`merge()`] handles entries with the same filename.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Only keep the first entry with a given filename.
+    #[default]
+    KeepFirst,
+    /// Only keep the last entry with a given filename.
+    KeepLast,
+    /// Keep every entry, writing duplicate filenames as-is.
+    KeepAll,
+    /// Return [`ZipError::DuplicateFilename`] before anything is written.
+    Error,
+}
+
+/// Merges the entries of several ZIP files into one, writing the result to the provided writer.
+///
+/// Entries are copied in the order of the sources (and their central directories) without decompressing or
+/// recompressing their data (see [`ZipFileWriter::copy_entry_raw()`]). Entries with the same filename, whether within
+/// the same source or across sources, are handled as per the provided policy. The sources' ZIP file comments aren't
+/// carried over.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::write::{merge, ConflictPolicy};
+/// # use async_zip::error::Result;
+/// # use tokio::fs::File;
+/// #
+/// async fn run() -> Result<()> {
+///     let sources = vec![File::open("./foo.zip").await?, File::open("./bar.zip").await?];
+///     merge(sources, File::create("./baz.zip").await?, ConflictPolicy::KeepLast).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn merge<I, R, W>(sources: I, writer: W, policy: ConflictPolicy) -> Result<W>
+where
+    I: IntoIterator<Item = R>,
+    R: AsyncRead + AsyncSeek + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut files = Vec::new();
+
+    for mut reader in sources {
+        let file = crate::read::file(&mut reader).await?;
+        files.push((reader, file));
+    }
+
+    // Maps each filename to the (source, entry) index of the entry which is kept.
+    let mut kept: HashMap<&str, (usize, usize)> = HashMap::new();
+
+    for (source, (_, file)) in files.iter().enumerate() {
+        for (index, stored_entry) in file.entries().iter().enumerate() {
+            let filename = stored_entry.entry().filename();
+
+            match (policy, kept.contains_key(filename)) {
+                (ConflictPolicy::Error, true) => return Err(ZipError::DuplicateFilename(filename.to_string())),
+                (ConflictPolicy::KeepFirst, true) => continue,
+                _ => kept.insert(filename, (source, index)),
+            };
+        }
+    }
+
+    let kept: HashMap<String, (usize, usize)> =
+        kept.into_iter().map(|(filename, position)| (filename.to_string(), position)).collect();
+    let mut writer = ZipFileWriter::new(writer);
+
+    for (source, (reader, file)) in files.iter_mut().enumerate() {
+        for (index, stored_entry) in file.entries().iter().enumerate() {
+            if policy == ConflictPolicy::KeepAll || kept[stored_entry.entry().filename()] == (source, index) {
+                writer.copy_entry_raw(stored_entry, None, &mut *reader).await?;
+            }
+        }
+    }
+
+    writer.close().await
+}
@@ -3,6 +3,12 @@
 
 //! A module which supports writing ZIP files.
 //!
+//! The writer never seeks, so any [`AsyncWrite`] implementer may be written to (including non-seekable sinks such as
+//! sockets or HTTP response bodies). Entries of unknown size are written via
+//! [`ZipFileWriter::write_entry_stream()`] or [`ZipFileWriter::write_entry_from_reader()`], which set bit 3 of the
+//! general purpose flag within the local file header and emit a trailing data descriptor once the entry's data has
+//! been written.
+//!
 //! # Example
 //! ### Whole data (u8 slice)
 //! ```no_run
@@ -50,24 +56,223 @@
 //! # }
 //! ```
 
+#[cfg(feature = "body")]
+pub mod body;
+pub(crate) mod central_directory;
+pub(crate) mod comment;
+#[cfg(feature = "futures")]
+pub mod compat;
 pub(crate) mod compressed_writer;
+pub(crate) mod crypto;
+pub(crate) mod edit;
 pub(crate) mod entry_stream;
 pub(crate) mod entry_whole;
+pub(crate) mod heuristic;
 pub(crate) mod io;
+pub(crate) mod merge;
+pub(crate) mod options;
+#[cfg(feature = "parallel")]
+pub(crate) mod parallel;
+pub(crate) mod recompress;
+#[cfg(feature = "futures")]
+pub(crate) mod sink;
+pub mod split;
 
-pub use entry_stream::EntryStreamWriter;
+pub use central_directory::CentralDirectoryWriter;
+pub use comment::update_comment;
+pub use edit::ZipEdits;
+pub use entry_stream::{EntryStreamWriter, WrittenEntry};
+pub use merge::{merge, ConflictPolicy};
+pub use options::{EntryOptions, WriterOptions};
+pub use recompress::recompress;
+#[cfg(feature = "futures")]
+pub use sink::EntrySink;
 
-use crate::entry::ZipEntry;
-use crate::error::Result;
-use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader};
+use crate::entry::builder::ZipEntryBuilder;
+use crate::entry::{StoredZipEntry, ZipEntry};
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::spec::attribute::AttributeCompatibility;
+use crate::spec::compression::Compression;
+use crate::spec::consts::{ALIGNMENT_EXTRA_FIELD_HEADER_ID, EXTENDED_TIMESTAMP_HEADER_ID};
+use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::extra_field::ExtraField;
+use crate::spec::filename::Utf8Flag;
+use crate::spec::header::{
+    CentralDirectoryRecord, EndOfCentralDirectoryHeader, Zip64EndOfCentralDirectoryLocator,
+    Zip64EndOfCentralDirectoryRecord,
+};
 use entry_whole::EntryWholeWriter;
 use io::offset::AsyncOffsetWriter;
+use split::VolumeLayout;
+
+use std::io::ErrorKind;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// The MS-DOS attribute bit marking an entry as a directory (within the lower byte of the external file attribute).
+const DOS_DIRECTORY_ATTRIBUTE: u32 = 0x10;
+
+/// The Unix mode given to directory entries without one (ie. `drwxr-xr-x`).
+const DEFAULT_UNIX_DIRECTORY_MODE: u32 = 0o40755;
 
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+/// The Unix file type bits marking an entry as a symbolic link (ie. `S_IFLNK`).
+const UNIX_SYMLINK_TYPE: u32 = 0o120000;
+
+/// The Unix permissions given to symbolic link entries without any (ie. `lrwxrwxrwx`).
+const DEFAULT_UNIX_SYMLINK_PERMISSIONS: u32 = 0o777;
 
 pub(crate) struct CentralDirectoryEntry {
     pub header: CentralDirectoryRecord,
     pub entry: ZipEntry,
+    /// The extra field data for the central directory, which may differ from the entry's own (eg. ZIP64 values).
+    pub extra_field: Vec<u8>,
+    pub lh_offset: u64,
+}
+
+impl CentralDirectoryEntry {
+    /// Constructs a new central directory entry, promoting any out-of-range values into a ZIP64 extra field.
+    ///
+    /// If the entry forces ZIP64 structures, its sizes are always promoted.
+    pub(crate) fn new(
+        mut header: CentralDirectoryRecord,
+        mut entry: ZipEntry,
+        sizes: (u64, u64),
+        lh_offset: u64,
+    ) -> Self {
+        let (uncompressed_size, compressed_size) = sizes;
+
+        // Record the final values within the entry itself, so that they're available within the write summary.
+        entry.crc32 = header.crc;
+        entry.uncompressed_size = uncompressed_size;
+        entry.compressed_size = compressed_size;
+
+        let mut zip64_values = Vec::new();
+
+        for (field, value, force) in [
+            (&mut header.uncompressed_size, uncompressed_size, entry.force_zip64),
+            (&mut header.compressed_size, compressed_size, entry.force_zip64),
+            (&mut header.lh_offset, lh_offset, false),
+        ] {
+            *field = if force { NON_ZIP64_MAX_SIZE } else { saturate(value) };
+
+            if *field == NON_ZIP64_MAX_SIZE {
+                zip64_values.push(value);
+            }
+        }
+
+        let mut extra_field = crate::spec::extra_field::without_zip64_extra_field(entry.extra_field());
+
+        if !zip64_values.is_empty() {
+            extra_field.extend(crate::spec::extra_field::zip64_extra_field(&zip64_values));
+            header.v_needed = std::cmp::max(header.v_needed, crate::spec::version::ZIP64_VERSION_NEEDED);
+        }
+
+        header.extra_field_length = extra_field.len() as u16;
+
+        Self { header, entry, extra_field, lh_offset }
+    }
+
+    /// Rebuilds this central directory entry with its local file header located within a split volume.
+    ///
+    /// The provided function returns the disk number & disk-relative offset of an offset within the whole output. The
+    /// whole-output offset is retained for the write summary.
+    pub(crate) fn relocate(self, locate: impl Fn(u64) -> (u32, u64)) -> Self {
+        let (disk, offset) = locate(self.lh_offset);
+        let sizes = (self.entry.uncompressed_size, self.entry.compressed_size);
+
+        let mut relocated = Self::new(self.header, self.entry, sizes, offset);
+        relocated.header.disk_start = disk as u16;
+        relocated.lh_offset = self.lh_offset;
+        relocated
+    }
+
+    /// Returns the length of this entry's central directory record.
+    pub(crate) fn record_length(&self) -> u64 {
+        let variable = self.entry.filename().len() + self.extra_field.len() + self.entry.comment().len();
+        (crate::spec::consts::SIGNATURE_LENGTH + crate::spec::consts::CDH_LENGTH + variable) as u64
+    }
+}
+
+/// Returns an extra field which pads a local file header so that its entry's data starts at an aligned offset.
+///
+/// The provided offset is that at which the data would start without any padding. Only stored entries are aligned (as
+/// aligning compressed data serves no purpose), and the field is empty if no alignment is required.
+pub(crate) fn alignment_padding(entry: &ZipEntry, data_offset: u64) -> Vec<u8> {
+    let alignment = u64::from(entry.alignment);
+
+    if alignment <= 1 || entry.compression() != Compression::Stored {
+        return Vec::new();
+    }
+
+    // The field holds the alignment itself, followed by however many zeroed bytes are needed.
+    let header_length = 6;
+    let padding = (alignment - (data_offset + header_length) % alignment) % alignment;
+
+    let mut field = Vec::with_capacity((header_length + padding) as usize);
+    field.extend_from_slice(&ALIGNMENT_EXTRA_FIELD_HEADER_ID.to_le_bytes());
+    field.extend_from_slice(&(2 + padding as u16).to_le_bytes());
+    field.extend_from_slice(&entry.alignment.to_le_bytes());
+    field.resize((header_length + padding) as usize, 0);
+    field
+}
+
+/// Returns whether the end of central directory values require a ZIP64 EOCDR (ie. any would be saturated).
+pub(crate) fn needs_zip64(num_of_entries: u64, size_cent_dir: u64, cent_dir_offset: u64) -> bool {
+    num_of_entries >= NON_ZIP64_MAX_NUM_FILES.into()
+        || size_cent_dir >= NON_ZIP64_MAX_SIZE.into()
+        || cent_dir_offset >= NON_ZIP64_MAX_SIZE.into()
+}
+
+/// Converts a value into its non-ZIP64 representation, saturating to the sentinel value if it's out of range.
+pub(crate) fn saturate(value: u64) -> u32 {
+    std::cmp::min(value, NON_ZIP64_MAX_SIZE.into()) as u32
+}
+
+/// The archive-wide policy which determines when entries are written using ZIP64 structures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Zip64Policy {
+    /// Only use ZIP64 structures where they're required (or where an entry forces them via
+    /// [`crate::ZipEntryBuilder::force_zip64()`]).
+    #[default]
+    Auto,
+    /// Always use ZIP64 structures for every entry.
+    Always,
+}
+
+/// A summary of a ZIP file which has been written, as returned by [`ZipFileWriter::close_with_summary()`].
+pub struct WriteSummary {
+    file: ZipFile,
+    cent_dir_offset: u64,
+    cent_dir_size: u64,
+    total_bytes: u64,
+}
+
+impl WriteSummary {
+    /// Returns the information of the ZIP file written, including each of its entries and their header offsets.
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Returns the offset at which the central directory was written.
+    pub fn central_directory_offset(&self) -> u64 {
+        self.cent_dir_offset
+    }
+
+    /// Returns the size in bytes of the central directory.
+    pub fn central_directory_size(&self) -> u64 {
+        self.cent_dir_size
+    }
+
+    /// Returns the total number of bytes written.
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    /// Consumes this summary and returns the information of the ZIP file written.
+    pub fn into_file(self) -> ZipFile {
+        self.file
+    }
 }
 
 /// A ZIP file writer which acts over AsyncWrite implementers.
@@ -77,28 +282,280 @@ pub(crate) struct CentralDirectoryEntry {
 pub struct ZipFileWriter<W: AsyncWrite + Unpin> {
     pub(crate) writer: AsyncOffsetWriter<W>,
     pub(crate) cd_entries: Vec<CentralDirectoryEntry>,
-    comment_opt: Option<String>,
+    comment_opt: Option<Vec<u8>>,
+    options: WriterOptions,
 }
 
 impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
     /// Construct a new ZIP file writer from a mutable reference to a writer.
     pub fn new(writer: W) -> Self {
-        Self { writer: AsyncOffsetWriter::new(writer), cd_entries: Vec::new(), comment_opt: None }
+        Self::with_options(writer, WriterOptions::default())
+    }
+
+    /// Construct a new ZIP file writer from a mutable reference to a writer and a set of archive-wide options.
+    pub fn with_options(writer: W, options: WriterOptions) -> Self {
+        Self { writer: AsyncOffsetWriter::new(writer), cd_entries: Vec::new(), comment_opt: None, options }
+    }
+
+    /// Returns a new entry builder which uses this writer's default compression method.
+    ///
+    /// See [`WriterOptions::default_compression()`].
+    pub fn entry_builder(&self, filename: String) -> ZipEntryBuilder {
+        ZipEntryBuilder::new(filename, self.options.default_compression_or_stored())
     }
 
     /// Write a new ZIP entry of known size and data.
     pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
-        EntryWholeWriter::from_raw(self, entry.into(), data).write().await
+        let entry = self.prepare_entry(entry.into(), Some(data))?;
+        EntryWholeWriter::from_raw(self, entry, data).write().await
+    }
+
+    /// Write a new ZIP entry of known size and data, overriding the entry's options with those provided.
+    ///
+    /// See [`EntryOptions`].
+    pub async fn write_entry_whole_with_options<E>(
+        &mut self,
+        entry: E,
+        data: &[u8],
+        options: EntryOptions,
+    ) -> Result<()>
+    where
+        E: Into<ZipEntry>,
+    {
+        self.write_entry_whole(options.apply(entry.into()), data).await
+    }
+
+    /// Write a directory entry, so that the directory is recreated on extraction even if it holds no other entries.
+    ///
+    /// A trailing slash is appended to the entry's filename if it's missing, and the entry is always stored without
+    /// compression or data. The MS-DOS directory attribute is set, as is a default Unix mode of `0o40755` where the
+    /// entry doesn't already specify one.
+    pub async fn write_dir_entry<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<()> {
+        let mut entry = entry.into();
+        entry.filename_policy.apply(&mut entry.filename)?;
+
+        if !entry.filename.ends_with('/') {
+            entry.filename.push('/');
+        }
+
+        entry.compression = Compression::Stored;
+        entry.external_file_attribute |= DOS_DIRECTORY_ATTRIBUTE;
+
+        if entry.attribute_compatibility == AttributeCompatibility::Unix && entry.external_file_attribute >> 16 == 0 {
+            entry.external_file_attribute |= DEFAULT_UNIX_DIRECTORY_MODE << 16;
+        }
+
+        self.write_entry_whole(entry, &[]).await
+    }
+
+    /// Write a symbolic link entry, with its data being the path of the link's target.
+    ///
+    /// The entry is always stored without compression, and the file type bits of its Unix mode are set to mark it as
+    /// a symbolic link (with a default permissions mode of `0o777` where the entry doesn't already specify one).
+    pub async fn write_symlink_entry<E: Into<ZipEntry>>(&mut self, entry: E, target: &str) -> Result<()> {
+        let mut entry = entry.into();
+        entry.compression = Compression::Stored;
+        entry.attribute_compatibility = AttributeCompatibility::Unix;
+
+        let mut permissions = (entry.external_file_attribute >> 16) & 0o7777;
+        if permissions == 0 {
+            permissions = DEFAULT_UNIX_SYMLINK_PERMISSIONS;
+        }
+
+        entry.external_file_attribute =
+            (entry.external_file_attribute & 0xFFFF) | (UNIX_SYMLINK_TYPE | permissions) << 16;
+
+        self.write_entry_whole(entry, target.as_bytes()).await
     }
 
     /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
     pub async fn write_entry_stream<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<EntryStreamWriter<'_, W>> {
-        EntryStreamWriter::from_raw(self, entry.into()).await
+        let entry = self.prepare_entry(entry.into(), None)?;
+        EntryStreamWriter::from_raw(self, entry).await
+    }
+
+    /// Write an entry of unknown size and data via streaming, overriding the entry's options with those provided.
+    ///
+    /// This accepts the same [`EntryOptions`] as [`ZipFileWriter::write_entry_whole_with_options()`].
+    pub async fn write_entry_stream_with_options<E>(
+        &mut self,
+        entry: E,
+        options: EntryOptions,
+    ) -> Result<EntryStreamWriter<'_, W>>
+    where
+        E: Into<ZipEntry>,
+    {
+        self.write_entry_stream(options.apply(entry.into())).await
+    }
+
+    /// Copy an entry from another archive without decompressing & recompressing its data.
+    ///
+    /// The entry's compressed data is read verbatim from the provided reader (which must be a source of the archive
+    /// the entry was read from), and its metadata (including its CRC32 value, sizes, compression method, and general
+    /// purpose flags) is carried over from the central directory. As such, encrypted entries remain decryptable. A new
+    /// filename may optionally be provided to rename the entry.
+    ///
+    /// Of this writer's options, only the ZIP64 policy applies to copied entries.
+    pub async fn copy_entry_raw<R>(
+        &mut self,
+        stored_entry: &StoredZipEntry,
+        filename: Option<String>,
+        reader: R,
+    ) -> Result<()>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        let mut reader = BufReader::with_capacity(self.options.buffer_size_or_default(), reader);
+        stored_entry.seek_to_data_offset(&mut reader).await?;
+
+        let mut entry = stored_entry.entry().clone();
+        let mut flags = stored_entry.general_purpose_flag;
+
+        if let Some(filename) = filename {
+            flags.filename_unicode |= !filename.is_ascii();
+            entry.filename = filename;
+        }
+
+        entry.force_zip64 |= self.options.zip64_policy == Zip64Policy::Always;
+        let mut version = crate::spec::version::as_needed_to_extract(&entry);
+
+        if flags.encrypted {
+            version = std::cmp::max(version, 20);
+        }
+        if stored_entry.compression_method == crate::spec::consts::AES_COMPRESSION_METHOD {
+            version = std::cmp::max(version, crate::spec::version::AES_VERSION_NEEDED);
+        }
+
+        let (crc, compressed_size) = (entry.crc32(), entry.compressed_size());
+        let sizes = (entry.uncompressed_size(), compressed_size);
+        let zip64 =
+            entry.force_zip64 || saturate(sizes.0) == NON_ZIP64_MAX_SIZE || saturate(sizes.1) == NON_ZIP64_MAX_SIZE;
+
+        let header = (stored_entry.compression_method, flags, version);
+        let cd_entry = entry_whole::write_lfh_with(self, entry, crc, sizes, header).await?;
+
+        let copied = tokio::io::copy_buf(&mut reader.take(compressed_size), &mut self.writer).await?;
+        if copied != compressed_size {
+            return Err(ZipError::UpstreamReadError(ErrorKind::UnexpectedEof.into()));
+        }
+
+        // The data descriptor flag is kept as ZipCrypto's password check byte depends upon it, so one must follow the
+        // data (even though the local file header already holds its values).
+        if flags.data_descriptor {
+            self.writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
+            self.writer.write_all(&crc.to_le_bytes()).await?;
+
+            if zip64 {
+                self.writer.write_all(&sizes.1.to_le_bytes()).await?;
+                self.writer.write_all(&sizes.0.to_le_bytes()).await?;
+            } else {
+                self.writer.write_all(&(sizes.1 as u32).to_le_bytes()).await?;
+                self.writer.write_all(&(sizes.0 as u32).to_le_bytes()).await?;
+            }
+        }
+
+        self.cd_entries.push(cd_entry);
+        Ok(())
+    }
+
+    /// Write an entry by streaming its data from a reader until EOF (ie. using a data descriptor).
+    ///
+    /// The data is piped through compression via an internal buffer so that it never needs to be held in memory in
+    /// full, with the CRC32 value and sizes being computed as it's written. Returns the number of uncompressed bytes
+    /// written.
+    pub async fn write_entry_from_reader<E, R>(&mut self, entry: E, reader: R) -> Result<u64>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        let mut reader = BufReader::with_capacity(self.options.buffer_size_or_default(), reader);
+        let mut entry_writer = self.write_entry_stream(entry).await?;
+
+        let written = tokio::io::copy_buf(&mut reader, &mut entry_writer).await?;
+        entry_writer.close().await?;
+
+        Ok(written)
     }
 
     /// Set the ZIP file comment.
     pub fn comment(&mut self, comment: String) {
+        self.comment_opt = Some(comment.into_bytes());
+    }
+
+    /// Set the ZIP file comment from raw bytes.
+    ///
+    /// Unlike [`ZipFileWriter::comment()`], the comment isn't required to be valid UTF-8, which allows comments in
+    /// legacy encodings (eg. CP437) to be written as-is. Returns an error if the comment is longer than 65535 bytes.
+    pub fn comment_raw(&mut self, comment: Vec<u8>) -> Result<()> {
+        if comment.len() > u16::MAX as usize {
+            return Err(ZipError::CommentTooLong);
+        }
+
         self.comment_opt = Some(comment);
+        Ok(())
+    }
+
+    /// Returns the length of the ZIP file comment which will be written.
+    pub(crate) fn comment_length(&self) -> usize {
+        self.comment_opt.as_ref().map(Vec::len).unwrap_or_default()
+    }
+
+    /// Set the policy which determines when entries are written using ZIP64 structures.
+    ///
+    /// This only applies to entries written after it's set.
+    pub fn zip64_policy(&mut self, policy: Zip64Policy) {
+        self.options.zip64_policy = policy;
+    }
+
+    /// Applies the archive-wide options and any entry options which are resolved when written.
+    ///
+    /// Returns an error if the entry's filename is rejected by its filename policy.
+    ///
+    /// If the entry's data is known upfront, it may be provided to sample when choosing a compression method.
+    pub(crate) fn prepare_entry(&self, mut entry: ZipEntry, data: Option<&[u8]>) -> Result<ZipEntry> {
+        entry.filename_policy.apply(&mut entry.filename)?;
+        entry.force_zip64 |= self.options.zip64_policy == Zip64Policy::Always;
+
+        if entry.utf8_flag == Utf8Flag::Auto {
+            entry.utf8_flag = self.options.utf8_flag;
+        }
+
+        entry.utf8_flag.check(&entry.filename, &entry.comment)?;
+
+        #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+        if matches!(entry.compression_level, async_compression::Level::Default) {
+            entry.compression_level = self.options.default_level.into_level();
+        }
+
+        if entry.auto_compression && heuristic::prefer_stored(entry.filename(), data) {
+            entry.compression = Compression::Stored;
+        }
+
+        if let Some(seconds) = entry.unix_modification_time.filter(|_| entry.extended_timestamp) {
+            if !crate::spec::extra_field::has_field(&entry.extra_field, EXTENDED_TIMESTAMP_HEADER_ID) {
+                // The extended timestamp field only holds 32-bit values, so later times are saturated.
+                let modified = Some(i32::try_from(seconds).unwrap_or(i32::MAX));
+                let field = ExtraField::ExtendedTimestamp { modified, accessed: None, created: None };
+                entry.extra_field.extend(field.to_bytes());
+            }
+        }
+
+        // The AES extra field holds the entry's actual compression method, and so is required by readers.
+        #[cfg(feature = "aes")]
+        if matches!(entry.encryption, Some(crate::spec::encryption::Encryption::Aes256(_)))
+            && !crate::spec::extra_field::has_field(&entry.extra_field, crate::spec::consts::AES_EXTRA_FIELD_HEADER_ID)
+        {
+            entry.extra_field.extend(crate::write::crypto::aes::extra_field(&entry));
+        }
+
+        Ok(entry)
+    }
+
+    /// Returns the number of bytes written to the inner writer so far.
+    ///
+    /// When called between entries, this is the offset at which the next entry's local file header will be written.
+    pub fn offset(&self) -> u64 {
+        self.writer.offset()
     }
 
     /// Returns a mutable reference to the inner writer.
@@ -112,37 +569,136 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
     ///
     /// This includes:
     /// - Writing all central directroy headers.
+    /// - Writing the ZIP64 end of central directory record & locator (if required).
     /// - Writing the end of central directory header.
     /// - Writing the file comment.
+    /// - Flushing the inner writer.
+    ///
+    /// The inner writer isn't shut down, so further data may still be written to it (see
+    /// [`ZipFileWriter::close_and_shutdown()`]).
     ///
     /// Failiure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<W> {
+    pub async fn close(self) -> Result<W> {
+        Ok(self.close_with_summary().await?.0)
+    }
+
+    /// Consumes this ZIP writer, completes all closing tasks, and then shuts down the inner writer.
+    ///
+    /// This is equivalent to [`ZipFileWriter::close()`], but also finalises wrapping writers (eg. multipart uploaders
+    /// or hashing writers) via [`AsyncWriteExt::shutdown()`]. The inner writer is returned along with the total number
+    /// of bytes written to it.
+    pub async fn close_and_shutdown(self) -> Result<(W, u64)> {
+        let (mut writer, summary) = self.close_with_summary().await?;
+        writer.shutdown().await?;
+        Ok((writer, summary.total_bytes()))
+    }
+
+    /// Consumes this ZIP writer, completes all closing tasks, and returns a summary of what was written.
+    ///
+    /// This is equivalent to [`ZipFileWriter::close()`], but also returns the information of every entry written
+    /// (including their final CRC32 values, sizes, and local file header offsets) so manifests may be produced without
+    /// re-reading the ZIP file.
+    pub async fn close_with_summary(self) -> Result<(W, WriteSummary)> {
+        self.close_with_layout(None).await
+    }
+
+    /// Completes all closing tasks, locating each header within its volume as per the provided layout (if any).
+    pub(crate) async fn close_with_layout(mut self, layout: Option<VolumeLayout>) -> Result<(W, WriteSummary)> {
+        let locate = |offset: u64| layout.map(|layout| layout.locate(offset)).unwrap_or((0, offset));
         let cd_offset = self.writer.offset();
+        let mut cd_disks = Vec::with_capacity(self.cd_entries.len());
+
+        if layout.is_some() {
+            self.cd_entries =
+                std::mem::take(&mut self.cd_entries).into_iter().map(|cd_entry| cd_entry.relocate(locate)).collect();
+        }
 
         for entry in &self.cd_entries {
+            cd_disks.push(locate(self.writer.offset()).0);
             self.writer.write_all(&crate::spec::consts::CDH_SIGNATURE.to_le_bytes()).await?;
             self.writer.write_all(&entry.header.as_slice()).await?;
             self.writer.write_all(entry.entry.filename().as_bytes()).await?;
-            self.writer.write_all(entry.entry.extra_field()).await?;
+            self.writer.write_all(&entry.extra_field).await?;
             self.writer.write_all(entry.entry.comment().as_bytes()).await?;
         }
 
+        let num_of_entries = self.cd_entries.len() as u64;
+        let size_cent_dir = self.writer.offset() - cd_offset;
+        let (cd_disk, cd_disk_offset) = locate(cd_offset);
+        let (disk_num, _) = locate(self.writer.offset());
+        let num_of_entries_disk = cd_disks.iter().filter(|disk| **disk == disk_num).count() as u64;
+
+        // Any values which don't fit within the EOCDR are saturated to their sentinel values, with the actual values
+        // instead being held within a ZIP64 EOCDR (which is then pointed to by a ZIP64 EOCD locator).
+        let saturate_entries = |num: u64| std::cmp::min(num, NON_ZIP64_MAX_NUM_FILES.into()) as u16;
         let header = EndOfCentralDirectoryHeader {
-            disk_num: 0,
-            start_cent_dir_disk: 0,
-            num_of_entries_disk: self.cd_entries.len() as u16,
-            num_of_entries: self.cd_entries.len() as u16,
-            size_cent_dir: (self.writer.offset() - cd_offset) as u32,
-            cent_dir_offset: cd_offset as u32,
-            file_comm_length: self.comment_opt.as_ref().map(|v| v.len() as u16).unwrap_or_default(),
+            disk_num: disk_num as u16,
+            start_cent_dir_disk: cd_disk as u16,
+            num_of_entries_disk: saturate_entries(num_of_entries_disk),
+            num_of_entries: saturate_entries(num_of_entries),
+            size_cent_dir: saturate(size_cent_dir),
+            cent_dir_offset: saturate(cd_disk_offset),
+            file_comm_length: self.comment_length() as u16,
         };
 
+        let zip64 = needs_zip64(num_of_entries, size_cent_dir, cd_disk_offset);
+
+        if zip64 {
+            let (zip64_eocdr_disk, zip64_eocdr_offset) = locate(self.writer.offset());
+            let zip64_eocdr = Zip64EndOfCentralDirectoryRecord {
+                size_of_record: (crate::spec::consts::ZIP64_EOCDR_LENGTH - 8) as u64,
+                v_made_by: crate::spec::version::as_made_by(AttributeCompatibility::Unix),
+                v_needed: crate::spec::version::ZIP64_VERSION_NEEDED,
+                disk_num,
+                start_cent_dir_disk: cd_disk,
+                num_of_entries_disk,
+                num_of_entries,
+                size_cent_dir,
+                cent_dir_offset: cd_disk_offset,
+            };
+            let total_disks = disk_num + 1;
+            let locator = Zip64EndOfCentralDirectoryLocator { zip64_eocdr_disk, zip64_eocdr_offset, total_disks };
+
+            self.writer.write_all(&crate::spec::consts::ZIP64_EOCDR_SIGNATURE.to_le_bytes()).await?;
+            self.writer.write_all(&zip64_eocdr.as_slice()).await?;
+            self.writer.write_all(&crate::spec::consts::ZIP64_EOCDL_SIGNATURE.to_le_bytes()).await?;
+            self.writer.write_all(&locator.as_slice()).await?;
+        }
+
         self.writer.write_all(&crate::spec::consts::EOCDR_SIGNATURE.to_le_bytes()).await?;
         self.writer.write_all(&header.as_slice()).await?;
-        if let Some(comment) = self.comment_opt {
-            self.writer.write_all(comment.as_bytes()).await?;
+        if let Some(comment) = &self.comment_opt {
+            self.writer.write_all(comment).await?;
         }
 
-        Ok(self.writer.into_inner())
+        let entries = self
+            .cd_entries
+            .into_iter()
+            .map(|cd_entry| StoredZipEntry {
+                entry: cd_entry.entry,
+                general_purpose_flag: cd_entry.header.flags,
+                compression_method: cd_entry.header.compression,
+                file_offset: cd_entry.lh_offset,
+                disk_start: cd_entry.header.disk_start,
+                data_offset: None,
+            })
+            .collect();
+
+        let summary = WriteSummary {
+            file: ZipFile {
+                entries,
+                zip64,
+                comment: String::from_utf8_lossy(&self.comment_opt.unwrap_or_default()).into_owned(),
+                warnings: Vec::new(),
+                disk_number: header.disk_num,
+                cent_dir_disk_number: header.start_cent_dir_disk,
+            },
+            cent_dir_offset: cd_offset,
+            cent_dir_size: size_cent_dir,
+            total_bytes: self.writer.offset(),
+        };
+
+        self.writer.flush().await?;
+        Ok((self.writer.into_inner(), summary))
     }
 }
@@ -0,0 +1,232 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::builder::ZipEntryBuilder;
+use crate::entry::ZipEntry;
+use crate::spec::attribute::Attributes;
+use crate::spec::compression::Compression;
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+use crate::spec::compression::CompressionLevel;
+use crate::spec::encryption::Encryption;
+use crate::spec::extra_field::ExtraField;
+use crate::spec::filename::Utf8Flag;
+use crate::write::Zip64Policy;
+
+/// The default buffer size used when piping an entry's data from a reader, equal to 64KiB.
+const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// A set of archive-wide options which configure how a ZIP file is written.
+///
+/// Entries inherit these defaults wherever they don't specify an option of their own (see each option for details).
+///
+/// ### Example
+/// ```
+/// # use async_zip::write::{WriterOptions, Zip64Policy, ZipFileWriter};
+/// # use async_zip::Compression;
+/// #
+/// let options = WriterOptions::new().default_compression(Compression::Stored).zip64_policy(Zip64Policy::Always);
+/// let writer = ZipFileWriter::with_options(Vec::<u8>::new(), options);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WriterOptions {
+    pub(crate) default_compression: Option<Compression>,
+    #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    pub(crate) default_level: CompressionLevel,
+    pub(crate) zip64_policy: Zip64Policy,
+    pub(crate) utf8_flag: Utf8Flag,
+    pub(crate) buffer_size: Option<usize>,
+}
+
+impl WriterOptions {
+    /// Constructs a new set of options with every option at its default value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compression method of entries constructed via [`ZipFileWriter::entry_builder()`].
+    ///
+    /// Defaults to [`Compression::Stored`].
+    ///
+    /// [`ZipFileWriter::entry_builder()`]: crate::write::ZipFileWriter::entry_builder
+    pub fn default_compression(mut self, compression: Compression) -> Self {
+        self.default_compression = Some(compression);
+        self
+    }
+
+    /// Sets the level of compression used for entries which leave theirs at [`CompressionLevel::Default`].
+    ///
+    /// Note that this requires a compression feature to be enabled.
+    #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    pub fn default_level(mut self, level: CompressionLevel) -> Self {
+        self.default_level = level;
+        self
+    }
+
+    /// Sets the policy which determines when entries are written using ZIP64 structures.
+    ///
+    /// Defaults to [`Zip64Policy::Auto`].
+    pub fn zip64_policy(mut self, policy: Zip64Policy) -> Self {
+        self.zip64_policy = policy;
+        self
+    }
+
+    /// Sets the UTF-8 flag policy used for entries which leave theirs at [`Utf8Flag::Auto`].
+    pub fn utf8_flag(mut self, flag: Utf8Flag) -> Self {
+        self.utf8_flag = flag;
+        self
+    }
+
+    /// Sets the size of the buffer used when piping an entry's data from a reader.
+    ///
+    /// This applies to [`ZipFileWriter::write_entry_from_reader()`] and [`ZipFileWriter::copy_entry_raw()`], and
+    /// defaults to 64KiB.
+    ///
+    /// [`ZipFileWriter::write_entry_from_reader()`]: crate::write::ZipFileWriter::write_entry_from_reader
+    /// [`ZipFileWriter::copy_entry_raw()`]: crate::write::ZipFileWriter::copy_entry_raw
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = Some(size);
+        self
+    }
+
+    pub(crate) fn default_compression_or_stored(&self) -> Compression {
+        self.default_compression.unwrap_or(Compression::Stored)
+    }
+
+    pub(crate) fn buffer_size_or_default(&self) -> usize {
+        self.buffer_size.unwrap_or(DEFAULT_BUFFER_SIZE).max(1)
+    }
+}
+
+/// A set of options which configure how a single entry is written, distinct from the entry's metadata.
+///
+/// Each option which is set overrides that of the entry it's applied to, whilst unset options leave the entry as-is.
+/// This allows a single set of options to be shared across many entries, with the same options being accepted by both
+/// [`ZipFileWriter::write_entry_whole_with_options()`] and [`ZipFileWriter::write_entry_stream_with_options()`].
+///
+/// ### Example
+/// ```
+/// # use async_zip::write::EntryOptions;
+/// # use async_zip::Compression;
+/// #
+/// let options = EntryOptions::new().compression(Compression::Stored).alignment(4).comment("aligned".to_string());
+/// ```
+///
+/// [`ZipFileWriter::write_entry_whole_with_options()`]: crate::write::ZipFileWriter::write_entry_whole_with_options
+/// [`ZipFileWriter::write_entry_stream_with_options()`]: crate::write::ZipFileWriter::write_entry_stream_with_options
+#[derive(Debug, Clone, Default)]
+pub struct EntryOptions {
+    pub(crate) compression: Option<Compression>,
+    #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    pub(crate) compression_level: Option<CompressionLevel>,
+    pub(crate) alignment: Option<u16>,
+    pub(crate) force_zip64: Option<bool>,
+    pub(crate) extra_fields: Vec<ExtraField>,
+    pub(crate) comment: Option<String>,
+    pub(crate) attributes: Option<Attributes>,
+    pub(crate) encryption: Option<Encryption>,
+}
+
+impl EntryOptions {
+    /// Constructs a new set of options with every option unset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the entry's compression method.
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sets the level of compression used when writing the entry.
+    ///
+    /// Note that this requires a compression feature to be enabled.
+    #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    pub fn compression_level(mut self, level: CompressionLevel) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Sets the alignment (in bytes) of the entry's data within the archive.
+    ///
+    /// See [`ZipEntryBuilder::alignment()`].
+    pub fn alignment(mut self, alignment: u16) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Sets whether the entry should always be written using ZIP64 structures, regardless of its size.
+    ///
+    /// See [`ZipEntryBuilder::force_zip64()`].
+    pub fn force_zip64(mut self, force: bool) -> Self {
+        self.force_zip64 = Some(force);
+        self
+    }
+
+    /// Appends a structured extra field to those already held by the entry.
+    pub fn add_extra_field(mut self, field: ExtraField) -> Self {
+        self.extra_fields.push(field);
+        self
+    }
+
+    /// Sets the entry's file comment.
+    pub fn comment(mut self, comment: String) -> Self {
+        self.comment = Some(comment);
+        self
+    }
+
+    /// Sets the entry's external file attribute from a typed set of attributes.
+    ///
+    /// The raw attribute is derived using the entry's attribute host compatibility (see
+    /// [`ZipEntryBuilder::attributes()`]).
+    pub fn attributes(mut self, attributes: Attributes) -> Self {
+        self.attributes = Some(attributes);
+        self
+    }
+
+    /// Sets the method used to encrypt the entry's data when written.
+    pub fn encryption(mut self, encryption: Encryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Applies every option which is set to the provided entry.
+    pub(crate) fn apply(self, entry: ZipEntry) -> ZipEntry {
+        let mut builder = ZipEntryBuilder::from(entry);
+
+        if let Some(compression) = self.compression {
+            builder = builder.compression(compression);
+        }
+
+        #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+        if let Some(level) = self.compression_level {
+            builder = builder.compression_level(level);
+        }
+
+        if let Some(alignment) = self.alignment {
+            builder = builder.alignment(alignment);
+        }
+
+        if let Some(force) = self.force_zip64 {
+            builder = builder.force_zip64(force);
+        }
+
+        for field in self.extra_fields {
+            builder = builder.add_extra_field(field);
+        }
+
+        if let Some(comment) = self.comment {
+            builder = builder.comment(comment);
+        }
+
+        if let Some(attributes) = self.attributes {
+            builder = builder.attributes(attributes);
+        }
+
+        if let Some(encryption) = self.encryption {
+            builder = builder.encryption(encryption);
+        }
+
+        builder.build()
+    }
+}
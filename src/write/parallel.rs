@@ -0,0 +1,172 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
+use crate::write::{entry_whole, ZipFileWriter};
+
+use std::collections::VecDeque;
+use std::future::Future;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
+
+type EncodedEntry = (ZipEntry, u32, u64, Vec<u8>);
+
+/// The encoding tasks of entries which are yet to be written, which are aborted if they're never awaited (eg. as an
+/// earlier entry failed).
+///
+/// Note that a blocking task which has already started can't be aborted, so runs to completion in the background.
+#[derive(Default)]
+struct PendingTasks(VecDeque<JoinHandle<Result<EncodedEntry>>>);
+
+impl Drop for PendingTasks {
+    fn drop(&mut self) {
+        self.0.iter().for_each(JoinHandle::abort);
+    }
+}
+
+impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
+    /// Writes a set of entries of known size and data, compressing up to `parallelism` of them concurrently.
+    ///
+    /// Each entry's data is compressed (and encrypted, if set) on tokio's blocking thread pool, whilst the results are
+    /// written to the archive in the order they were provided. As such, the output is identical to that of calling
+    /// [`ZipFileWriter::write_entry_whole()`] for each entry in turn.
+    ///
+    /// Note that this requires the `parallel` feature, and must be called from within a tokio runtime.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use async_zip::{Compression, ZipEntryBuilder, write::ZipFileWriter};
+    /// # use async_zip::error::Result;
+    /// #
+    /// # async fn run() -> Result<()> {
+    /// let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    /// let entries = (0..64).map(|index| {
+    ///     let builder = ZipEntryBuilder::new(format!("{index}.txt"), Compression::Stored);
+    ///     (builder, vec![b'a'; 4096])
+    /// });
+    ///
+    /// writer.write_entries_parallel(entries, 8).await?;
+    /// writer.close().await?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub async fn write_entries_parallel<I, E, D>(&mut self, entries: I, parallelism: usize) -> Result<()>
+    where
+        I: IntoIterator<Item = (E, D)>,
+        E: Into<ZipEntry>,
+        D: AsRef<[u8]> + Send + 'static,
+    {
+        let mut entries = entries.into_iter();
+        let mut pending = PendingTasks::default();
+
+        loop {
+            while pending.0.len() < parallelism.max(1) {
+                let Some((entry, data)) = entries.next() else { break };
+                let entry = self.prepare_entry(entry.into(), Some(data.as_ref()))?;
+                pending.0.push_back(encode_blocking(entry, data));
+            }
+
+            let Some(task) = pending.0.pop_front() else { break };
+            self.write_encoded_task(task).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a set of entries whose data is produced lazily, driving up to `parallelism` of the producers (and
+    /// compressing their data) concurrently.
+    ///
+    /// Each producer is only called once there's capacity for its entry, and returns a future which resolves to a
+    /// reader of the entry's data. That data is read into memory in full and then compressed on tokio's blocking thread
+    /// pool, so at most `parallelism` entries' data is held in memory at once. As with
+    /// [`ZipFileWriter::write_entries_parallel()`], entries are written to the archive in the order they were provided.
+    ///
+    /// If a producer (or a read from its reader) fails, [`ZipError::UpstreamReadError`] is returned.
+    ///
+    /// Note that this requires the `parallel` feature, and must be called from within a tokio runtime.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use async_zip::{Compression, ZipEntryBuilder, write::ZipFileWriter};
+    /// # use async_zip::error::Result;
+    /// #
+    /// # async fn run() -> Result<()> {
+    /// let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    /// let entries = ["foo.txt", "bar.txt"].map(|path| {
+    ///     let builder = ZipEntryBuilder::new(path.to_string(), Compression::Stored);
+    ///     (builder, move || tokio::fs::File::open(path))
+    /// });
+    ///
+    /// writer.write_entries_deferred(entries, 8).await?;
+    /// writer.close().await?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub async fn write_entries_deferred<I, E, P, F, R>(&mut self, entries: I, parallelism: usize) -> Result<()>
+    where
+        I: IntoIterator<Item = (E, P)>,
+        E: Into<ZipEntry>,
+        P: FnOnce() -> F,
+        F: Future<Output = std::io::Result<R>> + Send + 'static,
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let mut entries = entries.into_iter();
+        let mut pending = PendingTasks::default();
+
+        loop {
+            while pending.0.len() < parallelism.max(1) {
+                let Some((entry, producer)) = entries.next() else { break };
+                let entry = self.prepare_entry(entry.into(), None)?;
+                let source = producer();
+
+                pending.0.push_back(tokio::spawn(async move {
+                    let mut data = Vec::new();
+                    source.await?.read_to_end(&mut data).await?;
+
+                    match encode_blocking(entry, data).await {
+                        Ok(result) => result,
+                        Err(err) => std::panic::resume_unwind(err.into_panic()),
+                    }
+                }));
+            }
+
+            let Some(task) = pending.0.pop_front() else { break };
+            self.write_encoded_task(task).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Awaits an encoding task and writes its entry, propagating any panic from within the task.
+    async fn write_encoded_task(&mut self, task: JoinHandle<Result<EncodedEntry>>) -> Result<()> {
+        let (entry, crc, uncompressed_size, encoded_data) = match task.await {
+            Ok(result) => result?,
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
+        };
+
+        entry_whole::write_encoded(self, entry, crc, uncompressed_size, &encoded_data).await
+    }
+}
+
+/// Compresses (and encrypts, if set) an entry's data on tokio's blocking thread pool.
+fn encode_blocking<D>(entry: ZipEntry, data: D) -> JoinHandle<Result<EncodedEntry>>
+where
+    D: AsRef<[u8]> + Send + 'static,
+{
+    let handle = Handle::current();
+
+    tokio::task::spawn_blocking(move || {
+        // Encoding into memory never yields, so this only blocks whilst the data is being compressed.
+        handle.block_on(async move {
+            let (crc, encoded_data) = entry_whole::encode(&entry, data.as_ref()).await?;
+            let encoded_data = encoded_data.into_owned();
+
+            Ok::<_, ZipError>((entry, crc, data.as_ref().len() as u64, encoded_data))
+        })
+    })
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::builder::ZipEntryBuilder;
+use crate::error::{Result, ZipError};
+use crate::read::io::entry::ZipEntryReader;
+use crate::spec::compression::{Compression, CompressionLevel};
+use crate::write::ZipFileWriter;
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, BufReader};
+
+/// Rewrites a ZIP file with every entry compressed using the provided method & level, writing the result to the
+/// provided writer.
+///
+/// Each entry's data is decompressed and recompressed as it's streamed between the two archives (so is never held in
+/// memory in full), with its CRC32 value being verified along the way. Entries which already use the target method,
+/// as well as directories, are instead copied verbatim (see [`ZipFileWriter::copy_entry_raw()`]). All other entry
+/// metadata and the ZIP file comment are carried over.
+///
+/// The level only applies where a compression feature is enabled.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::write::recompress;
+/// # use async_zip::{Compression, CompressionLevel};
+/// # use async_zip::error::Result;
+/// # use tokio::fs::File;
+/// #
+/// # #[cfg(feature = "zstd")]
+/// async fn run() -> Result<()> {
+///     let input = File::open("./foo.zip").await?;
+///     recompress(input, File::create("./bar.zip").await?, Compression::Zstd, CompressionLevel::Best).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn recompress<R, W>(mut input: R, output: W, compression: Compression, level: CompressionLevel) -> Result<W>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let file = crate::read::file(&mut input).await?;
+    let mut writer = ZipFileWriter::new(output);
+
+    for (index, stored_entry) in file.entries().iter().enumerate() {
+        let entry = stored_entry.entry();
+
+        if entry.compression() == compression || entry.dir() {
+            writer.copy_entry_raw(stored_entry, None, &mut input).await?;
+            continue;
+        }
+
+        let context = |err: ZipError| err.in_entry(Some(index), entry.filename());
+        let mut reader = BufReader::new(&mut input);
+        stored_entry.seek_to_readable_data(&mut reader).await.map_err(context)?;
+
+        let mut entry_reader = ZipEntryReader::new_with_borrow(reader, entry.compression(), entry.compressed_size());
+        let builder = ZipEntryBuilder::from(entry.clone()).compression(compression);
+
+        #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+        let builder = builder.compression_level(level);
+
+        writer.write_entry_from_reader(builder, &mut entry_reader).await?;
+
+        if entry_reader.crc32() != entry.crc32() {
+            return Err(context(ZipError::CRC32CheckError));
+        }
+    }
+
+    #[cfg(not(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz")))]
+    let _ = level;
+
+    writer.comment(file.comment().to_string());
+    writer.close().await
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::{Result, ZipError};
+use crate::write::EntryStreamWriter;
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use bytes::{Buf, Bytes};
+use futures_sink::Sink;
+use tokio::io::AsyncWrite;
+
+/// A [`Sink`] of [`Bytes`] which writes its items as the data of a streamed entry.
+///
+/// This allows the output of channel or stream pipelines (eg. a HTTP request body) to be forwarded directly into an
+/// entry. Closing the sink only flushes the written data, so [`EntrySink::close()`] must still be called afterwards to
+/// complete the entry.
+///
+/// Note that this requires the `futures` feature.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::{Compression, ZipEntryBuilder, write::ZipFileWriter};
+/// # use async_zip::error::{Result, ZipError};
+/// # use bytes::Bytes;
+/// # use futures::{stream, StreamExt};
+/// #
+/// # async fn run() -> Result<()> {
+/// let mut writer = ZipFileWriter::new(Vec::new());
+/// let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+/// let mut sink = writer.write_entry_stream(entry).await?.into_sink();
+///
+/// let body = stream::iter([Bytes::from_static(b"foo "), Bytes::from_static(b"bar")]).map(Ok::<_, ZipError>);
+/// body.forward(&mut sink).await?;
+///
+/// sink.close().await?;
+/// writer.close().await?;
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+pub struct EntrySink<'b, W: AsyncWrite + Unpin> {
+    writer: EntryStreamWriter<'b, W>,
+    pending: Bytes,
+}
+
+impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
+    /// Converts this entry writer into a [`Sink`] of [`Bytes`].
+    ///
+    /// Note that this requires the `futures` feature.
+    pub fn into_sink(self) -> EntrySink<'b, W> {
+        EntrySink { writer: self, pending: Bytes::new() }
+    }
+}
+
+impl<'b, W: AsyncWrite + Unpin> EntrySink<'b, W> {
+    /// Writes any remaining buffered data and completes the entry (see [`EntryStreamWriter::close()`]).
+    pub async fn close(mut self) -> Result<()> {
+        std::future::poll_fn(|cx| self.poll_write_pending(cx)).await?;
+        self.writer.close().await
+    }
+
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while !self.pending.is_empty() {
+            let written = ready!(Pin::new(&mut self.writer).poll_write(cx, &self.pending))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(ZipError::UpstreamReadError(std::io::ErrorKind::WriteZero.into())));
+            }
+
+            self.pending.advance(written);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'b, W: AsyncWrite + Unpin> Sink<Bytes> for EntrySink<'b, W> {
+    type Error = ZipError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_write_pending(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Bytes) -> Result<()> {
+        self.get_mut().pending = item;
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        Poll::Ready(ready!(Pin::new(&mut this.writer).poll_flush(cx)).map_err(ZipError::from))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_flush(cx)
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP writer which splits its output into volumes of a maximum size (ie. a split archive).
+//!
+//! Each volume is requested from a factory as it's needed, with volumes being at most the configured size. As per the
+//! specification, the first volume starts with a split archive signature, and every header records the number of the
+//! volume (disk) it was written to along with its offset within that volume. The end of central directory records are
+//! never split across volumes.
+//!
+//! The final volume isn't known until the writer has been closed, so volumes should be created with the `.z01`,
+//! `.z02` (etc.) extensions, and the final volume then renamed to use the `.zip` extension (as extractors expect).
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::{Compression, ZipEntryBuilder, write::split::SplitZipFileWriter};
+//! # use async_zip::error::Result;
+//! #
+//! async fn run() -> Result<()> {
+//!     let volume = |index: u32| std::fs::File::create(format!("./foo.z{:02}", index + 1)).map(tokio::fs::File::from_std);
+//!     let mut writer = SplitZipFileWriter::new(64 * 1024 * 1024, volume).await?;
+//!
+//!     let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+//!     writer.write_entry_whole(entry, b"bar").await?;
+//!
+//!     let volumes = writer.close().await?;
+//!     tokio::fs::rename(format!("./foo.z{volumes:02}"), "./foo.zip").await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
+use crate::spec::consts::{
+    DATA_DESCRIPTOR_SIGNATURE, EOCDR_LENGTH, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDR_LENGTH,
+};
+use crate::write::{EntryStreamWriter, ZipFileWriter};
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+
+/// The minimum volume size permitted by the specification, equal to 64KiB.
+pub const MIN_VOLUME_SIZE: u64 = 64 * 1024;
+
+/// The layout of a split archive's volumes, used to locate a header within its volume.
+#[derive(Clone, Copy)]
+pub(crate) struct VolumeLayout {
+    volume_size: u64,
+    /// The physical offset at which the final volume was started early (so the end records aren't split), if any.
+    tail_start: Option<u64>,
+}
+
+impl VolumeLayout {
+    /// Returns the disk number & disk-relative offset of an offset within the ZIP writer's output.
+    pub(crate) fn locate(&self, offset: u64) -> (u32, u64) {
+        // The split archive signature precedes all output within the first volume.
+        let offset = offset + SIGNATURE_LENGTH as u64;
+
+        match self.tail_start {
+            Some(start) if offset >= start => ((start / self.volume_size) as u32 + 1, offset - start),
+            _ => ((offset / self.volume_size) as u32, offset % self.volume_size),
+        }
+    }
+}
+
+/// A writer which splits the data written to it across volumes of a maximum size.
+///
+/// This cannot be manually constructed; instead, see [`SplitZipFileWriter`].
+pub struct SplitWriter<W, F> {
+    volume: Option<W>,
+    closing: Option<W>,
+    factory: F,
+    index: u32,
+    layout: VolumeLayout,
+    /// The number of bytes written across all volumes (including the split archive signature).
+    offset: u64,
+    /// The number of bytes written to the current volume.
+    written: u64,
+    /// Whether opening a volume has failed, after which every operation fails as there's no volume to write to.
+    failed: bool,
+}
+
+impl<W, F> SplitWriter<W, F>
+where
+    W: AsyncWrite + Unpin,
+    F: FnMut(u32) -> std::io::Result<W> + Unpin,
+{
+    /// Returns the physical offset at which the current volume must end.
+    fn volume_end(&self) -> u64 {
+        let end = self.offset - self.written + self.layout.volume_size;
+
+        match self.layout.tail_start {
+            Some(start) if start > self.offset - self.written && start < end => start,
+            _ => end,
+        }
+    }
+
+    /// Drives the shutdown of a finished volume, and opens the next volume once complete.
+    fn poll_roll(&mut self, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        if self.failed {
+            return Poll::Ready(Err(std::io::Error::other("a previous volume couldn't be opened")));
+        }
+
+        if let Some(closing) = self.closing.as_mut() {
+            ready!(Pin::new(closing).poll_shutdown(c))?;
+            self.closing = None;
+            self.index += 1;
+
+            match (self.factory)(self.index) {
+                Ok(volume) => self.volume = Some(volume),
+                Err(err) => {
+                    self.failed = true;
+                    return Poll::Ready(Err(err));
+                }
+            }
+            self.written = 0;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W, F> AsyncWrite for SplitWriter<W, F>
+where
+    W: AsyncWrite + Unpin,
+    F: FnMut(u32) -> std::io::Result<W> + Unpin,
+{
+    fn poll_write(self: Pin<&mut Self>, c: &mut Context<'_>, b: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            ready!(this.poll_roll(c))?;
+            let remaining = this.volume_end() - this.offset;
+
+            if remaining == 0 {
+                this.closing = this.volume.take();
+                continue;
+            }
+
+            let length = std::cmp::min(b.len() as u64, remaining) as usize;
+            let volume = this.volume.as_mut().expect("volume opened after roll");
+            let written = ready!(Pin::new(volume).poll_write(c, &b[..length]))?;
+
+            this.offset += written as u64;
+            this.written += written as u64;
+            return Poll::Ready(Ok(written));
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_roll(c))?;
+        Pin::new(this.volume.as_mut().expect("volume opened after roll")).poll_flush(c)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_roll(c))?;
+        Pin::new(this.volume.as_mut().expect("volume opened after roll")).poll_shutdown(c)
+    }
+}
+
+/// A ZIP writer which splits its output into volumes of a maximum size.
+///
+/// See the [module-level docs](.) for more information.
+pub struct SplitZipFileWriter<W, F>
+where
+    W: AsyncWrite + Unpin,
+    F: FnMut(u32) -> std::io::Result<W> + Unpin,
+{
+    writer: ZipFileWriter<SplitWriter<W, F>>,
+}
+
+impl<W, F> SplitZipFileWriter<W, F>
+where
+    W: AsyncWrite + Unpin,
+    F: FnMut(u32) -> std::io::Result<W> + Unpin,
+{
+    /// Constructs a new split ZIP writer from a maximum volume size and a factory which opens each volume.
+    ///
+    /// The factory is provided with the zero-based index of each volume, and is called once the previous volume has
+    /// been filled (and shut down). Returns [`ZipError::InvalidVolumeSize`] if the volume size is smaller than
+    /// [`MIN_VOLUME_SIZE`] or larger than 4GiB.
+    pub async fn new(volume_size: u64, mut factory: F) -> Result<Self> {
+        if !(MIN_VOLUME_SIZE..=u32::MAX.into()).contains(&volume_size) {
+            return Err(ZipError::InvalidVolumeSize(volume_size));
+        }
+
+        let mut volume = factory(0)?;
+        volume.write_all(&DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
+
+        let layout = VolumeLayout { volume_size, tail_start: None };
+        let offset = SIGNATURE_LENGTH as u64;
+        let writer = SplitWriter {
+            volume: Some(volume),
+            closing: None,
+            factory,
+            index: 0,
+            layout,
+            offset,
+            written: offset,
+            failed: false,
+        };
+
+        Ok(Self { writer: ZipFileWriter::new(writer) })
+    }
+
+    /// Write a new ZIP entry of known size and data.
+    pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
+        self.writer.write_entry_whole(entry, data).await
+    }
+
+    /// Write a directory entry, so that the directory is recreated on extraction even if it holds no other entries.
+    ///
+    /// See [`ZipFileWriter::write_dir_entry()`].
+    pub async fn write_dir_entry<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<()> {
+        self.writer.write_dir_entry(entry).await
+    }
+
+    /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
+    pub async fn write_entry_stream<E: Into<ZipEntry>>(
+        &mut self,
+        entry: E,
+    ) -> Result<EntryStreamWriter<'_, SplitWriter<W, F>>> {
+        self.writer.write_entry_stream(entry).await
+    }
+
+    /// Write an entry by streaming its data from a reader until EOF (ie. using a data descriptor).
+    ///
+    /// See [`ZipFileWriter::write_entry_from_reader()`].
+    pub async fn write_entry_from_reader<E, R>(&mut self, entry: E, reader: R) -> Result<u64>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        self.writer.write_entry_from_reader(entry, reader).await
+    }
+
+    /// Set the ZIP file comment.
+    pub fn comment(&mut self, comment: String) {
+        self.writer.comment(comment);
+    }
+
+    /// Consumes this ZIP writer, completes all closing tasks, and shuts down the final volume.
+    ///
+    /// Returns the number of volumes written.
+    pub async fn close(mut self) -> Result<u32> {
+        let cd_offset = self.writer.offset();
+        let cd_size: u64 = self.writer.cd_entries.iter().map(|cd_entry| cd_entry.record_length()).sum();
+        let num_of_entries = self.writer.cd_entries.len() as u64;
+
+        // The end records are started within a new volume if they'd otherwise be split. As the central directory is
+        // always located within a volume, its offset never requires a ZIP64 EOCDR.
+        let mut tail_length = (SIGNATURE_LENGTH + EOCDR_LENGTH + self.writer.comment_length()) as u64;
+        if crate::write::needs_zip64(num_of_entries, cd_size, 0) {
+            tail_length += (2 * SIGNATURE_LENGTH + ZIP64_EOCDR_LENGTH + ZIP64_EOCDL_LENGTH) as u64;
+        }
+
+        let volume_size = self.writer.writer.inner_mut().layout.volume_size;
+        let tail_start = cd_offset + cd_size + SIGNATURE_LENGTH as u64;
+        if tail_start / volume_size != (tail_start + tail_length - 1) / volume_size {
+            self.writer.writer.inner_mut().layout.tail_start = Some(tail_start);
+        }
+
+        let layout = self.writer.writer.inner_mut().layout;
+        let (mut writer, _) = self.writer.close_with_layout(Some(layout)).await?;
+        writer.shutdown().await?;
+
+        Ok(writer.index + 1)
+    }
+}